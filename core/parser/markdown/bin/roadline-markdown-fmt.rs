@@ -0,0 +1,45 @@
+use clap::Parser;
+use roadline_parser_markdown::RoadmapFormatter;
+use std::path::PathBuf;
+
+/// Format roadmap markdown documents in place.
+#[derive(Debug, Parser)]
+#[clap(name = "roadline-markdown-fmt")]
+struct Args {
+	/// The roadmap documents to format.
+	#[clap(required = true)]
+	paths: Vec<PathBuf>,
+
+	/// Only check whether the documents are formatted; exits with an error if any are not.
+	#[clap(long)]
+	check: bool,
+}
+
+fn main() -> Result<(), anyhow::Error> {
+	let args = Args::parse();
+	let formatter = RoadmapFormatter::new();
+	let mut unformatted = Vec::new();
+
+	for path in &args.paths {
+		let content = std::fs::read_to_string(path)?;
+		let formatted = formatter.format(&content)?;
+
+		if formatted == content {
+			continue;
+		}
+
+		if args.check {
+			println!("{}", path.display());
+			unformatted.push(path);
+		} else {
+			std::fs::write(path, formatted)?;
+			println!("formatted {}", path.display());
+		}
+	}
+
+	if !unformatted.is_empty() {
+		anyhow::bail!("{} document(s) are not formatted", unformatted.len());
+	}
+
+	Ok(())
+}
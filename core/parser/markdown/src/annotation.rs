@@ -0,0 +1,146 @@
+//! Annotation parsing functionality for markdown roadmap documents.
+//!
+//! GitHub callouts such as `> [!NOTE]` become [`Annotation`]s on their task. A callout
+//! whose first paragraph is a lone link, as in `**[[Liam Monninger](mailto:liam@ramate.io)]**`,
+//! is attributed to that person.
+
+use super::rich_text::RichTextParser;
+use roadline_util::markdown::{Block, Inline, RichText};
+use roadline_util::task::subtask::Lead;
+use roadline_util::task::Annotation;
+
+/// Parser for the callouts of a task section.
+#[derive(Debug, Clone)]
+pub struct AnnotationParser {
+	rich_text_parser: RichTextParser,
+}
+
+impl Default for AnnotationParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl AnnotationParser {
+	/// Create a new annotation parser.
+	pub fn new() -> Self {
+		Self { rich_text_parser: RichTextParser::new() }
+	}
+
+	/// Parse every callout in the content of a task section, including its subsections.
+	pub fn parse(&self, content: &[String]) -> Vec<Annotation> {
+		let mut annotations = Vec::new();
+		Self::collect(self.rich_text_parser.parse(&content.join("\n")).blocks, &mut annotations);
+		annotations
+	}
+
+	/// Collect the callouts among blocks, looking into lists and plain quotes.
+	fn collect(blocks: Vec<Block>, annotations: &mut Vec<Annotation>) {
+		for block in blocks {
+			match block {
+				Block::Quote { callout: Some(kind), mut blocks } => {
+					let author = blocks.first().and_then(Self::attribution);
+					if author.is_some() {
+						blocks.remove(0);
+					}
+					annotations.push(Annotation::new(kind, author, RichText::new(blocks)));
+				}
+				Block::Quote { callout: None, blocks } => Self::collect(blocks, annotations),
+				Block::List { items, .. } => {
+					Self::collect(items.into_iter().flatten().collect(), annotations);
+				}
+				_ => {}
+			}
+		}
+	}
+
+	/// The person a paragraph consisting of a single link refers to.
+	///
+	/// Brackets, emphasis and whitespace around the link are allowed.
+	fn attribution(block: &Block) -> Option<Lead> {
+		let Block::Paragraph(inlines) = block else {
+			return None;
+		};
+
+		let mut link = None;
+		let mut pending: Vec<&Inline> = inlines.iter().collect();
+		while let Some(inline) = pending.pop() {
+			match inline {
+				Inline::Link { destination, content } if link.is_none() => {
+					link = Some((destination, content));
+				}
+				Inline::Strong(content) | Inline::Emphasis(content) => pending.extend(content),
+				Inline::Text(text) if text.chars().all(|c| "[]*_ ".contains(c)) => {}
+				_ => return None,
+			}
+		}
+
+		let (destination, content) = link?;
+		let name: String = content.iter().map(Inline::plain_text).collect();
+
+		Some(Lead::new(
+			name.trim().to_string(),
+			destination.trim().trim_start_matches("mailto:").to_string(),
+		))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use roadline_util::markdown::CalloutKind;
+
+	fn lines(content: &str) -> Vec<String> {
+		content.lines().map(str::to_string).collect()
+	}
+
+	#[test]
+	fn test_parse_attributed_callouts() {
+		let content = lines(
+			"> [!IMPORTANT]
+> **T1** focuses on readying OAC for validation.
+
+- **Starts:** T1 + 0 months
+
+> [!NOTE]
+> **[[Liam Monninger](mailto:liam@ramate.io)]**
+>
+> We do not underscore this as a separate point.
+
+#### T1.1: Draft
+
+> [!WARNING]
+> **[[Liam Monninger](mailto:liam@ramate.io)]
+>
+> This may slip.
+
+> Just a quote.
+",
+		);
+		let annotations = AnnotationParser::new().parse(&content);
+		let liam = Lead::new("Liam Monninger".to_string(), "liam@ramate.io".to_string());
+
+		let summary: Vec<_> = annotations
+			.iter()
+			.map(|annotation| {
+				(annotation.kind, annotation.author.clone(), annotation.body.plain_text())
+			})
+			.collect();
+		assert_eq!(
+			summary,
+			vec![
+				(
+					CalloutKind::Important,
+					None,
+					"T1 focuses on readying OAC for validation.".to_string()
+				),
+				(
+					CalloutKind::Note,
+					Some(liam.clone()),
+					"We do not underscore this as a separate point.".to_string()
+				),
+				(CalloutKind::Warning, Some(liam), "This may slip.".to_string()),
+			]
+		);
+	}
+}
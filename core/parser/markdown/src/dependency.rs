@@ -1,16 +1,19 @@
 //! Dependency parsing functionality for markdown roadmap documents.
 
 use super::error::MarkdownParseError;
-use super::task::TaskSection;
-use roadline_util::task::Id as TaskId;
+use super::link::LinkParser;
+use super::task::{parse_task_reference, TaskSection};
+use roadline_util::task::subtask::Id as SubtaskId;
+use roadline_util::task::{Id as TaskId, SubtaskDependency};
 
 /// Parser for task dependencies in markdown documents.
 ///
 /// This parser handles the parsing of dependency references from the
-/// "Depends-on:" field in task sections.
+/// "Depends-on:" field in task sections. References to whole tasks, e.g. `[T1](#t1-title)`,
+/// and to subtasks, e.g. `[T1.3](#t13-title)`, are parsed separately.
 #[derive(Debug, Clone)]
 pub struct DependencyParser {
-	// Configuration for dependency parsing
+	link_parser: LinkParser,
 }
 
 impl Default for DependencyParser {
@@ -22,7 +25,7 @@ impl Default for DependencyParser {
 impl DependencyParser {
 	/// Create a new dependency parser.
 	pub fn new() -> Self {
-		Self {}
+		Self { link_parser: LinkParser::new() }
 	}
 
 	/// Parse dependencies from a task section.
@@ -46,6 +49,28 @@ impl DependencyParser {
 		Ok(None)
 	}
 
+	/// Parse the subtask dependencies of a task section, e.g. `[T1.3](#t13-title)`.
+	pub fn parse_subtask_dependencies(
+		&self,
+		section: &TaskSection,
+	) -> Result<Vec<SubtaskDependency>, MarkdownParseError> {
+		for line in &section.content {
+			if let Some((field, value)) = self.parse_field_line(line.trim()) {
+				if field == "Depends-on" {
+					return value
+						.split(',')
+						.map(str::trim)
+						.filter(|dep| !self.is_external_dependency(dep))
+						.filter(|dep| Self::is_subtask_dependency(dep))
+						.map(|dep| self.parse_single_subtask_dependency(dep))
+						.collect();
+				}
+			}
+		}
+
+		Ok(Vec::new())
+	}
+
 	/// Parse a field line in the format "- **Field:** Value".
 	fn parse_field_line(&self, line: &str) -> Option<(String, String)> {
 		if !line.starts_with("- **") || !line.contains(":**") {
@@ -72,6 +97,9 @@ impl DependencyParser {
 	/// - Single dependency: "[T1](#t1-title)"
 	/// - Multiple dependencies: "[T1](#t1-title), [T2](#t2-title)"
 	/// - Empty set: "$\\emptyset$"
+	///
+	/// Links to tasks in other documents, e.g. "[OROAD-5 T3](../oroad-005/README.md#t3-title)",
+	/// are left to the [`RoadmapLoader`](crate::loader::RoadmapLoader).
 	fn parse_dependency_value(
 		&self,
 		value: &str,
@@ -84,15 +112,56 @@ impl DependencyParser {
 		}
 
 		// Split by commas and parse each dependency
-		let dependencies: Result<Vec<TaskId>, _> =
-			value.split(',').map(|dep| self.parse_single_dependency(dep.trim())).collect();
+		let dependencies: Result<Vec<TaskId>, _> = value
+			.split(',')
+			.map(str::trim)
+			.filter(|dep| !self.is_external_dependency(dep) && !Self::is_subtask_dependency(dep))
+			.map(|dep| self.parse_single_dependency(dep))
+			.collect();
 
 		Ok(Some(dependencies?))
 	}
 
+	/// Whether a dependency reference links to a task in another document.
+	fn is_external_dependency(&self, dep_str: &str) -> bool {
+		self.link_parser
+			.find_links(dep_str)
+			.first()
+			.is_some_and(|link| link.is_document_link())
+	}
+
+	/// Whether a dependency reference names a subtask, e.g. "[T1.3](#t13-title)".
+	fn is_subtask_dependency(dep_str: &str) -> bool {
+		dep_str
+			.strip_prefix("[T")
+			.and_then(|rest| rest.split_once(']'))
+			.is_some_and(|(id, _)| id.contains('.'))
+	}
+
+	/// Parse a single subtask dependency reference.
+	///
+	/// Expected format: "[T1.3](#t13-title)"
+	fn parse_single_subtask_dependency(
+		&self,
+		dep_str: &str,
+	) -> Result<SubtaskDependency, MarkdownParseError> {
+		let invalid =
+			|| MarkdownParseError::InvalidDependencyReference { reference: dep_str.to_string() };
+
+		let (id, _) = dep_str
+			.strip_prefix("[T")
+			.and_then(|rest| rest.split_once(']'))
+			.ok_or_else(invalid)?;
+		let (task, subtask) = id.split_once('.').ok_or_else(invalid)?;
+		let task: u8 = task.parse().map_err(|_| invalid())?;
+		let subtask: u8 = subtask.parse().map_err(|_| invalid())?;
+
+		Ok(SubtaskDependency::new(TaskId::new(task), SubtaskId::new(subtask)))
+	}
+
 	/// Parse a single dependency reference.
 	///
-	/// Expected format: "[T1](#t1-title)"
+	/// Expected format: "[T1](#t1-title)", or "[M1](#m1-title)" for a milestone
 	fn parse_single_dependency(&self, dep_str: &str) -> Result<TaskId, MarkdownParseError> {
 		if !dep_str.starts_with('[') || !dep_str.contains(']') {
 			return Err(MarkdownParseError::InvalidDependencyReference {
@@ -108,23 +177,11 @@ impl DependencyParser {
 					reference: dep_str.to_string(),
 				})?;
 
-		// Extract the task ID (e.g., "T1")
+		// Extract and parse the task ID (e.g., "T1" or "M1")
 		let task_id = &dep_str[1..end_bracket];
-
-		// Validate that it's a valid task ID format
-		if !task_id.starts_with('T') {
-			return Err(MarkdownParseError::InvalidDependencyReference {
-				reference: dep_str.to_string(),
-			});
-		}
-
-		// Parse the number part
-		let number_str = &task_id[1..];
-		let number: u8 = number_str.parse().map_err(|_| {
+		parse_task_reference(task_id).map(|(_, id)| id).ok_or_else(|| {
 			MarkdownParseError::InvalidDependencyReference { reference: dep_str.to_string() }
-		})?;
-
-		Ok(TaskId::new(number))
+		})
 	}
 
 	/// Parse dependencies from a raw markdown line.
@@ -188,6 +245,43 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_parse_milestone_dependency() -> Result<(), MarkdownParseError> {
+		let parser = DependencyParser::new();
+		let result = parser.parse_dependency_value("[T1](#t1-title), [M2](#m2-launch)")?;
+		assert_eq!(result, Some(vec![TaskId::new(1), TaskId::new(2)]));
+		Ok(())
+	}
+
+	#[test]
+	fn test_skip_external_dependencies() -> Result<(), MarkdownParseError> {
+		let parser = DependencyParser::new();
+		let result = parser.parse_dependency_value(
+			"[T1](#t1-title), [OROAD-5 T3](../oroad-005/README.md#t3-title)",
+		)?;
+		assert_eq!(result, Some(vec![TaskId::new(1)]));
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_subtask_dependencies() -> Result<(), MarkdownParseError> {
+		let parser = DependencyParser::new();
+		let section = TaskSection {
+			header: "### T4: Fourth".to_string(),
+			content: vec!["- **Depends-on:** [T2](#t2-title), [T1.3](#t13-title)".to_string()],
+			line_number: 1,
+		};
+
+		assert_eq!(parser.parse_dependencies(&section)?, Some(vec![TaskId::new(2)]));
+		assert_eq!(
+			parser.parse_subtask_dependencies(&section)?,
+			vec![SubtaskDependency::new(TaskId::new(1), SubtaskId::new(3))]
+		);
+		assert!(parser.parse_dependency_value("[T1.x](#t1x-title)").is_ok());
+		assert!(parser.parse_single_subtask_dependency("[T1.x](#t1x-title)").is_err());
+		Ok(())
+	}
+
 	#[test]
 	fn test_parse_dependency_line() -> Result<(), MarkdownParseError> {
 		let parser = DependencyParser::new();
@@ -0,0 +1,492 @@
+//! Document-level metadata for markdown roadmap documents.
+//!
+//! Besides its `### T` sections, a roadmap carries a title, its authors, the leads of
+//! all of its tasks, and optionally a front matter block declaring when it starts.
+
+use super::error::MarkdownParseError;
+use super::lead::LeadParser;
+use super::link::{DocumentLink, LinkParser};
+use super::outline;
+use chrono::{DateTime, NaiveDate};
+use roadline_representation_core::range_algebra::Date;
+use roadline_representation_core::roadline::RoadlineBuilder;
+use roadline_representation_core::timezone::Timezone;
+use roadline_util::task::subtask::Lead;
+use roadline_util::task::Task;
+use std::collections::BTreeMap;
+
+/// The front matter of a roadmap document.
+///
+/// Front matter is either a YAML block delimited by `---` lines or a TOML block
+/// delimited by `+++` lines at the very start of the document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FrontMatter {
+	/// The date the roadmap starts, declared as `start: 2025-01-06`.
+	pub start: Option<NaiveDate>,
+	/// The timezone the roadmap is planned in, e.g. `Europe/Berlin`.
+	pub timezone: Option<Timezone>,
+	/// Every other key, with its value rendered as a string.
+	pub fields: BTreeMap<String, String>,
+}
+
+/// How the `All leads` list of a document compares to the leads of its tasks.
+///
+/// Leads are matched by email, or by name when either of them has no email.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LeadCheck {
+	/// Leads of tasks or subtasks which are missing from `All leads`.
+	pub unlisted: Vec<Lead>,
+	/// Entries of `All leads` which lead no task or subtask.
+	pub unused: Vec<Lead>,
+}
+
+impl LeadCheck {
+	/// Whether `All leads` lists exactly the leads in use.
+	pub fn is_consistent(&self) -> bool {
+		self.unlisted.is_empty() && self.unused.is_empty()
+	}
+}
+
+/// A parsed roadmap document.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoadmapDocument {
+	/// The text of the top-level `# ` heading, e.g. `OROAD-0: The Attempt`.
+	pub title: Option<String>,
+	/// The people listed in the `Authors` field.
+	pub authors: Vec<Lead>,
+	/// The people listed in the `All leads` field.
+	pub all_leads: Vec<Lead>,
+	/// The date the roadmap starts.
+	///
+	/// Taken from the front matter, or else from a `Start` field outside of the task sections.
+	pub start: Option<NaiveDate>,
+	/// The front matter of the document, if any.
+	pub front_matter: FrontMatter,
+	/// The tasks of the document.
+	pub tasks: Vec<Task>,
+	/// Links from the tasks to other roadmap documents, in source order.
+	pub links: Vec<DocumentLink>,
+}
+
+impl RoadmapDocument {
+	/// The root date for range computations, i.e. midnight on the start date in the timezone
+	/// of the roadmap.
+	pub fn root_date(&self) -> Option<Date> {
+		self.start.map(|start| self.timezone_or_utc().midnight(start))
+	}
+
+	/// The name of the timezone declared in the front matter.
+	pub fn timezone(&self) -> Option<&str> {
+		self.front_matter.timezone.map(|timezone| timezone.name())
+	}
+
+	/// The timezone the roadmap is planned in, UTC unless the front matter declares one.
+	pub fn timezone_or_utc(&self) -> Timezone {
+		self.front_matter.timezone.unwrap_or_default()
+	}
+
+	/// A roadline builder with the tasks of the document, starting on its start date in its
+	/// timezone. Documents without a start date fall back to the default root date of
+	/// [`RoadlineBuilder::new`].
+	pub fn into_builder(self) -> Result<RoadlineBuilder, MarkdownParseError> {
+		let mut builder = match self.root_date() {
+			Some(root_date) => RoadlineBuilder::with_start_date(root_date),
+			None => RoadlineBuilder::new(),
+		}
+		.with_timezone(self.timezone_or_utc());
+
+		for task in self.tasks {
+			builder.add_task(task)?;
+		}
+
+		Ok(builder)
+	}
+
+	/// The leads of all tasks and subtasks, in order of first appearance.
+	pub fn used_leads(&self) -> Vec<Lead> {
+		let mut leads: Vec<Lead> = Vec::new();
+		let used = self.tasks.iter().flat_map(|task| {
+			task.leads()
+				.iter()
+				.chain(task.subtasks().into_iter().flat_map(|subtask| subtask.leads()))
+		});
+
+		for lead in used {
+			if !lead.is_unknown() && !leads.iter().any(|known| Self::same_lead(known, lead)) {
+				leads.push(lead.clone());
+			}
+		}
+
+		leads
+	}
+
+	/// Cross-check the `All leads` list against the leads actually in use.
+	pub fn check_leads(&self) -> LeadCheck {
+		let used = self.used_leads();
+
+		LeadCheck {
+			unlisted: used
+				.iter()
+				.filter(|lead| !self.all_leads.iter().any(|listed| Self::same_lead(listed, lead)))
+				.cloned()
+				.collect(),
+			unused: self
+				.all_leads
+				.iter()
+				.filter(|listed| !used.iter().any(|lead| Self::same_lead(listed, lead)))
+				.cloned()
+				.collect(),
+		}
+	}
+
+	/// Whether two entries refer to the same person.
+	fn same_lead(a: &Lead, b: &Lead) -> bool {
+		if a.email().is_empty() || b.email().is_empty() {
+			a.name() == b.name()
+		} else {
+			a.email().eq_ignore_ascii_case(b.email())
+		}
+	}
+}
+
+/// Parser for document-level metadata in markdown roadmap documents.
+///
+/// This parser reads the front matter, the title and the fields which appear outside
+/// of the `### T` task sections.
+#[derive(Debug, Clone)]
+pub struct DocumentParser {
+	lead_parser: LeadParser,
+	link_parser: LinkParser,
+}
+
+impl Default for DocumentParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl DocumentParser {
+	/// Create a new document parser.
+	pub fn new() -> Self {
+		Self { lead_parser: LeadParser::new(), link_parser: LinkParser::new() }
+	}
+
+	/// Parse the metadata of a document, attaching the already parsed tasks.
+	pub fn parse(
+		&self,
+		content: &str,
+		tasks: Vec<Task>,
+	) -> Result<RoadmapDocument, MarkdownParseError> {
+		let (front_matter, body) = self.parse_front_matter(content)?;
+		let mut document = RoadmapDocument {
+			title: None,
+			authors: Vec::new(),
+			all_leads: Vec::new(),
+			start: front_matter.start,
+			front_matter,
+			tasks,
+			links: self.link_parser.parse(content),
+		};
+
+		for line in body.lines() {
+			let line = line.trim();
+
+			// Task sections carry their own fields.
+			if outline::task_header(line).is_some() {
+				break;
+			}
+
+			if let Some(title) = line.strip_prefix("# ") {
+				if document.title.is_none() {
+					document.title = Some(title.trim().to_string());
+				}
+				continue;
+			}
+
+			let Some((field, value)) = outline::field_line(line) else {
+				continue;
+			};
+
+			match field {
+				"Authors" | "Author" => document.authors = self.lead_parser.parse_leads(value),
+				"All leads" => document.all_leads = self.lead_parser.parse_leads(value),
+				"Start" | "Starts" if document.start.is_none() => {
+					document.start = Some(Self::parse_date(value)?);
+				}
+				_ => {}
+			}
+		}
+
+		Ok(document)
+	}
+
+	/// Split the front matter off the start of a document.
+	///
+	/// Returns the parsed front matter and the remaining body of the document.
+	pub fn parse_front_matter<'a>(
+		&self,
+		content: &'a str,
+	) -> Result<(FrontMatter, &'a str), MarkdownParseError> {
+		let trimmed = content.trim_start();
+		let delimiter = if trimmed.starts_with("---") {
+			"---"
+		} else if trimmed.starts_with("+++") {
+			"+++"
+		} else {
+			return Ok((FrontMatter::default(), content));
+		};
+
+		let Some((opening, after_opening)) = trimmed.split_once('\n') else {
+			return Ok((FrontMatter::default(), content));
+		};
+		if opening.trim() != delimiter {
+			return Ok((FrontMatter::default(), content));
+		}
+
+		let closing = after_opening
+			.match_indices(delimiter)
+			.find(|(index, _)| *index == 0 || after_opening[..*index].ends_with('\n'))
+			.map(|(index, _)| index)
+			.ok_or_else(|| MarkdownParseError::InvalidFrontMatter {
+				message: format!("missing closing '{delimiter}'"),
+			})?;
+
+		let block = &after_opening[..closing];
+		let body = after_opening[closing + delimiter.len()..].trim_start_matches(['\r', '\n']);
+		let raw =
+			if delimiter == "---" { Self::yaml_fields(block)? } else { Self::toml_fields(block)? };
+
+		let mut front_matter = FrontMatter::default();
+		for (key, value) in raw {
+			match key.as_str() {
+				"start" => front_matter.start = Some(Self::parse_date(&value)?),
+				"timezone" => {
+					let timezone = Timezone::parse(&value).map_err(|e| {
+						MarkdownParseError::InvalidFrontMatter { message: e.to_string() }
+					})?;
+					front_matter.timezone = Some(timezone);
+				}
+				_ => {
+					front_matter.fields.insert(key, value);
+				}
+			}
+		}
+
+		Ok((front_matter, body))
+	}
+
+	/// Read the keys of a YAML front matter block.
+	fn yaml_fields(block: &str) -> Result<BTreeMap<String, String>, MarkdownParseError> {
+		let value: serde_yaml::Value = serde_yaml::from_str(block)
+			.map_err(|e| MarkdownParseError::InvalidFrontMatter { message: e.to_string() })?;
+
+		let mut fields = BTreeMap::new();
+		let Some(mapping) = value.as_mapping() else {
+			return Ok(fields);
+		};
+
+		for (key, value) in mapping {
+			let Some(key) = key.as_str() else {
+				continue;
+			};
+			let value = match value {
+				serde_yaml::Value::String(s) => s.clone(),
+				other => serde_yaml::to_string(other)
+					.map_err(|e| MarkdownParseError::InvalidFrontMatter { message: e.to_string() })?
+					.trim()
+					.to_string(),
+			};
+			fields.insert(key.to_string(), value);
+		}
+
+		Ok(fields)
+	}
+
+	/// Read the keys of a TOML front matter block.
+	fn toml_fields(block: &str) -> Result<BTreeMap<String, String>, MarkdownParseError> {
+		let table: toml::Table = toml::from_str(block)
+			.map_err(|e| MarkdownParseError::InvalidFrontMatter { message: e.to_string() })?;
+
+		Ok(table
+			.into_iter()
+			.map(|(key, value)| {
+				let value = match value {
+					toml::Value::String(s) => s,
+					toml::Value::Datetime(datetime) => datetime.to_string(),
+					other => other.to_string(),
+				};
+				(key, value)
+			})
+			.collect())
+	}
+
+	/// Parse a `YYYY-MM-DD` date, also accepting an RFC 3339 timestamp.
+	pub fn parse_date(value: &str) -> Result<NaiveDate, MarkdownParseError> {
+		let value = value.trim();
+
+		NaiveDate::parse_from_str(value, "%Y-%m-%d")
+			.or_else(|_| DateTime::parse_from_rfc3339(value).map(|date| date.date_naive()))
+			.map_err(|_| MarkdownParseError::InvalidDateExpression {
+				expression: value.to_string(),
+			})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::oroad::OROAD_0;
+	use roadline_util::task::subtask::{Id as SubtaskId, Status, StatusDate};
+
+	#[test]
+	fn test_parse_oroad_metadata() -> Result<(), MarkdownParseError> {
+		let document = DocumentParser::new().parse(OROAD_0, Vec::new())?;
+		let liam = Lead::new("Liam Monninger".to_string(), "liam@ramate.io".to_string());
+
+		assert_eq!(document.title.as_deref(), Some("OROAD-0: The Attempt"));
+		assert_eq!(document.authors, vec![liam.clone()]);
+		assert_eq!(document.all_leads, vec![liam]);
+		assert_eq!(document.start, None);
+		assert_eq!(document.root_date(), None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_yaml_front_matter() -> Result<(), MarkdownParseError> {
+		let content = "---\nstart: 2025-01-06\ntimezone: Europe/Berlin\nstatus: draft\n---\n# OROAD-1: Next\n";
+		let document = DocumentParser::new().parse(content, Vec::new())?;
+
+		assert_eq!(document.start, NaiveDate::from_ymd_opt(2025, 1, 6));
+		assert_eq!(document.timezone(), Some("Europe/Berlin"));
+		assert_eq!(document.front_matter.fields.get("status").map(String::as_str), Some("draft"));
+		assert_eq!(document.title.as_deref(), Some("OROAD-1: Next"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_toml_front_matter() -> Result<(), MarkdownParseError> {
+		let content = "+++\nstart = 2025-01-06\ntimezone = \"UTC\"\n+++\n# OROAD-1: Next\n";
+		let document = DocumentParser::new().parse(content, Vec::new())?;
+
+		assert_eq!(document.start, NaiveDate::from_ymd_opt(2025, 1, 6));
+		assert_eq!(document.timezone(), Some("UTC"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_start_field_outside_tasks() -> Result<(), MarkdownParseError> {
+		let content =
+			"# OROAD-1: Next\n- **Start:** 2025-03-01\n\n### T1: First\n- **Start:** 2026-01-01\n";
+		let document = DocumentParser::new().parse(content, Vec::new())?;
+
+		assert_eq!(document.start, NaiveDate::from_ymd_opt(2025, 3, 1));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_check_leads() -> Result<(), MarkdownParseError> {
+		let content = "# OROAD-1: Next
+- **All leads:** [Ada](ada@example.com), [Grace](grace@example.com)
+
+### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** 1 month
+- **Lead:** [Ada](mailto:ada@example.com)
+- **Contents:**
+    - **[T1.1](#t11-draft):** Draft
+
+#### T1.2: Review
+- **Leads:** [Linus](linus@example.com), [Grace](grace@example.com)
+";
+		let parser = crate::RoadlineParser::new();
+		let document = parser.parse_document(content)?;
+		let ada = Lead::new("Ada".to_string(), "ada@example.com".to_string());
+		let grace = Lead::new("Grace".to_string(), "grace@example.com".to_string());
+		let linus = Lead::new("Linus".to_string(), "linus@example.com".to_string());
+
+		assert_eq!(document.tasks[0].leads(), [ada.clone()]);
+
+		// Every lead of a subtask is kept, not just the first
+		let review = document.tasks[0]
+			.subtask(&SubtaskId::new(2))
+			.ok_or(MarkdownParseError::InvalidSubtaskId { id: "T1.2".to_string() })?;
+		assert_eq!(review.leads().collect::<Vec<_>>(), [&linus, &grace]);
+		assert_eq!(document.used_leads(), vec![ada, linus.clone(), grace]);
+
+		let check = document.check_leads();
+		assert_eq!(check, LeadCheck { unlisted: vec![linus], unused: vec![] });
+		assert!(!check.is_consistent());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_days_start_in_timezone() -> Result<(), anyhow::Error> {
+		let content = "---
+start: 2025-01-06
+timezone: Europe/Berlin
+---
+# OROAD-1: Next
+
+### T1: First
+- **Starts:** T1 + 0 days
+- **Depends-on:** $\\emptyset$
+- **Ends:** 10 workdays
+- **Started:** 2025-01-07
+- **Contents:**
+    - **[T1.1](#t11-draft)**: Draft ✅ 2025-01-08
+";
+		let parser = crate::RoadlineParser::new();
+		let document = parser.parse_document(content)?;
+
+		// Days start at midnight in Berlin, an hour before midnight UTC
+		let root_date = document.root_date().ok_or(anyhow::anyhow!("missing root date"))?;
+		assert_eq!(root_date.inner().to_rfc3339(), "2025-01-05T23:00:00+00:00");
+		let started = document.tasks[0].progress().started();
+		assert_eq!(started.map(|date| date.to_rfc3339()), Some("2025-01-06T23:00:00+00:00".into()));
+
+		// Completion dates too, and the tasks alone already carry local dates
+		let tasks = parser.parse_tasks(content)?;
+		assert_eq!(tasks, document.tasks);
+		let completed = match tasks[0].subtasks().first().map(|subtask| subtask.status()) {
+			Some(Status::Complete(StatusDate { date })) => *date,
+			_ => None,
+		};
+		assert_eq!(
+			completed.map(|date| date.to_rfc3339()),
+			Some("2025-01-07T23:00:00+00:00".into())
+		);
+
+		// Working days are counted in Berlin, ending at local midnight after the second Friday
+		let roadline = parser.parse_and_build(content)?;
+		assert_eq!(roadline.timezone().name(), "Europe/Berlin");
+		let span = roadline
+			.range_algebra()
+			.span(document.tasks[0].id())
+			.ok_or(anyhow::anyhow!("missing span"))?;
+		assert_eq!(span.end.inner().inner().to_rfc3339(), "2025-01-17T23:00:00+00:00");
+
+		let invalid = "---\ntimezone: Europe/Atlantis\n---\n# OROAD-1: Next\n";
+		assert!(matches!(
+			DocumentParser::new().parse(invalid, Vec::new()),
+			Err(MarkdownParseError::InvalidFrontMatter { .. })
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_unterminated_front_matter() {
+		let content = "---\nstart: 2025-01-06\n# OROAD-1: Next\n";
+
+		assert!(matches!(
+			DocumentParser::new().parse(content, Vec::new()),
+			Err(MarkdownParseError::InvalidFrontMatter { .. })
+		));
+	}
+}
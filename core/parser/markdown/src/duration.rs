@@ -0,0 +1,391 @@
+//! Duration grammar shared by the `Starts` and `Ends` fields.
+//!
+//! A duration is either a list of `<number> <unit>` terms such as `1 month 2 weeks` or
+//! `1.5 months`, or an ISO-8601 duration such as `P1M2W`. Relative expressions put a task
+//! reference in front of a duration, as in `T3 + 1 week` or `T3 - 1 week`. A three-point
+//! estimate puts optimistic, likely and pessimistic durations in a row, as in `2–3–6 weeks`.
+//! Working days, as in `10 workdays`, are kept apart for a working calendar to resolve.
+
+use super::error::MarkdownParseError;
+use roadline_util::task::range::Direction;
+use std::time::Duration as StdDuration;
+
+const HOUR: f64 = 3600.0;
+const DAY: f64 = 24.0 * HOUR;
+const WEEK: f64 = 7.0 * DAY;
+const MONTH: f64 = 30.0 * DAY;
+const QUARTER: f64 = 3.0 * MONTH;
+const YEAR: f64 = 365.0 * DAY;
+
+/// A duration relative to another task, e.g. "T3 - 1 week".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RelativeExpression<'a> {
+	/// The task reference, e.g. "T3".
+	pub reference: &'a str,
+	/// Whether the duration is added to or subtracted from the reference.
+	pub direction: Direction,
+	/// The duration expression, e.g. "1 week".
+	pub duration: &'a str,
+}
+
+/// Parser for duration expressions in markdown documents.
+#[derive(Debug, Clone)]
+pub struct DurationParser {
+	// Configuration for duration parsing
+}
+
+impl Default for DurationParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl DurationParser {
+	/// Create a new duration parser.
+	pub fn new() -> Self {
+		Self {}
+	}
+
+	/// Parse a duration expression into a standard duration.
+	///
+	/// Expected formats:
+	/// - "1 month", "2 weeks", "30 days", "12 hours", "1 year", "1 quarter" or "Q"
+	/// - "1 month 2 weeks", "1 month, 2 weeks" or "1 month and 2 weeks"
+	/// - "1.5 months" or "2w"
+	/// - "P1M2W" or "P1DT12H" (ISO-8601)
+	///
+	/// Months are counted as 30 days, quarters as 3 months and years as 365 days.
+	pub fn parse(&self, expression: &str) -> Result<StdDuration, MarkdownParseError> {
+		let trimmed = expression.trim();
+		let invalid =
+			|| MarkdownParseError::InvalidDurationExpression { expression: trimmed.to_string() };
+
+		let secs = match trimmed.strip_prefix(['P', 'p']) {
+			Some(iso) if iso.starts_with(|c: char| c.is_ascii_digit() || c == 'T' || c == 't') => {
+				Self::parse_iso(iso).ok_or_else(invalid)?
+			}
+			_ => Self::parse_terms(&trimmed.to_lowercase()).ok_or_else(invalid)?,
+		};
+
+		StdDuration::try_from_secs_f64(secs.round()).map_err(|_| invalid())
+	}
+
+	/// Parse a number of working days, e.g. "10 workdays", "10 working days" or
+	/// "10 business days".
+	///
+	/// Returns `None` if the expression is not counted in working days.
+	pub fn parse_workdays(&self, expression: &str) -> Option<u32> {
+		let expression = expression.trim().to_lowercase();
+		let number_len = expression.find(|c: char| !c.is_ascii_digit())?;
+		let number = expression[..number_len].parse().ok()?;
+
+		match expression[number_len..].trim() {
+			"workday" | "workdays" | "working day" | "working days" | "business day"
+			| "business days" => Some(number),
+			_ => None,
+		}
+	}
+
+	/// Parse a three-point estimate like "2–3–6 weeks" into its optimistic, likely and
+	/// pessimistic durations.
+	///
+	/// The parts may be separated by en dashes, em dashes or hyphens. Bare numbers take the
+	/// unit of the last part, so "2–3–6 weeks" reads as "2 weeks–3 weeks–6 weeks". An estimate
+	/// in working days such as "2–3–6 workdays" holds as many calendar days; see
+	/// [`DurationParser::parse_workdays`].
+	///
+	/// Returns `None` if the expression is not a three-point estimate.
+	pub fn parse_estimate(
+		&self,
+		expression: &str,
+	) -> Option<Result<[StdDuration; 3], MarkdownParseError>> {
+		let expression = expression.trim();
+		let parts: Vec<&str> = expression.split(['–', '—', '-']).map(str::trim).collect();
+		let [optimistic, likely, pessimistic] = parts.as_slice() else {
+			return None;
+		};
+		if parts.iter().any(|part| part.is_empty()) || expression.starts_with(['T', 'M']) {
+			return None;
+		}
+
+		let number_len = pessimistic.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(0);
+		let unit = pessimistic[number_len..].trim();
+		let with_unit = |part: &str| {
+			if part.chars().all(|c| c.is_ascii_digit() || c == '.') {
+				format!("{part} {unit}")
+			} else {
+				part.to_string()
+			}
+		};
+
+		// Working days hold as many calendar days, and cannot be mixed with other units
+		let in_workdays = self.parse_workdays(pessimistic).is_some();
+		let parse_part = |part: &str| match self.parse_workdays(part) {
+			Some(workdays) if in_workdays => {
+				Ok(StdDuration::from_secs(u64::from(workdays) * 24 * 60 * 60))
+			}
+			_ if in_workdays => Err(MarkdownParseError::InvalidDurationExpression {
+				expression: expression.to_string(),
+			}),
+			_ => self.parse(part),
+		};
+
+		let parse = || -> Result<[StdDuration; 3], MarkdownParseError> {
+			let durations = [
+				parse_part(&with_unit(optimistic))?,
+				parse_part(&with_unit(likely))?,
+				parse_part(pessimistic)?,
+			];
+			if durations[0] > durations[1] || durations[1] > durations[2] {
+				return Err(MarkdownParseError::InvalidDurationExpression {
+					expression: expression.to_string(),
+				});
+			}
+			Ok(durations)
+		};
+
+		Some(parse())
+	}
+
+	/// Split a relative expression like "T3 + 1 week", or "M3 + 1 week" for a milestone,
+	/// into its parts.
+	///
+	/// Returns `None` if the expression does not start with a task reference followed
+	/// by `+` or `-`.
+	pub fn split_relative<'a>(&self, expression: &'a str) -> Option<RelativeExpression<'a>> {
+		let expression = expression.trim();
+		let operator = expression.find(['+', '-'])?;
+		let reference = expression[..operator].trim();
+
+		let id = reference.strip_prefix(['T', 'M'])?;
+		if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit() || c == '.') {
+			return None;
+		}
+
+		let direction = if expression[operator..].starts_with('-') {
+			Direction::Before
+		} else {
+			Direction::After
+		};
+
+		Some(RelativeExpression {
+			reference,
+			direction,
+			duration: expression[operator + 1..].trim(),
+		})
+	}
+
+	/// Parse a list of `<number> <unit>` terms, returning the total in seconds.
+	fn parse_terms(expression: &str) -> Option<f64> {
+		let mut total = 0.0;
+		let mut terms = 0;
+		let mut rest = expression.trim();
+
+		while !rest.is_empty() {
+			let number_len =
+				rest.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(rest.len());
+			let number =
+				if number_len == 0 { 1.0 } else { rest[..number_len].parse::<f64>().ok()? };
+			rest = rest[number_len..].trim_start();
+
+			let unit_len = rest.find(|c: char| !c.is_alphabetic()).unwrap_or(rest.len());
+			let unit = &rest[..unit_len];
+
+			// A bare unit is only allowed for "Q", as in "Q" for one quarter.
+			if number_len == 0 && unit != "q" {
+				return None;
+			}
+			let unit = Self::unit(unit)?;
+
+			total += number * unit;
+			terms += 1;
+			rest = rest[unit_len..].trim_start_matches([',', ' ']);
+			rest = rest.strip_prefix("and ").unwrap_or(rest).trim_start();
+		}
+
+		(terms > 0).then_some(total)
+	}
+
+	/// Parse the part of an ISO-8601 duration after the leading `P`.
+	fn parse_iso(expression: &str) -> Option<f64> {
+		let mut total = 0.0;
+		let mut in_time = false;
+		let mut number = String::new();
+
+		for c in expression.chars() {
+			match c.to_ascii_uppercase() {
+				'T' if number.is_empty() && !in_time => in_time = true,
+				d if d.is_ascii_digit() || d == '.' || d == ',' => {
+					number.push(if d == ',' { '.' } else { d });
+				}
+				designator => {
+					let value: f64 = number.parse().ok()?;
+					number.clear();
+
+					total += value
+						* match (designator, in_time) {
+							('Y', false) => YEAR,
+							('M', false) => MONTH,
+							('W', false) => WEEK,
+							('D', false) => DAY,
+							('H', true) => HOUR,
+							('M', true) => 60.0,
+							('S', true) => 1.0,
+							_ => return None,
+						};
+				}
+			}
+		}
+
+		number.is_empty().then_some(total)
+	}
+
+	/// The length of a unit in seconds.
+	fn unit(unit: &str) -> Option<f64> {
+		let seconds = match unit {
+			"h" | "hr" | "hrs" | "hour" | "hours" => HOUR,
+			"d" | "day" | "days" => DAY,
+			"w" | "wk" | "wks" | "week" | "weeks" => WEEK,
+			"mo" | "mos" | "month" | "months" => MONTH,
+			"q" | "quarter" | "quarters" => QUARTER,
+			"y" | "yr" | "yrs" | "year" | "years" => YEAR,
+			_ => return None,
+		};
+
+		Some(seconds)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn days(n: u64) -> StdDuration {
+		StdDuration::from_secs(86400 * n)
+	}
+
+	#[test]
+	fn test_single_terms() -> Result<(), MarkdownParseError> {
+		let parser = DurationParser::new();
+
+		assert_eq!(parser.parse("1 month")?, days(30));
+		assert_eq!(parser.parse("2 weeks")?, days(14));
+		assert_eq!(parser.parse("30 days")?, days(30));
+		assert_eq!(parser.parse("12 hours")?, StdDuration::from_secs(12 * 3600));
+		assert_eq!(parser.parse("1 year")?, days(365));
+		assert_eq!(parser.parse("2 quarters")?, days(180));
+		assert_eq!(parser.parse("Q")?, days(90));
+		assert_eq!(parser.parse("1 Q")?, days(90));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_compound_and_decimal_terms() -> Result<(), MarkdownParseError> {
+		let parser = DurationParser::new();
+
+		assert_eq!(parser.parse("1 month 2 weeks")?, days(44));
+		assert_eq!(parser.parse("1 month, 2 weeks")?, days(44));
+		assert_eq!(parser.parse("1 month and 2 weeks")?, days(44));
+		assert_eq!(parser.parse("1.5 months")?, days(45));
+		assert_eq!(parser.parse("2w 3d")?, days(17));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_iso_durations() -> Result<(), MarkdownParseError> {
+		let parser = DurationParser::new();
+
+		assert_eq!(parser.parse("P1M2W")?, days(44));
+		assert_eq!(parser.parse("P1Y")?, days(365));
+		assert_eq!(parser.parse("P1DT12H")?, StdDuration::from_secs(86400 + 12 * 3600));
+		assert_eq!(parser.parse("PT30M")?, StdDuration::from_secs(1800));
+		assert_eq!(parser.parse("P0.5D")?, StdDuration::from_secs(43200));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_invalid_durations() {
+		let parser = DurationParser::new();
+
+		for expression in ["", "month", "1 fortnight", "P1X", "P1", "1..5 days", "T1"] {
+			assert!(
+				matches!(
+					parser.parse(expression),
+					Err(MarkdownParseError::InvalidDurationExpression { .. })
+				),
+				"{expression} should not parse"
+			);
+		}
+	}
+
+	#[test]
+	fn test_three_point_estimates() -> Result<(), MarkdownParseError> {
+		let parser = DurationParser::new();
+
+		assert_eq!(
+			parser.parse_estimate("2–3–6 weeks").transpose()?,
+			Some([days(14), days(21), days(42)])
+		);
+		assert_eq!(
+			parser.parse_estimate("2-3-6 weeks").transpose()?,
+			Some([days(14), days(21), days(42)])
+		);
+		assert_eq!(
+			parser.parse_estimate("1 week — 2 weeks — 1 month").transpose()?,
+			Some([days(7), days(14), days(30)])
+		);
+		assert_eq!(
+			parser.parse_estimate("2–3–6 workdays").transpose()?,
+			Some([days(2), days(3), days(6)])
+		);
+		assert!(matches!(
+			parser.parse_estimate("1 week–3–6 workdays"),
+			Some(Err(MarkdownParseError::InvalidDurationExpression { .. }))
+		));
+		assert_eq!(parser.parse_estimate("3 weeks").transpose()?, None);
+		assert_eq!(parser.parse_estimate("T1 - 1 week").transpose()?, None);
+		assert!(matches!(
+			parser.parse_estimate("6–3–2 weeks"),
+			Some(Err(MarkdownParseError::InvalidDurationExpression { .. }))
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_workdays() {
+		let parser = DurationParser::new();
+
+		assert_eq!(parser.parse_workdays("10 workdays"), Some(10));
+		assert_eq!(parser.parse_workdays("1 Working Day"), Some(1));
+		assert_eq!(parser.parse_workdays("3 business days"), Some(3));
+		assert_eq!(parser.parse_workdays("10 days"), None);
+		assert_eq!(parser.parse_workdays("1.5 workdays"), None);
+	}
+
+	#[test]
+	fn test_split_relative() {
+		let parser = DurationParser::new();
+
+		assert_eq!(
+			parser.split_relative("T3 - 1 week"),
+			Some(RelativeExpression {
+				reference: "T3",
+				direction: Direction::Before,
+				duration: "1 week"
+			})
+		);
+		assert_eq!(
+			parser.split_relative("T12+P1M"),
+			Some(RelativeExpression {
+				reference: "T12",
+				direction: Direction::After,
+				duration: "P1M"
+			})
+		);
+		assert_eq!(parser.split_relative("1 month"), None);
+	}
+}
@@ -1,6 +1,7 @@
 //! Error types for markdown parsing.
 
 use thiserror::Error;
+use roadline_representation_core::graph::GraphError;
 use roadline_representation_core::roadline::RoadlineBuilderError;
 
 /// Comprehensive error type for markdown parsing operations.
@@ -34,10 +35,18 @@ pub enum MarkdownParseError {
     #[error("Failed to parse dependency reference: {reference}")]
     InvalidDependencyReference { reference: String },
 
+    /// Failed to parse a lead, e.g. an entry without a name.
+    #[error("Failed to parse lead: '{lead}'")]
+    InvalidLead { lead: String },
+
     /// Failed to parse a duration expression.
     #[error("Failed to parse duration expression: {expression}")]
     InvalidDurationExpression { expression: String },
 
+    /// Failed to parse the front matter of a document.
+    #[error("Invalid front matter: {message}")]
+    InvalidFrontMatter { message: String },
+
     /// Missing required field in task section.
     #[error("Missing required field '{field}' in task section")]
     MissingRequiredField { field: String },
@@ -46,6 +55,49 @@ pub enum MarkdownParseError {
     #[error("Invalid format for field '{field}': {value}")]
     InvalidFieldFormat { field: String, value: String },
 
+    /// A task id referenced by a refactoring does not exist in the document.
+    #[error("Task '{id}' does not exist in the document")]
+    UnknownTaskId { id: String },
+
+    /// A refactoring would give more than one task the same id.
+    #[error("Task id '{id}' would be used by more than one task")]
+    TaskIdConflict { id: String },
+
+    /// A task and a milestone have the same number, e.g. `T3` and `M3`.
+    #[error("Task '{task}' and milestone '{milestone}' have the same task id")]
+    SharedTaskId { task: String, milestone: String },
+
+    /// A milestone declares an end after its start, though milestones take no time.
+    #[error("Milestone '{id}' cannot end after its start: {ends}")]
+    MilestoneDuration { id: String, ends: String },
+
+    /// A refactoring would shift a task id past the largest task id.
+    #[error("Shifting task '{task}' by {offset} exceeds the largest task id")]
+    OffsetOverflow { task: String, offset: u8 },
+
+    /// A linked roadmap document could not be read.
+    #[error("Failed to read document '{path}': {message}")]
+    DocumentRead { path: String, message: String },
+
+    /// A reference in one document does not resolve to a task.
+    #[error("Reference '{reference}' in '{document}' does not resolve to a task")]
+    UnresolvedReference { document: String, reference: String },
+
+    /// The composed documents have more tasks than task ids can address.
+    #[error("Composed roadmap has more than {limit} tasks")]
+    TooManyTasks { limit: usize },
+
+    /// Dependencies form a cycle, possibly spanning several documents.
+    #[error("Dependency cycle: {cycle}")]
+    DependencyCycle { cycle: String },
+
+    /// Error from the task graph.
+    #[error("Graph error: {source}")]
+    Graph {
+        #[from]
+        source: GraphError,
+    },
+
     /// Error from the roadline builder.
     #[error("Roadline builder error: {source}")]
     RoadlineBuilder {
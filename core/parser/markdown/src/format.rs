@@ -0,0 +1,424 @@
+//! Formatting for roadmap markdown documents.
+//!
+//! The formatter rewrites the parts of a roadmap which are derived from its tasks: the
+//! `## Roadmap` contents list, the `#tN-...` anchors in field links, and the order and
+//! indentation of `- **Field:**` blocks. Prose is left untouched.
+
+use super::error::MarkdownParseError;
+use super::fragment::{heading_fragments, plain_text};
+use super::outline::{self, Section};
+use super::task::KNOWN_FIELDS;
+use super::RoadlineParser;
+use roadline_util::task::Task;
+use std::collections::HashMap;
+
+/// The canonical order of the task fields known to the parser.
+///
+/// Unknown fields keep their relative order and are placed before `Contents`,
+/// which always comes last because it carries a nested list.
+const FIELD_ORDER: [&str; 3] = ["Starts", "Depends-on", "Ends"];
+
+/// The field which carries a nested list of subtasks.
+const CONTENTS_FIELD: &str = "Contents";
+
+/// Formatter for roadmap markdown documents.
+///
+/// # Example
+///
+/// ```
+/// use roadline_parser_markdown::RoadmapFormatter;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let markdown_content = r#"
+/// #### T1: Push Towards Validation
+/// - **Ends:** 1 month
+/// - **Starts:** T1 + 0 months
+/// - **Depends-on:** $\emptyset$
+/// "#;
+///
+/// let formatted = RoadmapFormatter::new().format(markdown_content)?;
+/// assert!(formatted.contains("- **Starts:** T1 + 0 months\n- **Depends-on:** $\\emptyset$\n- **Ends:** 1 month"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RoadmapFormatter {
+	parser: RoadlineParser,
+	indent: usize,
+}
+
+impl Default for RoadmapFormatter {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A field line together with its nested lines.
+#[derive(Debug, Clone)]
+struct FieldEntry {
+	name: String,
+	value: String,
+	children: Vec<(usize, String)>,
+}
+
+impl RoadmapFormatter {
+	/// Create a new formatter using four spaces of indentation for nested lists.
+	pub fn new() -> Self {
+		Self { parser: RoadlineParser::new(), indent: 4 }
+	}
+
+	/// Use a specific parser to read the document.
+	pub fn with_parser(mut self, parser: RoadlineParser) -> Self {
+		self.parser = parser;
+		self
+	}
+
+	/// Set the number of spaces used per level of nested list indentation.
+	pub fn with_indent(mut self, indent: usize) -> Self {
+		self.indent = indent;
+		self
+	}
+
+	/// Format a roadmap document, returning the rewritten document.
+	///
+	/// The document must parse as a roadmap; formatting is idempotent.
+	pub fn format(&self, content: &str) -> Result<String, MarkdownParseError> {
+		let tasks = self.parser.parse_tasks(content)?;
+		let anchors = Self::collect_anchors(content);
+
+		let lines: Vec<&str> = content.lines().collect();
+		let mut out: Vec<String> = Vec::with_capacity(lines.len());
+		let mut section = Section::Other;
+		let mut i = 0;
+
+		while i < lines.len() {
+			let line = lines[i];
+			let trimmed = line.trim();
+
+			if let Some(next) = Section::for_heading(trimmed) {
+				section = next;
+				out.push(line.to_string());
+				i += 1;
+				continue;
+			}
+
+			match section {
+				Section::Roadmap
+					if outline::field_line(trimmed).is_some_and(|(f, _)| f == CONTENTS_FIELD) =>
+				{
+					out.push(format!("- **{CONTENTS_FIELD}:**"));
+					i += 1;
+					while i < lines.len() && Self::is_nested(lines[i]) {
+						i += 1;
+					}
+					out.extend(self.roadmap_contents(&tasks, &anchors));
+				}
+				Section::Task if outline::field_line(trimmed).is_some() => {
+					let end = Self::field_block_end(&lines, i);
+					out.extend(self.format_field_block(&lines[i..end], &anchors));
+					i = end;
+				}
+				_ => {
+					out.push(line.to_string());
+					i += 1;
+				}
+			}
+		}
+
+		let mut formatted = out.join("\n");
+		if content.ends_with('\n') {
+			formatted.push('\n');
+		}
+
+		Ok(formatted)
+	}
+
+	/// Check whether a document is already formatted.
+	pub fn is_formatted(&self, content: &str) -> Result<bool, MarkdownParseError> {
+		Ok(self.format(content)? == content)
+	}
+
+	/// Collect the current fragments for every task and subtask header.
+	///
+	/// Keys are the ids used as link text, e.g. `T1`, `M2` or `T1.1`. A header which repeats
+	/// an earlier heading keeps the `-1`, `-2`, ... suffix of its fragment.
+	fn collect_anchors(content: &str) -> HashMap<String, String> {
+		let lines: Vec<&str> = content.lines().collect();
+		let mut anchors = HashMap::new();
+
+		for (line, fragment) in heading_fragments(content) {
+			let heading = lines.get(line - 1).map_or("", |heading| heading.trim());
+			let id = match (outline::task_header(heading), outline::subtask_header(heading)) {
+				(Some((kind, id, _)), _) => format!("{}{}", kind.prefix(), id.value()),
+				(None, Some((_, id, _))) => id.to_string(),
+				(None, None) => continue,
+			};
+			anchors.entry(id).or_insert(fragment);
+		}
+
+		anchors
+	}
+
+	/// Render the `## Roadmap` contents list from the parsed tasks.
+	fn roadmap_contents(&self, tasks: &[Task], anchors: &HashMap<String, String>) -> Vec<String> {
+		tasks
+			.iter()
+			.filter_map(|task| {
+				let id = format!("{}{}", task.kind().prefix(), task.id().value());
+				anchors.get(&id).map(|fragment| {
+					format!(
+						"{}- **[{}](#{}):** {}",
+						" ".repeat(self.indent),
+						id,
+						fragment,
+						plain_text(&task.title().text)
+					)
+				})
+			})
+			.collect()
+	}
+
+	/// Format a block of consecutive field lines and their nested lines.
+	fn format_field_block(&self, block: &[&str], anchors: &HashMap<String, String>) -> Vec<String> {
+		let mut entries: Vec<FieldEntry> = Vec::new();
+
+		for line in block {
+			let trimmed = line.trim();
+
+			// A misindented task field is lifted out, but nested lines, e.g. bold items under
+			// `Contents`, stay where they are
+			let is_root_field = match outline::field_line(trimmed) {
+				Some((name, _)) => {
+					!Self::is_nested(line)
+						|| (KNOWN_FIELDS.contains(&name)
+							&& entries.last().is_some_and(|entry| entry.name != CONTENTS_FIELD))
+				}
+				None => false,
+			};
+
+			match (is_root_field, entries.last_mut()) {
+				(true, _) | (false, None) => {
+					let (name, value) = outline::field_line(trimmed).unwrap_or_default();
+					entries.push(FieldEntry {
+						name: name.to_string(),
+						value: value.to_string(),
+						children: Vec::new(),
+					});
+				}
+				(false, Some(entry)) => {
+					entry.children.push((Self::indent_width(line), trimmed.to_string()));
+				}
+			}
+		}
+
+		entries.sort_by_key(|entry| Self::field_rank(&entry.name));
+
+		let mut out = Vec::new();
+		for entry in entries {
+			let value = self.rewrite_anchors(&entry.value, anchors);
+			if value.is_empty() {
+				out.push(format!("- **{}:**", entry.name));
+			} else {
+				out.push(format!("- **{}:** {}", entry.name, value));
+			}
+
+			let mut widths: Vec<usize> = entry.children.iter().map(|(width, _)| *width).collect();
+			widths.sort_unstable();
+			widths.dedup();
+
+			for (width, text) in &entry.children {
+				let depth = widths.iter().position(|w| w == width).unwrap_or(0) + 1;
+				out.push(format!(
+					"{}{}",
+					" ".repeat(self.indent * depth),
+					self.rewrite_anchors(text, anchors)
+				));
+			}
+		}
+
+		out
+	}
+
+	/// Rewrite `[T1](#...)` style links so that they point at the current fragments.
+	///
+	/// Links whose text is not a known task or subtask id are left as they are.
+	fn rewrite_anchors(&self, text: &str, anchors: &HashMap<String, String>) -> String {
+		let mut out = String::with_capacity(text.len());
+		let mut rest = text;
+
+		while let Some(open) = rest.find('[') {
+			let after_open = &rest[open + 1..];
+			let Some(close) = after_open.find("](#") else {
+				break;
+			};
+			let target_start = close + 3;
+			let Some(target_len) = after_open[target_start..].find(')') else {
+				break;
+			};
+
+			match anchors.get(&after_open[..close]) {
+				Some(fragment) => {
+					out.push_str(&rest[..open]);
+					out.push_str(&format!("[{}](#{})", &after_open[..close], fragment));
+					rest = &after_open[target_start + target_len + 1..];
+				}
+				None => {
+					out.push_str(&rest[..open + 1]);
+					rest = after_open;
+				}
+			}
+		}
+
+		out.push_str(rest);
+		out
+	}
+
+	/// Find the end (exclusive) of the field block starting at `start`.
+	fn field_block_end(lines: &[&str], start: usize) -> usize {
+		let mut end = start + 1;
+
+		while end < lines.len() {
+			let line = lines[end];
+			if outline::field_line(line.trim()).is_none() && !Self::is_nested(line) {
+				break;
+			}
+			end += 1;
+		}
+
+		end
+	}
+
+	/// The position of a field in the canonical field order.
+	fn field_rank(name: &str) -> usize {
+		if name == CONTENTS_FIELD {
+			return FIELD_ORDER.len() + 1;
+		}
+
+		FIELD_ORDER.iter().position(|field| *field == name).unwrap_or(FIELD_ORDER.len())
+	}
+
+	/// Whether a line is an indented, non-empty line belonging to a list above it.
+	fn is_nested(line: &str) -> bool {
+		Self::indent_width(line) > 0 && !line.trim().is_empty()
+	}
+
+	/// The width of the leading whitespace of a line, counting tabs as four spaces.
+	fn indent_width(line: &str) -> usize {
+		line.chars()
+			.take_while(|c| c.is_whitespace())
+			.map(|c| if c == '\t' { 4 } else { 1 })
+			.sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::tests::oroad::OROAD_0;
+
+	const STALE: &str = r#"# OROAD-X: Example
+
+## Roadmap
+- **All leads:** [Liam Monninger](liam@ramate.io)
+- **Contents:**
+    - **[T1](#t1-old-title):** Old Title
+
+### T1: Push Towards Validation
+Prose stays   exactly as written.
+
+- **Ends:**   1 month
+- **Depends-on:** $\emptyset$
+  - **Starts:** T1 + 0 months
+- **Contents:**
+  - **[T1.1](#t11-stale)**: Complete draft
+    - nested detail
+
+#### T1.1: Complete draft of **OART-1: BFA**
+- **Lead:** [Liam Monninger](mailto:liam@ramate.io)
+
+### T2: Validation and [`fuste`](https://github.com/ramate-io/fuste) MVP
+- **Starts:** T1 + 1 month
+- **Depends-on:** [T1](#t1-old-title)
+- **Ends:** 1 month
+"#;
+
+	#[test]
+	fn test_regenerates_roadmap_contents() -> Result<(), MarkdownParseError> {
+		let formatted = RoadmapFormatter::new().format(STALE)?;
+
+		assert!(formatted.contains(
+			"- **Contents:**\n    - **[T1](#t1-push-towards-validation):** Push Towards Validation\n    - **[T2](#t2-validation-and-fuste-mvp):** Validation and fuste MVP\n"
+		));
+		assert!(!formatted.contains("t1-old-title"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_normalizes_field_order_and_indentation() -> Result<(), MarkdownParseError> {
+		let formatted = RoadmapFormatter::new().format(STALE)?;
+
+		assert!(formatted.contains(
+			"- **Starts:** T1 + 0 months\n- **Depends-on:** $\\emptyset$\n- **Ends:** 1 month\n- **Contents:**\n    - **[T1.1](#t11-complete-draft-of-oart-1-bfa)**: Complete draft\n        - nested detail\n"
+		));
+		assert!(formatted.contains("- **Depends-on:** [T1](#t1-push-towards-validation)"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_leaves_prose_untouched() -> Result<(), MarkdownParseError> {
+		let formatted = RoadmapFormatter::new().format(STALE)?;
+
+		assert!(formatted.starts_with("# OROAD-X: Example\n"));
+		assert!(formatted.contains("Prose stays   exactly as written."));
+		assert!(formatted.contains("- **All leads:** [Liam Monninger](liam@ramate.io)"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_keeps_nested_bold_lines() -> Result<(), MarkdownParseError> {
+		let content = "### T1: First\n- **Starts:** T1 + 0 months\n- **Ends:** 1 month\n- **Contents:**\n    - **T1.1**: Draft\n        - **Note:** reviewed twice\n";
+		let formatted = RoadmapFormatter::new().format(content)?;
+
+		assert_eq!(formatted, content);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_anchors_of_duplicate_headings() -> Result<(), MarkdownParseError> {
+		let content = "## T2: Second\n\n### T1: First\n- **Starts:** T1 + 0 months\n- **Ends:** 1 month\n\n### T2: Second\n- **Starts:** T1 + 1 month\n- **Depends-on:** [T1](#t1-first)\n- **Ends:** 1 month\n\n### T3: Third\n- **Starts:** T2 + 1 month\n- **Depends-on:** [T2](#t2-second)\n- **Ends:** 1 month\n";
+		let formatted = RoadmapFormatter::new().format(content)?;
+
+		assert!(formatted.contains("- **Depends-on:** [T2](#t2-second-1)\n"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_format_is_idempotent() -> Result<(), MarkdownParseError> {
+		let formatter = RoadmapFormatter::new();
+
+		let once = formatter.format(STALE)?;
+		assert_eq!(formatter.format(&once)?, once);
+		assert!(formatter.is_formatted(&once)?);
+
+		let oroad = formatter.format(OROAD_0)?;
+		assert_eq!(formatter.format(&oroad)?, oroad);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_formatted_oroad_parses_the_same() -> Result<(), MarkdownParseError> {
+		let parser = RoadlineParser::new();
+		let formatted = RoadmapFormatter::new().format(OROAD_0)?;
+
+		assert_eq!(parser.parse_tasks(&formatted)?, parser.parse_tasks(OROAD_0)?);
+		assert!(formatted.contains("    - **[T9](#t9-an-interlude):** An Interlude\n"));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,187 @@
+//! Extract links and fragments from markdown documents
+use std::collections::{HashMap, HashSet};
+
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd};
+
+/// Returns the default markdown extensions used by lychee.
+/// Sadly, `|` is not const for `Options` so we can't use a const global.
+fn md_extensions() -> Options {
+	Options::ENABLE_HEADING_ATTRIBUTES
+		| Options::ENABLE_MATH
+		| Options::ENABLE_WIKILINKS
+		| Options::ENABLE_FOOTNOTES
+}
+
+/// Extract fragments/anchors from a Markdown string.
+///
+/// Fragments are generated from headings using the same unique kebab case method as GitHub.
+/// If a [heading attribute](https://github.com/raphlinus/pulldown-cmark/blob/master/specs/heading_attrs.txt)
+/// is present,
+/// this will be added to the fragment set **alongside** the other generated fragment.
+/// It means a single heading such as `## Frag 1 {#frag-2}` would generate two fragments.
+pub fn extract_markdown_fragments(input: &str) -> HashSet<String> {
+	let mut out = HashSet::new();
+
+	for heading in headings(input) {
+		out.extend(heading.attribute_id);
+		out.extend(heading.fragment);
+	}
+
+	out
+}
+
+/// Generates the fragment of every heading in a Markdown string, in document order.
+///
+/// Fragments are generated as in [extract_markdown_fragments], so a heading repeating an
+/// earlier one gets a `-1`, `-2`, ... suffix. Heading attributes are ignored. Each fragment
+/// is paired with the one-based line number of its heading.
+pub fn heading_fragments(input: &str) -> Vec<(usize, String)> {
+	headings(input)
+		.into_iter()
+		.filter_map(|heading| {
+			let line = input[..heading.offset].matches('\n').count() + 1;
+			heading.fragment.map(|fragment| (line, fragment))
+		})
+		.collect()
+}
+
+/// A heading of a Markdown string.
+struct Heading {
+	/// The byte offset at which the heading starts.
+	offset: usize,
+	/// The id given by a heading attribute, if any.
+	attribute_id: Option<String>,
+	/// The generated fragment, unless the heading has no text.
+	fragment: Option<String>,
+}
+
+/// Walks the headings of a Markdown string, generating their fragments.
+fn headings(input: &str) -> Vec<Heading> {
+	let mut in_heading = false;
+	let mut heading_text = String::new();
+	let mut heading_id: Option<CowStr<'_>> = None;
+	let mut heading_offset = 0;
+	let mut id_generator = HeadingIdGenerator::default();
+
+	let mut out = Vec::new();
+
+	for (event, range) in Parser::new_ext(input, md_extensions()).into_offset_iter() {
+		match event {
+			Event::Start(Tag::Heading { id, .. }) => {
+				heading_id = id;
+				heading_offset = range.start;
+				in_heading = true;
+			}
+			Event::End(TagEnd::Heading(_)) => {
+				let fragment =
+					(!heading_text.is_empty()).then(|| id_generator.generate(&heading_text));
+				out.push(Heading {
+					offset: heading_offset,
+					attribute_id: heading_id.take().map(|id| id.to_string()),
+					fragment,
+				});
+				heading_text.clear();
+				in_heading = false;
+			}
+			Event::Text(text) | Event::Code(text) => {
+				if in_heading {
+					heading_text.push_str(&text);
+				}
+			}
+
+			// An HTML node
+			Event::Html(_html) | Event::InlineHtml(_html) => {
+				// skip
+			}
+
+			// Silently skip over other events
+			_ => (),
+		}
+	}
+	out
+}
+
+/// Renders inline markdown as the plain text GitHub would use for a heading.
+///
+/// Links, emphasis and code spans are reduced to their text content.
+pub fn plain_text(markdown: &str) -> String {
+	let mut text = String::new();
+
+	for event in Parser::new_ext(markdown, md_extensions()) {
+		match event {
+			Event::Text(t) | Event::Code(t) => text.push_str(&t),
+			Event::SoftBreak | Event::HardBreak => text.push(' '),
+			_ => (),
+		}
+	}
+
+	text
+}
+
+#[derive(Default)]
+struct HeadingIdGenerator {
+	counter: HashMap<String, usize>,
+}
+
+impl HeadingIdGenerator {
+	fn generate(&mut self, heading: &str) -> String {
+		let mut id = Self::into_kebab_case(heading);
+		let count = self.counter.entry(id.clone()).or_insert(0);
+		if *count != 0 {
+			id = format!("{}-{}", id, *count);
+		}
+		*count += 1;
+
+		id
+	}
+
+	/// Converts text into kebab case
+	#[must_use]
+	fn into_kebab_case(text: &str) -> String {
+		text.to_lowercase()
+			.chars()
+			.filter_map(|ch| {
+				if ch.is_alphanumeric() || ch == '_' || ch == '-' {
+					Some(ch)
+				} else if ch.is_whitespace() {
+					Some('-')
+				} else {
+					None
+				}
+			})
+			.collect::<String>()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_heading_fragments() {
+		let fragments = heading_fragments(
+			"### T1: Push Towards Validation\n\nnot a heading\n\n### T3: Continued Validation and [`fuste`](https://github.com/ramate-io/fuste) MVP\n\n## Notes\n\n## Notes\n",
+		);
+
+		assert_eq!(
+			fragments,
+			vec![
+				(1, "t1-push-towards-validation".to_string()),
+				(5, "t3-continued-validation-and-fuste-mvp".to_string()),
+				(7, "notes".to_string()),
+				(9, "notes-1".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn test_plain_text() {
+		assert_eq!(
+			plain_text(
+				"Continued Validation and [`fuste`](https://github.com/ramate-io/fuste) MVP"
+			),
+			"Continued Validation and fuste MVP"
+		);
+		assert_eq!(plain_text("Draft of **OART-1: BFA**"), "Draft of OART-1: BFA");
+	}
+}
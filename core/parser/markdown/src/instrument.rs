@@ -1,12 +1,140 @@
+//! Instrumentation hooks for markdown roadmap parsing.
+//!
+//! An [`Instrumentation`] receives structured events as the parser walks a document.
+//! Every event carries a [`SourceSpan`], so tools such as anchor mappers, linters and
+//! editors can map what the parser saw back onto the source text.
+
+pub(crate) mod emitter;
+
 use crate::error::MarkdownParseError;
 use roadline_util::task::Id as TaskId;
 
+/// A region of the source document.
+///
+/// Offsets are byte offsets into the parsed document; `line` and `column` are one-based
+/// and point at `start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SourceSpan {
+	pub line: usize,
+	pub column: usize,
+	pub start: usize,
+	pub end: usize,
+}
+
+impl SourceSpan {
+	/// Create a span for a line starting at byte `line_start`, covering `start..end`.
+	pub fn new(line: usize, line_start: usize, start: usize, end: usize) -> Self {
+		Self { line, column: start - line_start + 1, start, end }
+	}
+
+	/// The text covered by this span.
+	pub fn text<'a>(&self, source: &'a str) -> Option<&'a str> {
+		source.get(self.start..self.end)
+	}
+}
+
+/// Emitted once a task has been parsed, at its header.
 #[derive(Debug, Clone)]
 pub struct TaskParsedEvent {
 	pub task: TaskId,
 	pub title_line_string: String,
+	pub span: SourceSpan,
+}
+
+/// Emitted for each subtask entry, either a `Contents` line or a `####` header.
+#[derive(Debug, Clone)]
+pub struct SubtaskParsedEvent {
+	pub task: TaskId,
+	/// The subtask id as written, e.g. `T1.1`.
+	pub subtask: String,
+	pub title: String,
+	pub span: SourceSpan,
+}
+
+/// Emitted for each dependency of a task, at the reference in its `Depends-on` field.
+#[derive(Debug, Clone)]
+pub struct DependencyParsedEvent {
+	pub task: TaskId,
+	pub dependency: TaskId,
+	pub span: SourceSpan,
+}
+
+/// Emitted for each `- **Field:** value` line in a task section.
+///
+/// Used both for known fields and for unknown fields. Fields of a task which the parser does
+/// not know are kept as custom fields of the task and reported as parsed; only fields of
+/// subtasks are reported as unknown.
+#[derive(Debug, Clone)]
+pub struct FieldParsedEvent {
+	pub task: TaskId,
+	/// The subtask whose `####` subsection contains the field, if any.
+	pub subtask: Option<String>,
+	pub field: String,
+	/// The raw, unparsed value of the field.
+	pub value: String,
+	/// The one-based line number of the field.
+	pub line: usize,
+	/// The span of the whole field line.
+	pub span: SourceSpan,
+	/// The span of the value alone.
+	pub value_span: SourceSpan,
+}
+
+/// Emitted at the start and at the end of every heading section.
+#[derive(Debug, Clone)]
+pub struct SectionEvent {
+	/// The heading text without the leading `#`s.
+	pub heading: String,
+	/// The heading level, i.e. the number of leading `#`s.
+	pub level: usize,
+	/// The task the section belongs to, if any.
+	pub task: Option<TaskId>,
+	/// The heading line when starting; the whole section when ending.
+	pub span: SourceSpan,
+}
+
+/// Emitted for problems which do not stop parsing.
+#[derive(Debug, Clone)]
+pub struct WarningEvent {
+	pub message: String,
+	pub span: SourceSpan,
 }
 
+/// Receives events while a document is parsed.
+///
+/// Only `on_task_parsed` is required; the other hooks default to doing nothing.
+/// Returning an error from any hook aborts the parse.
 pub trait Instrumentation {
 	fn on_task_parsed(&mut self, task: TaskParsedEvent) -> Result<(), MarkdownParseError>;
+
+	fn on_subtask_parsed(&mut self, _event: SubtaskParsedEvent) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
+
+	fn on_dependency_parsed(
+		&mut self,
+		_event: DependencyParsedEvent,
+	) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
+
+	fn on_field_parsed(&mut self, _event: FieldParsedEvent) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
+
+	fn on_unknown_field(&mut self, _event: FieldParsedEvent) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
+
+	fn on_section_started(&mut self, _event: SectionEvent) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
+
+	fn on_section_ended(&mut self, _event: SectionEvent) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
+
+	fn on_warning(&mut self, _event: WarningEvent) -> Result<(), MarkdownParseError> {
+		Ok(())
+	}
 }
@@ -0,0 +1,606 @@
+//! Walks a document as it is parsed and emits instrumentation events in source order.
+
+use super::{
+	DependencyParsedEvent, FieldParsedEvent, Instrumentation, SectionEvent, SourceSpan,
+	SubtaskParsedEvent, TaskParsedEvent, WarningEvent,
+};
+use crate::error::MarkdownParseError;
+use crate::outline;
+use crate::task::KNOWN_FIELDS;
+use roadline_util::task::{Id as TaskId, Kind, Task};
+use std::collections::{HashMap, HashSet};
+
+/// A line of the source document with its position.
+#[derive(Debug, Clone, Copy)]
+struct SourceLine<'a> {
+	number: usize,
+	start: usize,
+	text: &'a str,
+}
+
+impl SourceLine<'_> {
+	/// The span of the line without surrounding whitespace.
+	fn trimmed_span(&self) -> SourceSpan {
+		let leading = self.text.len() - self.text.trim_start().len();
+		let trailing = self.text.len() - self.text.trim_end().len();
+		self.span(leading, self.text.len() - trailing)
+	}
+
+	/// The span of `from..to`, relative to the start of the line.
+	fn span(&self, from: usize, to: usize) -> SourceSpan {
+		SourceSpan::new(self.number, self.start, self.start + from, self.start + to)
+	}
+
+	/// The span of a part of the line, given as a subslice of its text.
+	fn span_of(&self, part: &str) -> SourceSpan {
+		let from = part.as_ptr() as usize - self.text.as_ptr() as usize;
+		self.span(from, from + part.len())
+	}
+}
+
+/// A heading section which has been started but not yet ended.
+#[derive(Debug, Clone)]
+struct OpenSection {
+	heading: String,
+	level: usize,
+	task: Option<TaskId>,
+	start: usize,
+	line: usize,
+	line_start: usize,
+}
+
+/// Emits instrumentation events in source order while a document is parsed.
+///
+/// The parser hands over each task as soon as it has been parsed, and the emitter walks the
+/// lines of the document up to the next task header. A document which fails to parse thus
+/// still reports the events before the error.
+pub(crate) struct EventEmitter<'a, I: Instrumentation> {
+	instrumentation: &'a mut I,
+	content: &'a str,
+	/// The kinds of the tasks declared by task headers, by id.
+	declared: HashMap<TaskId, Kind>,
+	/// The task whose header comes next or whose section is being walked.
+	parsed: Option<Task>,
+	/// The index of the next line to walk.
+	next_line: usize,
+	sections: Vec<OpenSection>,
+	task: Option<TaskId>,
+	subtask: Option<String>,
+	in_contents: bool,
+	seen_fields: HashSet<String>,
+	seen_subtasks: HashSet<String>,
+	last_end: usize,
+}
+
+impl<'a, I: Instrumentation> EventEmitter<'a, I> {
+	pub(crate) fn new(content: &'a str, instrumentation: &'a mut I) -> Self {
+		Self {
+			instrumentation,
+			content,
+			declared: content
+				.lines()
+				.filter_map(|line| outline::task_header(line.trim()))
+				.map(|(kind, id, _)| (id, kind))
+				.collect(),
+			parsed: None,
+			next_line: 0,
+			sections: Vec::new(),
+			task: None,
+			subtask: None,
+			in_contents: false,
+			seen_fields: HashSet::new(),
+			seen_subtasks: HashSet::new(),
+			last_end: 0,
+		}
+	}
+
+	/// Hand over a task which has been parsed, before walking its section.
+	pub(crate) fn task_parsed(&mut self, task: Task) {
+		self.parsed = Some(task);
+	}
+
+	/// Walk the lines before the one-based line `line`, emitting events for each of them.
+	pub(crate) fn emit_until(&mut self, line: usize) -> Result<(), MarkdownParseError> {
+		let content = self.content;
+
+		for (index, text) in content.lines().enumerate().take(line - 1).skip(self.next_line) {
+			let line = SourceLine {
+				number: index + 1,
+				start: text.as_ptr() as usize - content.as_ptr() as usize,
+				text,
+			};
+			let trimmed = text.trim();
+
+			if let Some(level) = Self::heading_level(trimmed) {
+				self.on_heading(&line, level)?;
+			} else if self.task.is_some() && !trimmed.is_empty() {
+				self.on_task_line(&line)?;
+			}
+
+			if !trimmed.is_empty() {
+				self.last_end = line.trimmed_span().end;
+			}
+			self.next_line = index + 1;
+		}
+
+		Ok(())
+	}
+
+	/// Walk the lines before the one-based line `line`, or the rest of the document, and end
+	/// every open section.
+	pub(crate) fn finish(mut self, line: Option<usize>) -> Result<(), MarkdownParseError> {
+		self.emit_until(line.unwrap_or(usize::MAX))?;
+		self.close_sections(0)
+	}
+
+	/// Handle a heading: close the sections it ends and open its own.
+	fn on_heading(
+		&mut self,
+		line: &SourceLine<'_>,
+		level: usize,
+	) -> Result<(), MarkdownParseError> {
+		self.close_sections(level)?;
+
+		let trimmed = line.text.trim();
+		let heading = trimmed[level..].trim().to_string();
+		self.in_contents = false;
+
+		if level <= 3 {
+			self.task = outline::task_header(trimmed).map(|(_, id, _)| id);
+			self.subtask = None;
+			self.seen_fields.clear();
+			self.seen_subtasks.clear();
+		} else {
+			self.subtask =
+				self.task.and(outline::subtask_header(trimmed).map(|(_, id, _)| id.to_string()));
+			self.seen_fields.clear();
+		}
+
+		let span = line.trimmed_span();
+		let task = self.task;
+		self.sections.push(OpenSection {
+			heading: heading.clone(),
+			level,
+			task,
+			start: span.start,
+			line: line.number,
+			line_start: line.start,
+		});
+		self.instrumentation.on_section_started(SectionEvent {
+			heading: heading.clone(),
+			level,
+			task,
+			span,
+		})?;
+
+		match (self.task, self.subtask.clone()) {
+			(Some(task), None) if level == 3 && self.parsed_task(task).is_some() => {
+				self.instrumentation.on_task_parsed(TaskParsedEvent {
+					task,
+					title_line_string: trimmed.to_string(),
+					span,
+				})?;
+			}
+			(Some(task), Some(subtask)) if level > 3 => {
+				let title = heading.split_once(':').map(|(_, title)| title.trim()).unwrap_or("");
+				self.subtask_parsed(task, &subtask, title, span)?;
+			}
+			_ => {}
+		}
+
+		Ok(())
+	}
+
+	/// Handle a non-empty, non-heading line within a task section.
+	fn on_task_line(&mut self, line: &SourceLine<'_>) -> Result<(), MarkdownParseError> {
+		let Some(task) = self.task else {
+			return Ok(());
+		};
+		let trimmed = line.text.trim();
+
+		if let Some((field, value)) = outline::field_line(trimmed) {
+			self.in_contents = field == "Contents" && self.subtask.is_none();
+			return self.on_field(line, task, field, value);
+		}
+
+		if self.in_contents {
+			match Self::subtask_entry(trimmed) {
+				Some((subtask, title)) => {
+					self.subtask_parsed(task, subtask, title, line.trimmed_span())?;
+				}
+				None if !trimmed.starts_with('-') => self.in_contents = false,
+				None => {}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Handle a `- **Field:** value` line.
+	fn on_field(
+		&mut self,
+		line: &SourceLine<'_>,
+		task: TaskId,
+		field: &str,
+		value: &str,
+	) -> Result<(), MarkdownParseError> {
+		let span = line.trimmed_span();
+		let event = FieldParsedEvent {
+			task,
+			subtask: self.subtask.clone(),
+			field: field.to_string(),
+			value: value.to_string(),
+			line: line.number,
+			span,
+			value_span: line.span_of(value),
+		};
+
+		if !self.seen_fields.insert(field.to_string()) {
+			self.warn(format!("Field '{field}' is declared more than once"), span)?;
+		}
+
+		// Custom fields are kept with the task; only fields of subtasks can be unknown
+		if self.subtask.is_some() && !KNOWN_FIELDS.contains(&field) {
+			return self.instrumentation.on_unknown_field(event);
+		}
+		self.instrumentation.on_field_parsed(event)?;
+
+		if self.subtask.is_some() {
+			return Ok(());
+		}
+
+		let Some(parsed) = self.parsed_task(task) else {
+			return Ok(());
+		};
+
+		match field {
+			"Depends-on" => {
+				let dependencies: Vec<TaskId> = parsed.depends_on().iter().copied().collect();
+				for dependency in dependencies {
+					let reference = self.label(dependency);
+					let part = ['T', 'M']
+						.iter()
+						.find_map(|prefix| {
+							Self::find_reference(value, &format!("{prefix}{}", dependency.value()))
+						})
+						.unwrap_or(value);
+					let span = line.span_of(part);
+
+					self.instrumentation.on_dependency_parsed(DependencyParsedEvent {
+						task,
+						dependency,
+						span,
+					})?;
+
+					if !self.declared.contains_key(&dependency) {
+						self.warn(
+							format!(
+								"{} depends on {reference}, which is not defined",
+								self.label(task)
+							),
+							span,
+						)?;
+					}
+				}
+			}
+			"Starts" => {
+				let reference = parsed.range.start.point_of_reference().0;
+				if !parsed.is_root() && reference != task && !self.declared.contains_key(&reference)
+				{
+					self.warn(
+						format!(
+							"{} starts relative to {}, which is not defined",
+							self.label(task),
+							self.label(reference)
+						),
+						line.span_of(value),
+					)?;
+				}
+			}
+			_ => {}
+		}
+
+		Ok(())
+	}
+
+	/// The parsed task with an id, if it has been handed over.
+	fn parsed_task(&self, task: TaskId) -> Option<&Task> {
+		self.parsed.as_ref().filter(|parsed| *parsed.id() == task)
+	}
+
+	/// The reference to a task, e.g. `T3`, or `M3` for a declared milestone.
+	fn label(&self, task: TaskId) -> String {
+		let prefix = self.declared.get(&task).map_or('T', |kind| kind.prefix());
+		format!("{prefix}{}", task.value())
+	}
+
+	/// Emit a subtask event, once for each subtask of a task.
+	///
+	/// A subtask listed under `Contents` and declared in a `####` subsection is reported at
+	/// whichever comes first.
+	fn subtask_parsed(
+		&mut self,
+		task: TaskId,
+		subtask: &str,
+		title: &str,
+		span: SourceSpan,
+	) -> Result<(), MarkdownParseError> {
+		if !self.seen_subtasks.insert(subtask.to_string()) {
+			return Ok(());
+		}
+
+		self.instrumentation.on_subtask_parsed(SubtaskParsedEvent {
+			task,
+			subtask: subtask.to_string(),
+			title: title.to_string(),
+			span,
+		})
+	}
+
+	/// Emit a warning.
+	fn warn(&mut self, message: String, span: SourceSpan) -> Result<(), MarkdownParseError> {
+		self.instrumentation.on_warning(WarningEvent { message, span })
+	}
+
+	/// End every open section with a level of at least `level`.
+	fn close_sections(&mut self, level: usize) -> Result<(), MarkdownParseError> {
+		while self.sections.last().is_some_and(|section| section.level >= level) {
+			let Some(section) = self.sections.pop() else {
+				break;
+			};
+
+			let span = SourceSpan::new(
+				section.line,
+				section.line_start,
+				section.start,
+				self.last_end.max(section.start),
+			);
+			self.instrumentation.on_section_ended(SectionEvent {
+				heading: section.heading,
+				level: section.level,
+				task: section.task,
+				span,
+			})?;
+		}
+
+		Ok(())
+	}
+
+	/// The level of a heading line, if the line is a heading.
+	fn heading_level(line: &str) -> Option<usize> {
+		let level = line.chars().take_while(|c| *c == '#').count();
+		let is_heading = (1..=6).contains(&level) && line[level..].starts_with(' ');
+		is_heading.then_some(level)
+	}
+
+	/// Parse a `Contents` entry like `- **[T1.1](#t11-title)**: Title` or `- **T1.1**: Title`.
+	///
+	/// Task-list entries like `- [x] **[T1.1](#t11-title)**: Title` are accepted as well.
+	fn subtask_entry(line: &str) -> Option<(&str, &str)> {
+		let rest = line.strip_prefix("- ")?;
+		let rest = ["[ ] ", "[x] ", "[X] "]
+			.iter()
+			.find_map(|checkbox| rest.strip_prefix(checkbox))
+			.unwrap_or(rest);
+		let rest = rest.strip_prefix("**")?;
+		let (label, title) = rest.split_once("**")?;
+		let id = match label.strip_prefix('[') {
+			Some(link) => link.split_once(']')?.0,
+			None => label.trim_end_matches(':'),
+		};
+
+		id.starts_with('T').then_some((id, title.trim_start_matches(':').trim()))
+	}
+
+	/// Find a `T1` style reference as a whole token, preferring a `[T1](...)` link.
+	fn find_reference<'s>(value: &'s str, reference: &str) -> Option<&'s str> {
+		let link = format!("[{reference}]");
+		if let Some(start) = value.find(&link) {
+			let end = value[start..].find(')').map_or(start + link.len(), |end| start + end + 1);
+			return Some(&value[start..end]);
+		}
+
+		value.match_indices(reference).map(|(start, _)| start).find_map(|start| {
+			let end = start + reference.len();
+			let bounded = !value[end..].starts_with(|c: char| c.is_ascii_alphanumeric());
+			bounded.then(|| &value[start..end])
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::instrument::*;
+	use crate::tests::oroad::OROAD_0;
+	use crate::RoadlineParser;
+
+	/// Records every event as a line of text.
+	#[derive(Debug, Default)]
+	struct Recorder {
+		events: Vec<String>,
+		spans: Vec<SourceSpan>,
+	}
+
+	impl Instrumentation for Recorder {
+		fn on_task_parsed(&mut self, event: TaskParsedEvent) -> Result<(), MarkdownParseError> {
+			self.events.push(format!("task T{}", event.task.value()));
+			self.spans.push(event.span);
+			Ok(())
+		}
+
+		fn on_subtask_parsed(
+			&mut self,
+			event: SubtaskParsedEvent,
+		) -> Result<(), MarkdownParseError> {
+			self.events.push(format!("subtask {}: {}", event.subtask, event.title));
+			self.spans.push(event.span);
+			Ok(())
+		}
+
+		fn on_dependency_parsed(
+			&mut self,
+			event: DependencyParsedEvent,
+		) -> Result<(), MarkdownParseError> {
+			self.events.push(format!(
+				"dependency T{} -> T{}",
+				event.task.value(),
+				event.dependency.value()
+			));
+			self.spans.push(event.span);
+			Ok(())
+		}
+
+		fn on_field_parsed(&mut self, event: FieldParsedEvent) -> Result<(), MarkdownParseError> {
+			self.events
+				.push(format!("field {} = {} @{}", event.field, event.value, event.line));
+			self.spans.push(event.value_span);
+			Ok(())
+		}
+
+		fn on_unknown_field(&mut self, event: FieldParsedEvent) -> Result<(), MarkdownParseError> {
+			self.events.push(format!("unknown {} in {:?}", event.field, event.subtask));
+			self.spans.push(event.span);
+			Ok(())
+		}
+
+		fn on_section_started(&mut self, event: SectionEvent) -> Result<(), MarkdownParseError> {
+			self.events.push(format!("start {} {}", event.level, event.heading));
+			self.spans.push(event.span);
+			Ok(())
+		}
+
+		fn on_section_ended(&mut self, event: SectionEvent) -> Result<(), MarkdownParseError> {
+			self.events.push(format!("end {} {}", event.level, event.heading));
+			self.spans.push(event.span);
+			Ok(())
+		}
+
+		fn on_warning(&mut self, event: WarningEvent) -> Result<(), MarkdownParseError> {
+			self.events.push(format!("warning {}", event.message));
+			self.spans.push(event.span);
+			Ok(())
+		}
+	}
+
+	const DOCUMENT: &str = "## Roadmap
+- **Contents:**
+    - **[T1](#t1-first):** First
+
+### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** 1 month
+- **Contents:**
+    - **[T1.1](#t11-draft)**: Draft
+
+#### T1.1: Draft
+- **Lead:** [Ada](ada@example.com)
+- **Reviewer:** Grace
+
+### T2: Second
+- **Starts:** T1 + 1 month
+- **Depends-on:** [T1](#t1-first)
+- **Ends:** 1 month
+- **Ends:** 2 months
+- **Priority:** P1
+";
+
+	#[test]
+	fn test_events_in_source_order() -> Result<(), MarkdownParseError> {
+		let mut recorder = Recorder::default();
+		RoadlineParser::new().parse_tasks_with_instrumentation(DOCUMENT, &mut recorder)?;
+
+		assert_eq!(
+			recorder.events,
+			vec![
+				"start 2 Roadmap",
+				"start 3 T1: First",
+				"task T1",
+				"field Starts = T1 + 0 months @6",
+				"field Depends-on = $\\emptyset$ @7",
+				"field Ends = 1 month @8",
+				"field Contents =  @9",
+				"subtask T1.1: Draft",
+				"start 4 T1.1: Draft",
+				"field Lead = [Ada](ada@example.com) @13",
+				"unknown Reviewer in Some(\"T1.1\")",
+				"end 4 T1.1: Draft",
+				"end 3 T1: First",
+				"start 3 T2: Second",
+				"task T2",
+				"field Starts = T1 + 1 month @17",
+				"field Depends-on = [T1](#t1-first) @18",
+				"dependency T2 -> T1",
+				"field Ends = 1 month @19",
+				"warning Field 'Ends' is declared more than once",
+				"field Ends = 2 months @20",
+				"field Priority = P1 @21",
+				"end 3 T2: Second",
+				"end 2 Roadmap",
+			]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_event_spans() -> Result<(), MarkdownParseError> {
+		let mut recorder = Recorder::default();
+		RoadlineParser::new().parse_tasks_with_instrumentation(DOCUMENT, &mut recorder)?;
+
+		let text = |index: usize| recorder.spans[index].text(DOCUMENT);
+		assert_eq!(text(0), Some("## Roadmap"));
+		assert_eq!(text(2), Some("### T1: First"));
+		assert_eq!(text(3), Some("T1 + 0 months"));
+		assert_eq!(text(7), Some("- **[T1.1](#t11-draft)**: Draft"));
+		assert_eq!(text(17), Some("[T1](#t1-first)"));
+		assert_eq!(text(23).map(str::trim_end), Some(DOCUMENT.trim_end()));
+
+		assert_eq!(recorder.spans[7].line, 10);
+		assert_eq!(recorder.spans[7].column, 5);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_events_before_parse_error() {
+		const FAILING: &str = "### M1: Kickoff
+- **Starts:** T0 + 0 weeks
+- **Ends:** 0 weeks
+
+### T2: Build
+- **Starts:** M1 + 0 weeks
+- **Depends-on:** [M1](#m1-kickoff)
+- **Ends:** 2 weeks
+
+### T3: Ship
+- **Ends:** soon
+";
+		let mut recorder = Recorder::default();
+		let result = RoadlineParser::new().parse_tasks_with_instrumentation(FAILING, &mut recorder);
+
+		assert!(result.is_err());
+		assert!(recorder.events.contains(&"task T2".to_string()));
+		assert!(!recorder.events.contains(&"task T3".to_string()));
+		assert!(recorder.events.contains(&"field Ends = soon @11".to_string()));
+		assert_eq!(recorder.events.last().map(String::as_str), Some("end 3 T3: Ship"));
+
+		let dependency = recorder.events.iter().position(|e| e == "dependency T2 -> T1");
+		let span = dependency.map(|index| recorder.spans[index]);
+		assert_eq!(span.and_then(|span| span.text(FAILING)), Some("[M1](#m1-kickoff)"));
+	}
+
+	#[test]
+	fn test_oroad_events() -> Result<(), MarkdownParseError> {
+		let mut recorder = Recorder::default();
+		RoadlineParser::new().parse_tasks_with_instrumentation(OROAD_0, &mut recorder)?;
+
+		let count = |prefix: &str| recorder.events.iter().filter(|e| e.starts_with(prefix)).count();
+		assert_eq!(count("task "), 9);
+		assert_eq!(count("dependency "), 8);
+		assert_eq!(count("start "), count("end "));
+		assert_eq!(count("warning "), 0);
+
+		Ok(())
+	}
+}
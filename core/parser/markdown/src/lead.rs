@@ -0,0 +1,116 @@
+//! Lead parsing functionality for markdown roadmap documents.
+
+use roadline_util::task::subtask::Lead;
+
+/// Parser for lists of people written as markdown links.
+///
+/// Leads and authors are written as `[Name](email)` links separated by commas,
+/// where the link target may carry a `mailto:` prefix.
+#[derive(Debug, Clone)]
+pub struct LeadParser {
+	// Configuration for lead parsing
+}
+
+impl Default for LeadParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl LeadParser {
+	/// Create a new lead parser.
+	pub fn new() -> Self {
+		Self {}
+	}
+
+	/// Parse every `[Name](email)` link in a field value.
+	///
+	/// Entries without a link are kept as a lead with an empty email.
+	pub fn parse_leads(&self, value: &str) -> Vec<Lead> {
+		let mut leads = Vec::new();
+		let mut rest = value.trim();
+
+		while !rest.is_empty() {
+			let entry_end = Self::entry_end(rest);
+			let entry = rest[..entry_end].trim();
+
+			if let Some(lead) = self.parse_lead(entry) {
+				leads.push(lead);
+			}
+
+			rest = rest[entry_end..].trim_start_matches([',', ';']).trim();
+		}
+
+		leads
+	}
+
+	/// Parse a single `[Name](email)` entry.
+	pub fn parse_lead(&self, entry: &str) -> Option<Lead> {
+		let entry = entry.trim().trim_matches('*').trim();
+		if entry.is_empty() {
+			return None;
+		}
+
+		let link = entry.strip_prefix('[').and_then(|rest| {
+			let (name, target) = rest.split_once("](")?;
+			let target = target.strip_suffix(')')?;
+			Some((name, target))
+		});
+
+		match link {
+			Some((name, target)) => Some(Lead::new(
+				name.trim().to_string(),
+				target.trim().trim_start_matches("mailto:").to_string(),
+			)),
+			None => Some(Lead::new(entry.to_string(), String::new())),
+		}
+	}
+
+	/// Find the end of the first entry, ignoring separators inside links.
+	fn entry_end(value: &str) -> usize {
+		let mut depth = 0usize;
+
+		for (index, c) in value.char_indices() {
+			match c {
+				'[' | '(' => depth += 1,
+				']' | ')' => depth = depth.saturating_sub(1),
+				',' | ';' if depth == 0 => return index,
+				_ => {}
+			}
+		}
+
+		value.len()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_leads() {
+		let parser = LeadParser::new();
+		let leads = parser.parse_leads(
+			"[Liam Monninger](liam@ramate.io), [Ada Lovelace](mailto:ada@example.com)",
+		);
+
+		assert_eq!(
+			leads,
+			vec![
+				Lead::new("Liam Monninger".to_string(), "liam@ramate.io".to_string()),
+				Lead::new("Ada Lovelace".to_string(), "ada@example.com".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn test_parse_lead_without_link() {
+		let parser = LeadParser::new();
+
+		assert_eq!(
+			parser.parse_lead("Liam Monninger"),
+			Some(Lead::new("Liam Monninger".to_string(), String::new()))
+		);
+		assert_eq!(parser.parse_lead("  "), None);
+	}
+}
@@ -4,26 +4,53 @@
 //! used by OAC (Ordered Atomic Collaboration) roadmaps. It parses tasks, subtasks,
 //! dependencies, and dates into the roadline representation system.
 
+pub mod annotation;
 pub mod dependency;
+pub mod document;
+pub mod duration;
 pub mod error;
+pub mod format;
+pub mod fragment;
 pub mod instrument;
+pub mod lead;
+pub mod link;
+pub mod loader;
+pub(crate) mod outline;
 pub mod range;
+pub mod refactor;
+pub mod rich_text;
 pub mod subtask;
 pub mod summary;
 pub mod task;
 pub mod tests;
 
+pub use annotation::AnnotationParser;
 pub use dependency::DependencyParser;
+pub use document::{DocumentParser, FrontMatter, LeadCheck, RoadmapDocument};
+pub use duration::DurationParser;
 pub use error::MarkdownParseError;
-pub use instrument::{Instrumentation, TaskParsedEvent};
+pub use format::RoadmapFormatter;
+pub use instrument::{
+	DependencyParsedEvent, FieldParsedEvent, Instrumentation, SectionEvent, SourceSpan,
+	SubtaskParsedEvent, TaskParsedEvent, WarningEvent,
+};
+pub use lead::LeadParser;
+pub use link::{DocumentLink, LinkKind, LinkParser};
+pub use loader::{ComposedRoadmap, DocumentSource, NamespacedTaskId, RoadmapLoader};
 pub use range::{EndDate, StartDate};
+pub use refactor::{RenameReport, TaskRenamer, UnrewrittenReference};
+pub use rich_text::RichTextParser;
 pub use subtask::SubtaskParser;
 pub use summary::SummaryParser;
 pub use task::TaskParser;
 
+use crate::instrument::emitter::EventEmitter;
 use crate::task::TaskSection;
-use roadline_representation_core::roadline::RoadlineBuilder;
-use roadline_util::task::Task;
+use roadline_representation_core::range_algebra::explain::{Explainer, Explanation};
+use roadline_representation_core::roadline::RoadlineBuilderError;
+use roadline_util::task::{Id as TaskId, Task};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Main parser for roadmap markdown documents.
 ///
@@ -58,6 +85,7 @@ use roadline_util::task::Task;
 pub struct RoadlineParser {
 	pub task_parser: TaskParser,
 	pub dependency_parser: DependencyParser,
+	pub document_parser: DocumentParser,
 }
 
 impl Default for RoadlineParser {
@@ -69,7 +97,11 @@ impl Default for RoadlineParser {
 impl RoadlineParser {
 	/// Create a new roadmap parser with default configuration.
 	pub fn new() -> Self {
-		Self { task_parser: TaskParser::new(), dependency_parser: DependencyParser::new() }
+		Self {
+			task_parser: TaskParser::new(),
+			dependency_parser: DependencyParser::new(),
+			document_parser: DocumentParser::new(),
+		}
 	}
 
 	/// Parse a complete markdown document and return a vector of tasks.
@@ -85,6 +117,9 @@ impl RoadlineParser {
 	///
 	/// This method parses the entire document, extracting all tasks and their
 	/// subtasks, dependencies, and temporal information, while emitting instrumentation events.
+	/// Events are emitted in source order as the document is parsed, so a document which fails
+	/// to parse still reports the events up to the failing task section. Actual and completion
+	/// dates start at midnight in the timezone declared by the front matter, if any.
 	///
 	/// If you don't need instrumentation, use `parse_tasks` instead.
 	pub fn parse_tasks_with_instrumentation<I: Instrumentation>(
@@ -93,31 +128,86 @@ impl RoadlineParser {
 		instrumentation: &mut I,
 	) -> Result<Vec<Task>, MarkdownParseError> {
 		let mut tasks = Vec::new();
-		let mut task_sections = self.extract_task_sections(content)?;
-
-		// First pass: parse all tasks without dependencies
-		for section in &mut task_sections {
-			let task = self.task_parser.parse_task_section(section)?;
+		let task_parser = match self.document_parser.parse_front_matter(content)?.0.timezone {
+			Some(timezone) => Cow::Owned(self.task_parser.clone().with_timezone(timezone)),
+			None => Cow::Borrowed(&self.task_parser),
+		};
+		let task_sections = self.extract_task_sections(content)?;
+		let next_lines: Vec<usize> =
+			task_sections.iter().skip(1).map(|section| section.line_number).collect();
+		let mut emitter = EventEmitter::new(content, instrumentation);
+		let mut kinds = HashMap::new();
 
-			// Emit instrumentation event
-			let event =
-				TaskParsedEvent { task: *task.id(), title_line_string: section.header.clone() };
-			instrumentation.on_task_parsed(event)?;
+		for (i, section) in task_sections.iter().enumerate() {
+			emitter.emit_until(section.line_number)?;
 
-			tasks.push(task);
-		}
+			// Tasks and milestones share their numbering, so `T3` and `M3` may not both exist
+			let parsed = self.parse_task(&task_parser, section).and_then(|task| {
+				match kinds.insert(*task.id(), task.kind()) {
+					Some(kind) if kind != task.kind() => Err(MarkdownParseError::SharedTaskId {
+						task: format!("T{}", task.id().value()),
+						milestone: format!("M{}", task.id().value()),
+					}),
+					_ => Ok(task),
+				}
+			});
 
-		// Second pass: resolve dependencies
-		for (i, section) in task_sections.iter().enumerate() {
-			if let Some(dependencies) = self.dependency_parser.parse_dependencies(section)? {
-				for dep_id in dependencies {
-					tasks[i].depends_on_mut().insert(dep_id);
+			match parsed {
+				Ok(task) => {
+					emitter.task_parsed(task.clone());
+					tasks.push(task);
+				}
+				Err(error) => {
+					// Report the events of the failing section before the error
+					emitter.finish(next_lines.get(i).copied())?;
+					return Err(error);
 				}
 			}
 		}
 
+		emitter.finish(None)?;
+
 		Ok(tasks)
 	}
+
+	/// Parse a task section with `task_parser`, including its dependencies.
+	fn parse_task(
+		&self,
+		task_parser: &TaskParser,
+		section: &TaskSection,
+	) -> Result<Task, MarkdownParseError> {
+		let mut task = task_parser.parse_task_section(section)?;
+
+		if let Some(dependencies) = self.dependency_parser.parse_dependencies(section)? {
+			task.depends_on_mut().extend(dependencies);
+		}
+		let subtask_dependencies = self.dependency_parser.parse_subtask_dependencies(section)?;
+		task.subtask_dependencies_mut().extend(subtask_dependencies);
+
+		Ok(task)
+	}
+
+	/// Parse a complete markdown document, including its document-level metadata.
+	///
+	/// Besides the tasks, this reads the title, `Authors`, `All leads` and the
+	/// front matter of the document.
+	pub fn parse_document(&self, content: &str) -> Result<RoadmapDocument, MarkdownParseError> {
+		let mut noop = NoOpInstrumentation;
+		self.parse_document_with_instrumentation(content, &mut noop)
+	}
+
+	/// Parse a complete markdown document with instrumentation, including its
+	/// document-level metadata.
+	///
+	/// If you don't need instrumentation, use `parse_document` instead.
+	pub fn parse_document_with_instrumentation<I: Instrumentation>(
+		&self,
+		content: &str,
+		instrumentation: &mut I,
+	) -> Result<RoadmapDocument, MarkdownParseError> {
+		let tasks = self.parse_tasks_with_instrumentation(content, instrumentation)?;
+		self.document_parser.parse(content, tasks)
+	}
 }
 
 /// No-op instrumentation implementation for when instrumentation is not needed.
@@ -147,7 +237,12 @@ impl RoadlineParser {
 
 	/// Parse a markdown document with instrumentation and build a roadline representation.
 	///
-	/// This is a convenience method that combines parsing and roadline building with instrumentation.
+	/// This is a convenience method that combines parsing and roadline building with
+	/// instrumentation. The start date declared by the document is used as the root date;
+	/// documents without one fall back to the default root date of [`RoadlineBuilder::new`].
+	/// Days are resolved in the timezone declared by the document, or UTC.
+	///
+	/// [`RoadlineBuilder::new`]: roadline_representation_core::roadline::RoadlineBuilder::new
 	///
 	/// If you don't need instrumentation, use `parse_and_build` instead.
 	pub fn parse_and_build_with_instrumentation<I: Instrumentation>(
@@ -155,16 +250,50 @@ impl RoadlineParser {
 		content: &str,
 		instrumentation: &mut I,
 	) -> Result<roadline_representation_core::roadline::Roadline, MarkdownParseError> {
-		let tasks = self.parse_tasks_with_instrumentation(content, instrumentation)?;
-		let mut builder = RoadlineBuilder::new();
-
-		for task in tasks {
-			builder.add_task(task)?;
-		}
+		let builder = self
+			.parse_document_with_instrumentation(content, instrumentation)?
+			.into_builder()?;
 
 		builder.build().map_err(|e| MarkdownParseError::RoadlineBuilder { source: e })
 	}
 
+	/// The lines the tasks of a markdown document are declared on, counting from 1.
+	pub fn task_lines(&self, content: &str) -> Result<HashMap<TaskId, usize>, MarkdownParseError> {
+		Ok(self
+			.extract_task_sections(content)?
+			.into_iter()
+			.filter_map(|section| {
+				let (reference, _) = section.header.trim_start_matches('#').split_once(':')?;
+				let (_, task_id) = task::parse_task_reference(reference)?;
+				Some((task_id, section.line_number))
+			})
+			.collect())
+	}
+
+	/// Explain why a markdown document could not be built into a roadline.
+	///
+	/// Scheduling errors, such as dependency cycles or tasks starting before their
+	/// dependencies end, are explained in terms of the titles and lines of the tasks
+	/// involved. Other errors are explained by their message.
+	pub fn explain(&self, content: &str, error: &MarkdownParseError) -> Vec<Explanation> {
+		let MarkdownParseError::RoadlineBuilder {
+			source: RoadlineBuilderError::RangeAlgebra { source },
+		} = error
+		else {
+			return vec![Explanation::new(error.to_string())];
+		};
+
+		let (Ok(document), Ok(lines)) = (self.parse_document(content), self.task_lines(content))
+		else {
+			return vec![Explanation::new(error.to_string())];
+		};
+
+		Explainer::new(&document.tasks)
+			.with_lines(lines)
+			.with_timezone(document.timezone_or_utc())
+			.explain(source)
+	}
+
 	/// Extract task sections from markdown content.
 	///
 	/// This method identifies and extracts individual task sections from the
@@ -177,8 +306,8 @@ impl RoadlineParser {
 		for (line_num, line) in lines.iter().enumerate() {
 			let line = line.trim();
 
-			// Check if this is a task header (starts with ### T)
-			if line.starts_with("### T") && line.contains(':') {
+			// Check if this is a task or milestone header (starts with ### T or ### M)
+			if task::is_task_header(line) {
 				// Save previous section if it exists
 				if let Some(section) = current_section.take() {
 					sections.push(section);
@@ -191,7 +320,8 @@ impl RoadlineParser {
 					line_number: line_num + 1,
 				});
 			} else if let Some(ref mut section) = current_section {
-				section.content.push(line.to_string());
+				// Keep indentation, which nested lists in summaries and subtasks depend on
+				section.content.push(lines[line_num].trim_end().to_string());
 			}
 		}
 
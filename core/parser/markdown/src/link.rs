@@ -0,0 +1,262 @@
+//! Links from tasks to other roadmap documents.
+//!
+//! A task may depend on, or start relative to, a task in another document, as in
+//! `- **Depends-on:** [OROAD-5 T3](../oroad-005/README.md#t3-utilities)`. It may also adopt
+//! a whole sub-roadmap, either through a `Sub-roadmaps` field or through a list of links
+//! following a sentence which mentions sub-roadmaps:
+//!
+//! ```markdown
+//! **T1** adopts the following sub-roadmaps:
+//!
+//! - **[OROAD-5](/oroad/oroad-005/README.md)**: Week 0
+//! ```
+
+use crate::outline;
+use roadline_util::task::Id as TaskId;
+
+/// What a link from a task to another document means.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkKind {
+	/// The task depends on the linked task.
+	Dependency,
+	/// The task starts relative to the linked task.
+	Start,
+	/// The task adopts the linked document as a sub-roadmap.
+	SubRoadmap,
+}
+
+/// A link from a task to another roadmap document.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocumentLink {
+	/// The task the link appears in.
+	pub task: TaskId,
+	pub kind: LinkKind,
+	/// The path of the linked document as written, without its fragment.
+	pub path: String,
+	/// The linked task, if the link points at one.
+	pub target: Option<TaskId>,
+	/// The text of the link, e.g. `OROAD-5 T3`.
+	pub text: String,
+	/// The one-based line number of the link.
+	pub line: usize,
+}
+
+/// A markdown link found in a line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MarkdownLink<'a> {
+	pub text: &'a str,
+	pub destination: &'a str,
+	/// The byte offset just past the closing parenthesis.
+	pub end: usize,
+}
+
+impl<'a> MarkdownLink<'a> {
+	/// The destination without its `#fragment`.
+	pub fn path(&self) -> &'a str {
+		self.destination.split_once('#').map_or(self.destination, |(path, _)| path)
+	}
+
+	/// The `#fragment` of the destination, if any.
+	pub fn fragment(&self) -> Option<&'a str> {
+		self.destination.split_once('#').map(|(_, fragment)| fragment)
+	}
+
+	/// Whether the link points at another markdown document rather than into this one.
+	pub fn is_document_link(&self) -> bool {
+		let path = self.path();
+		!path.is_empty() && !path.contains("://") && path.to_ascii_lowercase().ends_with(".md")
+	}
+
+	/// The task the link points at, taken from a `#t3-...` fragment or a trailing `T3` in the text.
+	pub fn target_task(&self) -> Option<TaskId> {
+		let from_fragment = self.fragment().and_then(|fragment| {
+			let digits: String = fragment
+				.strip_prefix(['t', 'T'])?
+				.chars()
+				.take_while(char::is_ascii_digit)
+				.collect();
+			digits.parse().ok()
+		});
+
+		from_fragment
+			.or_else(|| {
+				let last = self.text.split_whitespace().last()?;
+				last.strip_prefix('T')?.parse().ok()
+			})
+			.map(TaskId::new)
+	}
+}
+
+/// Parser for links from tasks to other roadmap documents.
+#[derive(Debug, Clone)]
+pub struct LinkParser {
+	// Configuration for link parsing
+}
+
+impl Default for LinkParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl LinkParser {
+	/// Create a new link parser.
+	pub fn new() -> Self {
+		Self {}
+	}
+
+	/// Find every markdown link in a line, e.g. `[T1](#t1-title)`.
+	///
+	/// Nested brackets in the text are allowed, as in `[[Name](mailto:name@example.com)]`,
+	/// in which case the innermost link is returned.
+	pub fn find_links<'a>(&self, line: &'a str) -> Vec<MarkdownLink<'a>> {
+		let mut links = Vec::new();
+		let mut search_from = 0;
+
+		while let Some(middle) = line[search_from..].find("](").map(|index| search_from + index) {
+			let text_start = line[..middle].rfind('[').map(|index| index + 1);
+			let destination_end = line[middle + 2..].find(')').map(|index| middle + 2 + index);
+
+			match (text_start, destination_end) {
+				(Some(text_start), Some(destination_end)) => {
+					links.push(MarkdownLink {
+						text: &line[text_start..middle],
+						destination: line[middle + 2..destination_end].trim(),
+						end: destination_end + 1,
+					});
+					search_from = destination_end + 1;
+				}
+				_ => search_from = middle + 2,
+			}
+		}
+
+		links
+	}
+
+	/// Find the links from the tasks of a document to other documents.
+	pub fn parse(&self, content: &str) -> Vec<DocumentLink> {
+		let mut links = Vec::new();
+		let mut task = None;
+		let mut in_sub_roadmap_list = false;
+
+		for (index, raw_line) in content.lines().enumerate() {
+			let line = raw_line.trim();
+
+			if line.starts_with('#') {
+				in_sub_roadmap_list = false;
+				if let Some((_, id, _)) = outline::task_header(line) {
+					task = Some(id);
+				} else if !line.starts_with("####") {
+					task = None;
+				}
+				continue;
+			}
+
+			let Some(task) = task else {
+				continue;
+			};
+
+			let kind = match outline::field_line(line).map(|(field, _)| field) {
+				Some("Depends-on") => Some(LinkKind::Dependency),
+				Some("Starts") => Some(LinkKind::Start),
+				Some("Sub-roadmaps" | "Sub-roadmap") => Some(LinkKind::SubRoadmap),
+				Some(_) => None,
+				None if in_sub_roadmap_list && line.starts_with(['-', '*']) => {
+					Some(LinkKind::SubRoadmap)
+				}
+				None => {
+					if !line.is_empty() {
+						in_sub_roadmap_list = line.to_ascii_lowercase().contains("sub-roadmap");
+					}
+					None
+				}
+			};
+
+			let Some(kind) = kind else {
+				continue;
+			};
+
+			for link in self.find_links(line).into_iter().filter(MarkdownLink::is_document_link) {
+				// A start has one reference; a sub-roadmap entry names one document
+				if kind != LinkKind::Dependency
+					&& links.last().is_some_and(|last: &DocumentLink| last.line == index + 1)
+				{
+					break;
+				}
+
+				links.push(DocumentLink {
+					task,
+					kind,
+					path: link.path().to_string(),
+					target: if kind == LinkKind::SubRoadmap { None } else { link.target_task() },
+					text: link.text.to_string(),
+					line: index + 1,
+				});
+			}
+		}
+
+		links
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_find_links() {
+		let parser = LinkParser::new();
+		let links = parser.find_links("**[[Liam](mailto:liam@ramate.io)]** and [T1](#t1-title)");
+
+		assert_eq!(links.len(), 2);
+		assert_eq!(links[0].text, "Liam");
+		assert_eq!(links[1].destination, "#t1-title");
+		assert!(!links[1].is_document_link());
+
+		let external =
+			parser.find_links("[OROAD-5 T3](../oroad-005/README.md#t3-utilities) + 1 week");
+		assert!(external[0].is_document_link());
+		assert_eq!(external[0].path(), "../oroad-005/README.md");
+		assert_eq!(external[0].target_task(), Some(TaskId::new(3)));
+	}
+
+	#[test]
+	fn test_parse_document_links() {
+		let content = "# OROAD-1
+- **All leads:** [Ada](ada@example.com)
+
+### T1: First
+- **Starts:** [OROAD-5 T2](../oroad-005/README.md#t2-second) + 1 week
+- **Depends-on:** [T2](#t2-second), [OROAD-5 T3](../oroad-005/README.md#t3-third)
+- **Ends:** 1 month
+
+**T1** adopts the following sub-roadmaps:
+
+- **[OROAD-6](/oroad/oroad-006/README.md)**: Week 0
+- **[OROAD-7](oroad-007.md)**: Week 1
+
+Not a sub-roadmap: [OROAD-8](oroad-008.md).
+
+### T2: Second
+- **Sub-roadmaps:** [OROAD-9](oroad-009.md)
+";
+		let links = LinkParser::new().parse(content);
+		let summary: Vec<_> = links
+			.iter()
+			.map(|link| {
+				(link.task.value(), link.kind, link.path.as_str(), link.target.map(|id| id.value()))
+			})
+			.collect();
+
+		assert_eq!(
+			summary,
+			vec![
+				(1, LinkKind::Start, "../oroad-005/README.md", Some(2)),
+				(1, LinkKind::Dependency, "../oroad-005/README.md", Some(3)),
+				(1, LinkKind::SubRoadmap, "/oroad/oroad-006/README.md", None),
+				(1, LinkKind::SubRoadmap, "oroad-007.md", None),
+				(2, LinkKind::SubRoadmap, "oroad-009.md", None),
+			]
+		);
+	}
+}
@@ -0,0 +1,703 @@
+//! Loading roadmaps which span several documents.
+//!
+//! A roadmap may depend on the tasks of other roadmaps, start relative to them, or adopt
+//! them as sub-roadmaps. The [`RoadmapLoader`] follows these links, resolving their paths
+//! relative to the linking document, and merges every document it reaches into one
+//! [`ComposedRoadmap`] whose [`Graph`] spans all of them.
+
+use super::document::RoadmapDocument;
+use super::error::MarkdownParseError;
+use super::link::{DocumentLink, LinkKind};
+use super::RoadlineParser;
+use roadline_representation_core::graph::Graph;
+use roadline_representation_core::roadline::{Roadline, RoadlineBuilder};
+use roadline_util::task::range::{PointOfReference, Start, TargetDate};
+use roadline_util::task::{Id as TaskId, SubtaskDependency, Task};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration as StdDuration;
+
+/// Where the loader reads documents from.
+pub trait DocumentSource {
+	/// Read the document at a normalized path.
+	fn read(&self, path: &Path) -> Result<String, MarkdownParseError>;
+}
+
+/// Reads documents from the file system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FileSystemSource;
+
+impl DocumentSource for FileSystemSource {
+	fn read(&self, path: &Path) -> Result<String, MarkdownParseError> {
+		std::fs::read_to_string(path).map_err(|e| MarkdownParseError::DocumentRead {
+			path: path.display().to_string(),
+			message: e.to_string(),
+		})
+	}
+}
+
+/// Serves documents from memory, keyed by their normalized paths.
+impl DocumentSource for HashMap<PathBuf, String> {
+	fn read(&self, path: &Path) -> Result<String, MarkdownParseError> {
+		self.get(path).cloned().ok_or_else(|| MarkdownParseError::DocumentRead {
+			path: path.display().to_string(),
+			message: "no such document".to_string(),
+		})
+	}
+}
+
+/// A task as identified within its own document, e.g. `oroad-005/README.md#T3`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NamespacedTaskId {
+	/// The document the task is defined in.
+	pub document: PathBuf,
+	/// The id of the task within its document.
+	pub task: TaskId,
+}
+
+impl fmt::Display for NamespacedTaskId {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}#T{}", self.document.display(), self.task.value())
+	}
+}
+
+/// A document adopted as a sub-roadmap by a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubRoadmap {
+	/// The adopting task.
+	pub parent: NamespacedTaskId,
+	/// The adopted document.
+	pub document: PathBuf,
+}
+
+/// A document reached by the loader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadedDocument {
+	/// The normalized path of the document.
+	pub path: PathBuf,
+	/// The document, with its tasks under their own ids.
+	pub document: RoadmapDocument,
+	/// The id each task of the document has in the composed roadmap.
+	pub ids: BTreeMap<TaskId, TaskId>,
+}
+
+/// Several roadmap documents merged into one.
+///
+/// The tasks of the root document keep their ids. The tasks of other documents are given
+/// ids which are still free, and [`ComposedRoadmap::namespace`] maps them back.
+#[derive(Debug, Clone)]
+pub struct ComposedRoadmap {
+	/// The documents in the order they were loaded, starting with the root document.
+	pub documents: Vec<LoadedDocument>,
+	/// The tasks of every document under their composed ids, with links between documents resolved.
+	pub tasks: Vec<Task>,
+	/// The dependency graph of all tasks.
+	pub graph: Graph,
+	/// The document and local id of each composed task id.
+	pub namespaces: BTreeMap<TaskId, NamespacedTaskId>,
+	/// The sub-roadmaps adopted by tasks, in the order they were found.
+	pub sub_roadmaps: Vec<SubRoadmap>,
+}
+
+impl ComposedRoadmap {
+	/// The root document.
+	pub fn root(&self) -> Option<&LoadedDocument> {
+		self.documents.first()
+	}
+
+	/// The composed id of a task of a document.
+	pub fn task_id(&self, document: &Path, task: TaskId) -> Option<TaskId> {
+		self.documents
+			.iter()
+			.find(|loaded| loaded.path == document)?
+			.ids
+			.get(&task)
+			.copied()
+	}
+
+	/// The document and local id of a composed task id.
+	pub fn namespace(&self, task: &TaskId) -> Option<&NamespacedTaskId> {
+		self.namespaces.get(task)
+	}
+
+	/// Build a roadline of all tasks, starting on the start date of the root document.
+	///
+	/// Days are resolved in the timezone of the root document.
+	pub fn build(&self) -> Result<Roadline, MarkdownParseError> {
+		let root = self.root().map(|root| &root.document);
+		let mut builder = match root.and_then(|document| document.root_date()) {
+			Some(root_date) => RoadlineBuilder::with_start_date(root_date),
+			None => RoadlineBuilder::new(),
+		}
+		.with_timezone(root.map(|document| document.timezone_or_utc()).unwrap_or_default());
+
+		builder.add_tasks(self.tasks.iter().cloned())?;
+
+		builder.build().map_err(|e| MarkdownParseError::RoadlineBuilder { source: e })
+	}
+}
+
+/// Loader for roadmaps which link to other roadmap documents.
+///
+/// Relative link paths are resolved against the directory of the linking document. Paths
+/// starting with `/` are resolved against the base directory, which defaults to the
+/// directory of the root document.
+///
+/// # Example
+///
+/// ```no_run
+/// use roadline_parser_markdown::loader::RoadmapLoader;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let roadmap = RoadmapLoader::new()
+///     .with_base_dir(".")
+///     .load("oroad/oroad-000/README.md")?;
+/// let roadline = roadmap.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RoadmapLoader<S = FileSystemSource> {
+	parser: RoadlineParser,
+	source: S,
+	base_dir: Option<PathBuf>,
+}
+
+impl Default for RoadmapLoader {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RoadmapLoader {
+	/// Create a loader which reads documents from the file system.
+	pub fn new() -> Self {
+		Self::with_source(FileSystemSource)
+	}
+}
+
+impl<S: DocumentSource> RoadmapLoader<S> {
+	/// Create a loader which reads documents from the given source.
+	pub fn with_source(source: S) -> Self {
+		Self { parser: RoadlineParser::new(), source, base_dir: None }
+	}
+
+	/// Resolve link paths starting with `/` against a directory, e.g. the repository root.
+	pub fn with_base_dir(self, base_dir: impl Into<PathBuf>) -> Self {
+		Self { base_dir: Some(base_dir.into()), ..self }
+	}
+
+	/// Load a roadmap and every document it links to, directly or indirectly.
+	///
+	/// # Errors
+	///
+	/// Returns an error if a document cannot be read or parsed, if a link names a task
+	/// which does not exist, if the documents have more tasks than task ids can address,
+	/// or if dependencies form a cycle.
+	pub fn load(&self, path: impl AsRef<Path>) -> Result<ComposedRoadmap, MarkdownParseError> {
+		let root = normalize(path.as_ref());
+		let base_dir = match &self.base_dir {
+			Some(base_dir) => normalize(base_dir),
+			None => directory(&root),
+		};
+
+		let documents = self.load_documents(&root, &base_dir)?;
+		let documents = Self::assign_ids(documents)?;
+		let roadmap = Self::compose(documents, &base_dir)?;
+		Self::check_cycles(&roadmap.graph, &roadmap.namespaces)?;
+
+		Ok(roadmap)
+	}
+
+	/// Read and parse documents breadth first, following each link once.
+	fn load_documents(
+		&self,
+		root: &Path,
+		base_dir: &Path,
+	) -> Result<Vec<(PathBuf, RoadmapDocument)>, MarkdownParseError> {
+		let mut documents = Vec::new();
+		let mut seen = HashSet::from([root.to_path_buf()]);
+		let mut queue = VecDeque::from([root.to_path_buf()]);
+
+		while let Some(path) = queue.pop_front() {
+			let content = self.source.read(&path)?;
+			let document = self.parser.parse_document(&content)?;
+
+			for link in &document.links {
+				let linked = resolve(&path, &link.path, base_dir);
+				if seen.insert(linked.clone()) {
+					queue.push_back(linked);
+				}
+			}
+
+			documents.push((path, document));
+		}
+
+		Ok(documents)
+	}
+
+	/// Give every task an id in the composed roadmap.
+	///
+	/// The root document keeps its ids; other documents take the lowest ids still free.
+	fn assign_ids(
+		documents: Vec<(PathBuf, RoadmapDocument)>,
+	) -> Result<Vec<LoadedDocument>, MarkdownParseError> {
+		let used: BTreeSet<u8> = documents
+			.first()
+			.map(|(_, document)| document.tasks.iter().map(|task| task.id().value()).collect())
+			.unwrap_or_default();
+		let mut free = (1..=u8::MAX).filter(|id| !used.contains(id));
+
+		let mut loaded = Vec::with_capacity(documents.len());
+		for (index, (path, document)) in documents.into_iter().enumerate() {
+			let mut ids = BTreeMap::new();
+			for task in &document.tasks {
+				let id = if index == 0 {
+					*task.id()
+				} else {
+					free.next()
+						.map(TaskId::new)
+						.ok_or(MarkdownParseError::TooManyTasks { limit: usize::from(u8::MAX) })?
+				};
+				ids.insert(*task.id(), id);
+			}
+			loaded.push(LoadedDocument { path, document, ids });
+		}
+
+		Ok(loaded)
+	}
+
+	/// Move every task to its composed id and resolve the links between documents.
+	fn compose(
+		documents: Vec<LoadedDocument>,
+		base_dir: &Path,
+	) -> Result<ComposedRoadmap, MarkdownParseError> {
+		let indices: HashMap<&Path, usize> = documents
+			.iter()
+			.enumerate()
+			.map(|(index, loaded)| (loaded.path.as_path(), index))
+			.collect();
+
+		let mut tasks = Vec::new();
+		let mut namespaces = BTreeMap::new();
+		for loaded in &documents {
+			for task in &loaded.document.tasks {
+				let mut task = task.clone();
+				let local = *task.id();
+
+				*task.id_mut() = Self::local_id(loaded, local)?;
+				let depends_on = task
+					.depends_on()
+					.iter()
+					.map(|dependency| Self::local_id(loaded, *dependency))
+					.collect::<Result<_, _>>()?;
+				*task.depends_on_mut() = depends_on;
+				let subtask_dependencies = task
+					.subtask_dependencies()
+					.iter()
+					.map(|dependency| {
+						let task = Self::local_id(loaded, dependency.task())?;
+						Ok(SubtaskDependency::new(task, dependency.subtask()))
+					})
+					.collect::<Result<_, MarkdownParseError>>()?;
+				*task.subtask_dependencies_mut() = subtask_dependencies;
+
+				// T0 stands for the start of the roadmap rather than a task
+				let reference = task.range.start.point_of_reference().0;
+				if reference.value() != 0 {
+					task.range.start.0.point_of_reference =
+						PointOfReference::from(Self::local_id(loaded, reference)?);
+				}
+
+				namespaces.insert(
+					*task.id(),
+					NamespacedTaskId { document: loaded.path.clone(), task: local },
+				);
+				tasks.push(task);
+			}
+		}
+
+		let positions: HashMap<TaskId, usize> = tasks
+			.iter()
+			.enumerate()
+			.map(|(position, task)| (*task.id(), position))
+			.collect();
+
+		let mut sub_roadmaps = Vec::new();
+		for loaded in &documents {
+			for link in &loaded.document.links {
+				let linked_path = resolve(&loaded.path, &link.path, base_dir);
+				let linked = indices
+					.get(linked_path.as_path())
+					.map(|index| &documents[*index])
+					.ok_or_else(|| Self::unresolved(loaded, link))?;
+				let position = positions
+					.get(&Self::local_id(loaded, link.task)?)
+					.copied()
+					.ok_or_else(|| Self::unresolved(loaded, link))?;
+
+				match link.kind {
+					LinkKind::Dependency | LinkKind::Start => {
+						let target = link
+							.target
+							.and_then(|target| linked.ids.get(&target).copied())
+							.ok_or_else(|| Self::unresolved(loaded, link))?;
+
+						if link.kind == LinkKind::Dependency {
+							tasks[position].depends_on_mut().insert(target);
+						} else {
+							tasks[position].range.start.0.point_of_reference =
+								PointOfReference::from(target);
+						}
+					}
+					LinkKind::SubRoadmap => sub_roadmaps.push(SubRoadmap {
+						parent: NamespacedTaskId { document: loaded.path.clone(), task: link.task },
+						document: linked.path.clone(),
+					}),
+				}
+			}
+		}
+
+		for sub_roadmap in &sub_roadmaps {
+			Self::adopt(&documents, &indices, &positions, &mut tasks, sub_roadmap);
+		}
+
+		let mut graph = Graph::new();
+		for task in &tasks {
+			graph.add(task.clone())?;
+		}
+
+		Ok(ComposedRoadmap { documents, tasks, graph, namespaces, sub_roadmaps })
+	}
+
+	/// Start the root tasks of a sub-roadmap along with the task adopting it.
+	///
+	/// Root tasks of the sub-roadmap take on the dependencies and start reference of the
+	/// adopting task, with their own start offset added to its offset.
+	fn adopt(
+		documents: &[LoadedDocument],
+		indices: &HashMap<&Path, usize>,
+		positions: &HashMap<TaskId, usize>,
+		tasks: &mut [Task],
+		sub_roadmap: &SubRoadmap,
+	) {
+		let parent = indices
+			.get(sub_roadmap.parent.document.as_path())
+			.and_then(|index| documents[*index].ids.get(&sub_roadmap.parent.task))
+			.and_then(|id| positions.get(id))
+			.map(|position| tasks[*position].clone());
+		let child = indices.get(sub_roadmap.document.as_path()).map(|index| &documents[*index]);
+
+		let (Some(parent), Some(child)) = (parent, child) else {
+			return;
+		};
+
+		for id in child.ids.values() {
+			let Some(task) = positions.get(id).map(|position| &mut tasks[*position]) else {
+				continue;
+			};
+			if !task.is_root() || task.id() == parent.id() {
+				continue;
+			}
+
+			let offset = parent.range.start.0.signed_secs() + task.range.start.0.signed_secs();
+			let reference = if parent.is_root() {
+				PointOfReference::from(*task.id())
+			} else {
+				parent.range.start.point_of_reference().clone()
+			};
+			let duration =
+				StdDuration::from_secs(u64::try_from(offset.unsigned_abs()).unwrap_or(u64::MAX))
+					.into();
+
+			task.range.start = Start::from(if offset < 0 {
+				TargetDate::before(reference, duration)
+			} else {
+				TargetDate::new(reference, duration)
+			});
+			*task.depends_on_mut() = parent.depends_on().clone();
+			*task.subtask_dependencies_mut() = parent.subtask_dependencies().clone();
+		}
+	}
+
+	/// Fail if dependencies form a cycle, naming its tasks by document.
+	fn check_cycles(
+		graph: &Graph,
+		namespaces: &BTreeMap<TaskId, NamespacedTaskId>,
+	) -> Result<(), MarkdownParseError> {
+		let mut cycles: Vec<String> = graph
+			.find_cycles()?
+			.into_iter()
+			.map(|cycle| {
+				let mut labels: Vec<String> = cycle
+					.iter()
+					.map(|id| {
+						namespaces
+							.get(id)
+							.map_or_else(|| format!("T{}", id.value()), ToString::to_string)
+					})
+					.collect();
+				labels.sort();
+				labels.join(", ")
+			})
+			.collect();
+
+		if cycles.is_empty() {
+			return Ok(());
+		}
+
+		cycles.sort();
+		Err(MarkdownParseError::DependencyCycle { cycle: cycles.join("; ") })
+	}
+
+	/// The composed id of a task of a loaded document.
+	fn local_id(loaded: &LoadedDocument, task: TaskId) -> Result<TaskId, MarkdownParseError> {
+		loaded
+			.ids
+			.get(&task)
+			.copied()
+			.ok_or_else(|| MarkdownParseError::UnresolvedReference {
+				document: loaded.path.display().to_string(),
+				reference: format!("T{}", task.value()),
+			})
+	}
+
+	fn unresolved(loaded: &LoadedDocument, link: &DocumentLink) -> MarkdownParseError {
+		MarkdownParseError::UnresolvedReference {
+			document: loaded.path.display().to_string(),
+			reference: format!("[{}]({})", link.text, link.path),
+		}
+	}
+}
+
+/// Resolve a link path written in a document.
+fn resolve(document: &Path, link: &str, base_dir: &Path) -> PathBuf {
+	match link.strip_prefix('/') {
+		Some(rooted) => normalize(&base_dir.join(rooted)),
+		None => normalize(&directory(document).join(link)),
+	}
+}
+
+/// The directory containing a document.
+fn directory(document: &Path) -> PathBuf {
+	document.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/// Remove `.` and `..` components without touching the file system.
+fn normalize(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => match normalized.components().next_back() {
+				Some(Component::Normal(_)) => {
+					normalized.pop();
+				}
+				Some(Component::RootDir | Component::Prefix(_)) => {}
+				_ => normalized.push(".."),
+			},
+			other => normalized.push(other),
+		}
+	}
+
+	normalized
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const ROOT: &str = "# OROAD-0
+- **All leads:** [Ada](mailto:ada@example.com)
+
+### T1: Validation
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** T1 + 2 months
+
+**T1** adopts the following sub-roadmaps:
+
+- **[OROAD-5](/oroad/oroad-005/README.md)**: Week 0
+
+### T2: Release
+- **Starts:** [OROAD-5 T2](../oroad-005/README.md#t2-utilities) + 2 months
+- **Depends-on:** [T1](#t1-validation), [OROAD-5 T2](../oroad-005/README.md#t2-utilities)
+- **Ends:** T2 + 1 month
+";
+
+	const OROAD_5: &str = "# OROAD-5
+
+### T1: Tooling
+- **Starts:** T1 + 1 week
+- **Depends-on:** $\\emptyset$
+- **Ends:** T1 + 1 month
+
+### T2: Utilities
+- **Starts:** T1 + 1 month
+- **Depends-on:** [T1](#t1-tooling)
+- **Ends:** T2 + 1 month
+";
+
+	fn source(documents: &[(&str, &str)]) -> HashMap<PathBuf, String> {
+		documents
+			.iter()
+			.map(|&(path, content)| (PathBuf::from(path), content.to_string()))
+			.collect()
+	}
+
+	#[test]
+	fn test_load_cross_document_roadmap() -> Result<(), MarkdownParseError> {
+		let loader = RoadmapLoader::with_source(source(&[
+			("oroad/oroad-000/README.md", ROOT),
+			("oroad/oroad-005/README.md", OROAD_5),
+		]))
+		.with_base_dir("");
+		let roadmap = loader.load("oroad/./oroad-000/README.md")?;
+
+		assert_eq!(roadmap.documents.len(), 2);
+		assert_eq!(roadmap.tasks.len(), 4);
+
+		let oroad_5 = Path::new("oroad/oroad-005/README.md");
+		let tooling = roadmap.task_id(oroad_5, TaskId::new(1));
+		let utilities = roadmap.task_id(oroad_5, TaskId::new(2));
+		assert_eq!(tooling, Some(TaskId::new(3)));
+		assert_eq!(utilities, Some(TaskId::new(4)));
+		assert_eq!(
+			roadmap.namespace(&TaskId::new(4)).map(ToString::to_string),
+			Some("oroad/oroad-005/README.md#T2".to_string())
+		);
+
+		// The release depends on, and starts relative to, the utilities of OROAD-5
+		let release = roadmap.tasks.iter().find(|task| task.id().value() == 2);
+		assert_eq!(
+			release.map(|task| task.depends_on().iter().map(TaskId::value).collect::<Vec<_>>()),
+			Some(vec![1, 4])
+		);
+		assert_eq!(release.map(|task| task.range.start.point_of_reference().0.value()), Some(4));
+
+		// OROAD-5 is adopted by T1, which is a root, so its tooling starts a week into T1
+		assert_eq!(
+			roadmap.sub_roadmaps,
+			vec![SubRoadmap {
+				parent: NamespacedTaskId {
+					document: PathBuf::from("oroad/oroad-000/README.md"),
+					task: TaskId::new(1),
+				},
+				document: oroad_5.to_path_buf(),
+			}]
+		);
+		let tooling = roadmap.tasks.iter().find(|task| Some(*task.id()) == tooling);
+		assert!(tooling.is_some_and(|task| task.is_root()));
+		assert_eq!(tooling.map(|task| task.range.start.0.signed_secs()), Some(86400 * 7));
+
+		roadmap.build()?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_sub_roadmap_of_dependent_task() -> Result<(), MarkdownParseError> {
+		let root = "# Root
+
+### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** T1 + 1 month
+
+### T2: Second
+- **Starts:** T1 + 1 month
+- **Depends-on:** [T1](#t1-first)
+- **Ends:** T2 + 1 month
+- **Sub-roadmaps:** [Child](child/README.md)
+";
+		let child = "# Child
+
+### T1: Child task
+- **Starts:** T1 + 1 week
+- **Depends-on:** $\\emptyset$
+- **Ends:** T1 + 1 week
+";
+		let loader = RoadmapLoader::with_source(source(&[
+			("docs/README.md", root),
+			("docs/child/README.md", child),
+		]));
+		let roadmap = loader.load("docs/README.md")?;
+
+		let child_task = roadmap
+			.task_id(Path::new("docs/child/README.md"), TaskId::new(1))
+			.and_then(|id| roadmap.tasks.iter().find(|task| *task.id() == id));
+		assert_eq!(
+			child_task.map(|task| task.depends_on().iter().map(TaskId::value).collect::<Vec<_>>()),
+			Some(vec![1])
+		);
+		assert_eq!(child_task.map(|task| task.range.start.point_of_reference().0.value()), Some(1));
+		assert_eq!(child_task.map(|task| task.range.start.0.signed_secs()), Some(86400 * 37));
+
+		roadmap.build()?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_cross_document_cycle() {
+		let first = "### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** [B T1](b.md#t1-second)
+- **Ends:** T1 + 1 month
+";
+		let second = "### T1: Second
+- **Starts:** T1 + 0 months
+- **Depends-on:** [A T1](a.md#t1-first)
+- **Ends:** T1 + 1 month
+";
+		let loader = RoadmapLoader::with_source(source(&[("a.md", first), ("b.md", second)]));
+
+		match loader.load("a.md") {
+			Err(MarkdownParseError::DependencyCycle { cycle }) => {
+				assert_eq!(cycle, "a.md#T1, b.md#T1");
+			}
+			other => panic!("Expected a dependency cycle, got {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_unresolved_cross_document_reference() {
+		let first = "### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** [B T7](b.md#t7-missing)
+- **Ends:** T1 + 1 month
+";
+		let second = "### T1: Second
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** T1 + 1 month
+";
+		let loader = RoadmapLoader::with_source(source(&[("a.md", first), ("b.md", second)]));
+
+		assert!(matches!(loader.load("a.md"), Err(MarkdownParseError::UnresolvedReference { .. })));
+		assert!(matches!(
+			RoadmapLoader::with_source(source(&[("a.md", first)])).load("a.md"),
+			Err(MarkdownParseError::DocumentRead { .. })
+		));
+	}
+
+	#[test]
+	fn test_normalize() {
+		assert_eq!(normalize(Path::new("a/./b/../c.md")), PathBuf::from("a/c.md"));
+		assert_eq!(normalize(Path::new("../a/b.md")), PathBuf::from("../a/b.md"));
+		assert_eq!(
+			resolve(
+				Path::new("oroad/oroad-000/README.md"),
+				"../oroad-005/README.md",
+				Path::new("")
+			),
+			PathBuf::from("oroad/oroad-005/README.md")
+		);
+		assert_eq!(
+			resolve(Path::new("oroad/oroad-000/README.md"), "/docs/x.md", Path::new("repo")),
+			PathBuf::from("repo/docs/x.md")
+		);
+	}
+}
@@ -0,0 +1,94 @@
+//! The outline of roadmap markdown documents.
+//!
+//! The formatter, the refactorings, the instrumentation and the link parser all walk a
+//! document line by line. These helpers tell them which lines are task and subtask headers,
+//! which are `- **Field:** value` lines, and which section a heading starts.
+
+use crate::task::parse_task_reference;
+use roadline_util::task::{Id as TaskId, Kind};
+
+/// The kind of section a line belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Section {
+	Other,
+	Roadmap,
+	Task,
+}
+
+impl Section {
+	/// Determine the section started by a heading line, if the line is a heading.
+	///
+	/// `####` subsections stay within whatever section they are nested in.
+	pub(crate) fn for_heading(line: &str) -> Option<Self> {
+		if !line.starts_with('#') || line.starts_with("#### ") {
+			None
+		} else if task_header(line).is_some() {
+			Some(Self::Task)
+		} else if line == "## Roadmap" {
+			Some(Self::Roadmap)
+		} else {
+			Some(Self::Other)
+		}
+	}
+}
+
+/// Parse a task header like `### T4: Title` or `### M4: Title`.
+///
+/// Returns the kind and id of the task and the rest of the line, starting at the colon.
+pub(crate) fn task_header(line: &str) -> Option<(Kind, TaskId, &str)> {
+	let rest = line.strip_prefix("### ")?;
+	let colon = rest.find(':')?;
+	let (kind, id) = parse_task_reference(&rest[..colon])?;
+	Some((kind, id, &rest[colon..]))
+}
+
+/// Parse a subtask header like `#### T4.1: Title`.
+///
+/// Returns the id of the task, the subtask id as written, e.g. `T4.1`, and the rest of the
+/// line, starting at the colon.
+pub(crate) fn subtask_header(line: &str) -> Option<(TaskId, &str, &str)> {
+	let rest = line.strip_prefix("#### ")?;
+	let colon = rest.find(':')?;
+	let id = rest[..colon].trim();
+	let (task, subtask) = id.strip_prefix('T')?.split_once('.')?;
+	subtask.parse::<u8>().ok()?;
+	Some((TaskId::new(task.parse().ok()?), id, &rest[colon..]))
+}
+
+/// Parse a field line in the format "- **Field:** Value", returning the field and its value.
+///
+/// Field names consist of letters, spaces and hyphens, so that bold list items
+/// such as `- **T1.1:** ...` or `- **[T1](#t1)**: ...` are not mistaken for fields.
+pub(crate) fn field_line(line: &str) -> Option<(&str, &str)> {
+	let rest = line.strip_prefix("- **")?;
+	let (field, value) = rest.split_once(":**")?;
+
+	if field.is_empty() || !field.chars().all(|c| c.is_alphabetic() || c == ' ' || c == '-') {
+		return None;
+	}
+
+	Some((field, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_classifies_lines() {
+		assert_eq!(
+			task_header("### M3: Launch"),
+			Some((Kind::Milestone, TaskId::new(3), ": Launch"))
+		);
+		assert_eq!(task_header("### Motivation: why"), None);
+		assert_eq!(subtask_header("#### T3.2: Review"), Some((TaskId::new(3), "T3.2", ": Review")));
+		assert_eq!(subtask_header("#### Notes: none"), None);
+		assert_eq!(field_line("- **Depends-on:** [T1](#t1)"), Some(("Depends-on", "[T1](#t1)")));
+		assert_eq!(field_line("- **[T1.1](#t11)**: Draft"), None);
+
+		assert_eq!(Section::for_heading("### T1: First"), Some(Section::Task));
+		assert_eq!(Section::for_heading("## Roadmap"), Some(Section::Roadmap));
+		assert_eq!(Section::for_heading("#### T1.1: Draft"), None);
+		assert_eq!(Section::for_heading("- **Ends:** 1 month"), None);
+	}
+}
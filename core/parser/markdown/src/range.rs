@@ -7,6 +7,7 @@ pub use end::EndDate;
 pub use start::StartDate;
 
 use super::error::MarkdownParseError;
+use super::link::LinkParser;
 use roadline_util::task::{range::Range, Id as TaskId};
 
 /// Parser for task ranges in markdown documents.
@@ -17,6 +18,7 @@ use roadline_util::task::{range::Range, Id as TaskId};
 pub struct RangeParser {
 	start_parser: StartDate,
 	end_parser: EndDate,
+	link_parser: LinkParser,
 }
 
 impl Default for RangeParser {
@@ -28,16 +30,25 @@ impl Default for RangeParser {
 impl RangeParser {
 	/// Create a new range parser.
 	pub fn new() -> Self {
-		Self { start_parser: StartDate::new(), end_parser: EndDate::new() }
+		Self {
+			start_parser: StartDate::new(),
+			end_parser: EndDate::new(),
+			link_parser: LinkParser::new(),
+		}
 	}
 
 	/// Parse a complete range from start and end date expressions.
 	///
 	/// # Arguments
 	/// * `starts` - Required start date expression (e.g., "T0 + 0 months")
-	/// * `ends` - Required end date expression (e.g., "1 month")
+	/// * `ends` - Required end date expression (e.g., "1 month", "2–3–6 weeks" or "10 workdays")
 	/// * `task_id` - The task ID for reference
 	///
+	/// A start relative to a task in another document, e.g.
+	/// "[OROAD-5 T3](../oroad-005/README.md#t3-title) + 1 week", is read as relative to the
+	/// task itself, which treats it as a root of this document. The
+	/// [`RoadmapLoader`](crate::loader::RoadmapLoader) points it at the linked task.
+	///
 	/// # Returns
 	/// A `Range` object with parsed start and end dates.
 	///
@@ -47,11 +58,14 @@ impl RangeParser {
 		&self,
 		starts: Option<&str>,
 		ends: Option<&str>,
-		_task_id: &TaskId,
+		task_id: &TaskId,
 	) -> Result<Range, MarkdownParseError> {
 		// Parse the start date - required
 		let start = match starts {
-			Some(starts) => self.start_parser.parse(starts)?,
+			Some(starts) => match self.external_start(starts, task_id) {
+				Some(rewritten) => self.start_parser.parse(&rewritten)?,
+				None => self.start_parser.parse(starts)?,
+			},
 			None => {
 				return Err(MarkdownParseError::InvalidDateExpression {
 					expression: "Missing start date".to_string(),
@@ -60,8 +74,12 @@ impl RangeParser {
 		};
 
 		// Parse the end date - required
-		let end = match ends {
-			Some(ends) => self.end_parser.parse(ends)?,
+		let (end, estimate, workdays) = match ends {
+			Some(ends) => (
+				self.end_parser.parse(ends)?,
+				self.end_parser.parse_estimate(ends)?,
+				self.end_parser.parse_workdays(ends),
+			),
 			None => {
 				return Err(MarkdownParseError::InvalidDateExpression {
 					expression: "Missing end date".to_string(),
@@ -69,7 +87,21 @@ impl RangeParser {
 			}
 		};
 
-		Ok(Range::new(start, end))
+		let range = Range::new(start, end);
+		Ok(match (estimate, workdays) {
+			(Some(estimate), _) => range.with_estimate(estimate),
+			(None, Some(workdays)) => range.with_workdays(workdays),
+			(None, None) => range,
+		})
+	}
+
+	/// Rewrite a start relative to a task in another document to be relative to the task itself.
+	fn external_start(&self, starts: &str, task_id: &TaskId) -> Option<String> {
+		let starts = starts.trim();
+		let link = self.link_parser.find_links(starts).into_iter().next()?;
+
+		(starts.starts_with('[') && link.is_document_link())
+			.then(|| format!("T{} {}", task_id.value(), starts[link.end..].trim()))
 	}
 }
 
@@ -85,6 +117,43 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_parse_three_point_estimate() -> Result<(), MarkdownParseError> {
+		let parser = RangeParser::new();
+		let result = parser.parse(Some("T1 + 0 days"), Some("2–3–6 weeks"), &TaskId::new(1))?;
+		assert_eq!(result.end().duration().0.as_secs(), 86400 * 21);
+		assert_eq!(result.estimate().map(|e| e.optimistic().0.as_secs()), Some(86400 * 14));
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_workdays() -> Result<(), MarkdownParseError> {
+		let parser = RangeParser::new();
+		let result = parser.parse(Some("T1 + 0 days"), Some("10 workdays"), &TaskId::new(1))?;
+		assert_eq!(result.workdays(), Some(10));
+
+		// Estimates and start offsets may be counted in working days too
+		let result =
+			parser.parse(Some("T1 + 5 workdays"), Some("2–3–6 workdays"), &TaskId::new(2))?;
+		assert_eq!(result.start().workdays(), Some(5));
+		assert_eq!(result.workdays(), Some(3));
+		assert_eq!(result.estimate().map(|estimate| estimate.is_in_workdays()), Some(true));
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_external_start() -> Result<(), MarkdownParseError> {
+		let parser = RangeParser::new();
+		let result = parser.parse(
+			Some("[OROAD-5 T3](../oroad-005/README.md#t3-title) + 1 week"),
+			Some("1 month"),
+			&TaskId::new(2),
+		)?;
+		assert_eq!(result.start().point_of_reference().0.value(), 2);
+		assert_eq!(result.start().duration().0.as_secs(), 86400 * 7);
+		Ok(())
+	}
+
 	#[test]
 	fn test_parse_missing_start_date() {
 		let parser = RangeParser::new();
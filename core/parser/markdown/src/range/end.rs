@@ -1,8 +1,9 @@
 //! End date parsing functionality for markdown roadmap documents.
 
+use super::super::duration::DurationParser;
 use super::super::error::MarkdownParseError;
 use roadline_util::duration::Duration;
-use roadline_util::task::range::End;
+use roadline_util::task::range::{Direction, End, Estimate};
 use std::time::Duration as StdDuration;
 
 /// Represents different end date formats for backward compatibility.
@@ -17,7 +18,7 @@ pub enum EndDateFormat {
 /// Parser for end date expressions in markdown documents.
 #[derive(Debug, Clone)]
 pub struct EndDate {
-	// Configuration for end date parsing
+	duration_parser: DurationParser,
 }
 
 impl Default for EndDate {
@@ -29,7 +30,7 @@ impl Default for EndDate {
 impl EndDate {
 	/// Create a new end date parser.
 	pub fn new() -> Self {
-		Self {}
+		Self { duration_parser: DurationParser::new() }
 	}
 
 	/// Parse an end date expression from a string.
@@ -37,7 +38,21 @@ impl EndDate {
 	/// Expected formats:
 	/// - "T1 + 1 month" (old format: relative to task start)
 	/// - "1 month" (new format: duration from start)
+	///
+	/// The duration accepts the full grammar of [`DurationParser`], e.g. "1 month 2 weeks"
+	/// or "P1M2W". A three-point estimate such as "2–3–6 weeks" ends after its likely
+	/// duration; see [`EndDate::parse_estimate`]. Working days such as "10 workdays" end
+	/// after as many calendar days until a working calendar resolves them; see
+	/// [`EndDate::parse_workdays`].
 	pub fn parse(&self, expression: &str) -> Result<End, MarkdownParseError> {
+		if let Some(workdays) = self.parse_workdays(expression) {
+			return Ok(End::from(StdDuration::from_secs(u64::from(workdays) * 24 * 60 * 60)));
+		}
+
+		if let Some(estimate) = self.parse_estimate(expression)? {
+			return Ok(End::new(*estimate.likely()));
+		}
+
 		let format = self.parse_format(expression)?;
 
 		match format {
@@ -49,21 +64,49 @@ impl EndDate {
 		}
 	}
 
+	/// Parse a three-point estimate such as "2–3–6 weeks", or "2–3–6 workdays", from an end
+	/// date expression.
+	///
+	/// Returns `None` if the expression is a single duration.
+	pub fn parse_estimate(&self, expression: &str) -> Result<Option<Estimate>, MarkdownParseError> {
+		let Some(durations) = self.duration_parser.parse_estimate(expression) else {
+			return Ok(None);
+		};
+		let [optimistic, likely, pessimistic] = durations?.map(Duration::from);
+		let estimate = Estimate::new(optimistic, likely, pessimistic);
+
+		// The units of an estimate are those of its last part
+		let last = expression.rsplit(['–', '—', '-']).next().unwrap_or_default();
+		Ok(Some(match self.duration_parser.parse_workdays(last) {
+			Some(_) => estimate.in_workdays(),
+			None => estimate,
+		}))
+	}
+
+	/// Parse a number of working days such as "10 workdays", or "T1 + 10 workdays", from an
+	/// end date expression.
+	///
+	/// Returns `None` if the duration is not counted in working days.
+	pub fn parse_workdays(&self, expression: &str) -> Option<u32> {
+		let duration = match self.duration_parser.split_relative(expression) {
+			Some(relative) => relative.duration,
+			None => expression,
+		};
+		self.duration_parser.parse_workdays(duration)
+	}
+
 	/// Parse an end date expression into the appropriate format.
 	fn parse_format(&self, expression: &str) -> Result<EndDateFormat, MarkdownParseError> {
 		let expression = expression.trim();
 
-		if expression.starts_with('T') && expression.contains(" + ") {
-			// Old format: "T1 + 1 month"
-			let parts: Vec<&str> = expression.split(" + ").collect();
-			if parts.len() == 2 {
-				let task_ref = parts[0].trim().to_string();
-				let duration_str = parts[1].trim();
-				let duration = self.parse_duration(duration_str)?;
-				return Ok(EndDateFormat::TaskReference(task_ref, duration));
+		if let Some(relative) = self.duration_parser.split_relative(expression) {
+			// Old format: "T1 + 1 month"; an end cannot lie before its reference
+			if relative.direction == Direction::After {
+				let duration = self.parse_duration(relative.duration)?;
+				return Ok(EndDateFormat::TaskReference(relative.reference.to_string(), duration));
 			}
-		} else if expression.chars().any(|c| c.is_alphabetic()) && !expression.contains('-') {
-			// New format: "1 month", "2 weeks", etc. (contains letters but no dashes)
+		} else if expression.chars().any(|c| c.is_alphabetic()) {
+			// New format: "1 month", "2 weeks", "P1M2W", etc.
 			let duration = self.parse_duration(expression)?;
 			return Ok(EndDateFormat::Duration(duration));
 		}
@@ -73,45 +116,7 @@ impl EndDate {
 
 	/// Parse a duration expression into a standard duration.
 	fn parse_duration(&self, expression: &str) -> Result<StdDuration, MarkdownParseError> {
-		let expression = expression.trim().to_lowercase();
-
-		if expression.ends_with("month") || expression.ends_with("months") {
-			let number = self.extract_number(&expression)?;
-			Ok(StdDuration::from_secs(86400 * 30 * number as u64))
-		} else if expression.ends_with("week") || expression.ends_with("weeks") {
-			let number = self.extract_number(&expression)?;
-			Ok(StdDuration::from_secs(86400 * 7 * number as u64))
-		} else if expression.ends_with("day") || expression.ends_with("days") {
-			let number = self.extract_number(&expression)?;
-			Ok(StdDuration::from_secs(86400 * number as u64))
-		} else {
-			Err(MarkdownParseError::InvalidDurationExpression {
-				expression: expression.to_string(),
-			})
-		}
-	}
-
-	/// Extract a number from the beginning of a string.
-	fn extract_number(&self, s: &str) -> Result<u32, MarkdownParseError> {
-		let mut number_str = String::new();
-
-		for ch in s.chars() {
-			if ch.is_ascii_digit() {
-				number_str.push(ch);
-			} else {
-				break;
-			}
-		}
-
-		if number_str.is_empty() {
-			return Err(MarkdownParseError::InvalidDurationExpression {
-				expression: s.to_string(),
-			});
-		}
-
-		number_str.parse().map_err(|_| MarkdownParseError::InvalidDurationExpression {
-			expression: s.to_string(),
-		})
+		self.duration_parser.parse(expression)
 	}
 }
 
@@ -131,6 +136,32 @@ mod tests {
 		let result = parser.parse("T1 + 1 month")?;
 		assert_eq!(result.duration().0.as_secs(), 86400 * 30);
 
+		// Test richer durations
+		let result = parser.parse("P1M2W")?;
+		assert_eq!(result.duration().0.as_secs(), 86400 * 44);
+
+		let result = parser.parse("1.5 months")?;
+		assert_eq!(result.duration().0.as_secs(), 86400 * 45);
+
+		// An end cannot lie before its reference
+		assert!(parser.parse("T1 - 1 week").is_err());
+
+		// A three-point estimate ends after its likely duration
+		let result = parser.parse("2–3–6 weeks")?;
+		assert_eq!(result.duration().0.as_secs(), 86400 * 21);
+		let estimate = parser.parse_estimate("2–3–6 weeks")?;
+		assert_eq!(estimate.map(|estimate| estimate.pessimistic().0.as_secs()), Some(86400 * 42));
+		assert_eq!(parser.parse_estimate("1 month")?, None);
+		let estimate = parser.parse_estimate("2–3–6 workdays")?;
+		assert_eq!(estimate.as_ref().map(Estimate::is_in_workdays), Some(true));
+		assert_eq!(estimate.map(|estimate| estimate.likely_workdays()), Some(3));
+
+		// Working days end after as many calendar days until a calendar resolves them
+		let result = parser.parse("10 workdays")?;
+		assert_eq!(result.duration().0.as_secs(), 86400 * 10);
+		assert_eq!(parser.parse_workdays("T1 + 10 workdays"), Some(10));
+		assert_eq!(parser.parse_workdays("2 weeks"), None);
+
 		Ok(())
 	}
 
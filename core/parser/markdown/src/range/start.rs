@@ -1,9 +1,11 @@
 //! Start date parsing functionality for markdown roadmap documents.
 
+use super::super::duration::DurationParser;
 use super::super::error::MarkdownParseError;
+use super::super::task::parse_task_reference;
 use roadline_util::duration::Duration;
 use roadline_util::task::{
-	range::{PointOfReference, Start, TargetDate},
+	range::{Direction, PointOfReference, Start, TargetDate},
 	Id as TaskId,
 };
 use std::time::Duration as StdDuration;
@@ -11,14 +13,16 @@ use std::time::Duration as StdDuration;
 /// Represents different start date formats.
 #[derive(Debug, Clone, PartialEq)]
 pub enum StartDateFormat {
-	/// Relative start: "T1 + 1 month"
-	TaskReference(String, StdDuration),
+	/// Relative start: "T1 + 1 month" or "T1 - 1 week"
+	TaskReference(String, Direction, StdDuration),
+	/// Relative start in working days: "T1 + 5 workdays"
+	WorkdayReference(String, Direction, u32),
 }
 
 /// Parser for start date expressions in markdown documents.
 #[derive(Debug, Clone)]
 pub struct StartDate {
-	// Configuration for start date parsing
+	duration_parser: DurationParser,
 }
 
 impl Default for StartDate {
@@ -30,7 +34,7 @@ impl Default for StartDate {
 impl StartDate {
 	/// Create a new start date parser.
 	pub fn new() -> Self {
-		Self {}
+		Self { duration_parser: DurationParser::new() }
 	}
 
 	/// Parse a start date expression from a string.
@@ -38,17 +42,32 @@ impl StartDate {
 	/// Expected formats:
 	/// - "T0 + 0 months" (relative to another task)
 	/// - "T1 + 1 month" (relative to task T1)
+	/// - "T3 - 1 week" (before the start of task T3)
+	/// - "M2 + 1 week" (relative to milestone M2)
+	///
+	/// The duration accepts the full grammar of [`DurationParser`], or a number of working days
+	/// such as "T1 + 5 workdays", which a working calendar resolves.
 	pub fn parse(&self, expression: &str) -> Result<Start, MarkdownParseError> {
 		let format = self.parse_format(expression)?;
 
 		match format {
-			StartDateFormat::TaskReference(task_ref, duration) => {
+			StartDateFormat::TaskReference(task_ref, direction, duration) => {
 				let ref_task_id = self.parse_task_id(&task_ref)?;
 				Ok(Start::from(TargetDate {
 					point_of_reference: PointOfReference::from(ref_task_id),
 					duration: Duration::from(duration),
+					direction,
+					workdays: None,
 				}))
 			}
+			StartDateFormat::WorkdayReference(task_ref, direction, workdays) => {
+				let ref_task_id = self.parse_task_id(&task_ref)?;
+				let target_date = TargetDate {
+					direction,
+					..TargetDate::new(PointOfReference::from(ref_task_id), StdDuration::ZERO.into())
+				};
+				Ok(Start::from(target_date.with_workdays(workdays)))
+			}
 		}
 	}
 
@@ -56,15 +75,23 @@ impl StartDate {
 	fn parse_format(&self, expression: &str) -> Result<StartDateFormat, MarkdownParseError> {
 		let expression = expression.trim();
 
-		if expression.starts_with('T') && expression.contains(" + ") {
-			// Relative format: "T1 + 1 month"
-			let parts: Vec<&str> = expression.split(" + ").collect();
-			if parts.len() == 2 {
-				let task_ref = parts[0].trim().to_string();
-				let duration_str = parts[1].trim();
-				let duration = self.parse_duration(duration_str)?;
-				return Ok(StartDateFormat::TaskReference(task_ref, duration));
+		if let Some(relative) = self.duration_parser.split_relative(expression) {
+			// Relative format in working days: "T1 + 5 workdays"
+			if let Some(workdays) = self.duration_parser.parse_workdays(relative.duration) {
+				return Ok(StartDateFormat::WorkdayReference(
+					relative.reference.to_string(),
+					relative.direction,
+					workdays,
+				));
 			}
+
+			// Relative format: "T1 + 1 month" or "T1 - 1 week"
+			let duration = self.parse_duration(relative.duration)?;
+			return Ok(StartDateFormat::TaskReference(
+				relative.reference.to_string(),
+				relative.direction,
+				duration,
+			));
 		}
 
 		Err(MarkdownParseError::InvalidDateExpression { expression: expression.to_string() })
@@ -72,60 +99,14 @@ impl StartDate {
 
 	/// Parse a task ID from a string.
 	fn parse_task_id(&self, task_str: &str) -> Result<TaskId, MarkdownParseError> {
-		if task_str == "T0" {
-			Ok(TaskId::new(0))
-		} else if task_str.starts_with('T') {
-			let number_str = &task_str[1..];
-			let number: u8 = number_str.parse().map_err(|_| {
-				MarkdownParseError::InvalidDateExpression { expression: task_str.to_string() }
-			})?;
-			Ok(TaskId::new(number))
-		} else {
-			Err(MarkdownParseError::InvalidDateExpression { expression: task_str.to_string() })
-		}
+		parse_task_reference(task_str).map(|(_, id)| id).ok_or_else(|| {
+			MarkdownParseError::InvalidDateExpression { expression: task_str.to_string() }
+		})
 	}
 
 	/// Parse a duration expression into a standard duration.
 	fn parse_duration(&self, expression: &str) -> Result<StdDuration, MarkdownParseError> {
-		let expression = expression.trim().to_lowercase();
-
-		if expression.ends_with("month") || expression.ends_with("months") {
-			let number = self.extract_number(&expression)?;
-			Ok(StdDuration::from_secs(86400 * 30 * number as u64))
-		} else if expression.ends_with("week") || expression.ends_with("weeks") {
-			let number = self.extract_number(&expression)?;
-			Ok(StdDuration::from_secs(86400 * 7 * number as u64))
-		} else if expression.ends_with("day") || expression.ends_with("days") {
-			let number = self.extract_number(&expression)?;
-			Ok(StdDuration::from_secs(86400 * number as u64))
-		} else {
-			Err(MarkdownParseError::InvalidDurationExpression {
-				expression: expression.to_string(),
-			})
-		}
-	}
-
-	/// Extract a number from the beginning of a string.
-	fn extract_number(&self, s: &str) -> Result<u32, MarkdownParseError> {
-		let mut number_str = String::new();
-
-		for ch in s.chars() {
-			if ch.is_ascii_digit() {
-				number_str.push(ch);
-			} else {
-				break;
-			}
-		}
-
-		if number_str.is_empty() {
-			return Err(MarkdownParseError::InvalidDurationExpression {
-				expression: s.to_string(),
-			});
-		}
-
-		number_str.parse().map_err(|_| MarkdownParseError::InvalidDurationExpression {
-			expression: s.to_string(),
-		})
+		self.duration_parser.parse(expression)
 	}
 }
 
@@ -145,6 +126,29 @@ mod tests {
 		let result = parser.parse("T0 + 0 months")?;
 		assert!(result.point_of_reference().0.value() == 0);
 
+		// Test negative offsets and compound durations
+		let result = parser.parse("T3 - 1 week")?;
+		assert!(result.point_of_reference().0.value() == 3);
+		assert_eq!(result.direction(), Direction::Before);
+		assert_eq!(result.duration().0.as_secs(), 86400 * 7);
+
+		let result = parser.parse("T2 + 1 month 2 weeks")?;
+		assert_eq!(result.direction(), Direction::After);
+		assert_eq!(result.duration().0.as_secs(), 86400 * 44);
+
+		// Test starts relative to a milestone
+		let result = parser.parse("M2 + 1 week")?;
+		assert!(result.point_of_reference().0.value() == 2);
+
+		// Test offsets in working days
+		let result = parser.parse("T1 + 5 workdays")?;
+		assert_eq!(result.workdays(), Some(5));
+		assert_eq!(result.duration().0.as_secs(), 86400 * 5);
+		let result = parser.parse("T1 - 2 business days")?;
+		assert_eq!(result.direction(), Direction::Before);
+		assert_eq!(result.workdays(), Some(2));
+		assert_eq!(parser.parse("T1 + 1 week")?.workdays(), None);
+
 		Ok(())
 	}
 
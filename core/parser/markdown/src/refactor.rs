@@ -0,0 +1,530 @@
+//! Refactorings of task ids in roadmap markdown documents.
+//!
+//! Renaming a task touches its header, its subtask ids, the anchors derived from those
+//! headers, and every reference to it from other tasks. [`TaskRenamer`] rewrites all of
+//! these in the markdown source and reports the references it could not rewrite safely.
+
+use super::error::MarkdownParseError;
+use super::fragment::heading_fragments;
+use super::outline::{self, Section};
+use roadline_util::task::Id as TaskId;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Renames and renumbers task ids across a roadmap document.
+///
+/// # Example
+///
+/// ```
+/// use roadline_parser_markdown::TaskRenamer;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let markdown_content = r#"
+/// #### T1: Push Towards Validation
+/// - **Starts:** T1 + 0 months
+/// - **Depends-on:** $\emptyset$
+/// - **Ends:** 1 month
+///
+/// #### T2: Validation
+/// - **Starts:** T1 + 1 month
+/// - **Depends-on:** [T1](#t1-push-towards-validation)
+/// - **Ends:** 1 month
+/// "#;
+///
+/// // Make room for a new task after T1.
+/// let report = TaskRenamer::new().shift_from(2, 1).apply(markdown_content)?;
+/// assert!(report.content.contains("### T3: Validation"));
+/// assert!(report.unrewritten.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TaskRenamer {
+	renames: BTreeMap<u8, u8>,
+	shifts: Vec<(u8, u8)>,
+}
+
+/// The result of applying a [`TaskRenamer`] to a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameReport {
+	/// The rewritten document.
+	pub content: String,
+	/// The tasks whose ids changed, keyed by their old id.
+	pub renamed: BTreeMap<TaskId, TaskId>,
+	/// References to renamed tasks which were left untouched.
+	pub unrewritten: Vec<UnrewrittenReference>,
+}
+
+/// A reference to a renamed task which could not be rewritten.
+///
+/// These are mentions of task ids in prose or in titles, where rewriting could change
+/// the meaning of the text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnrewrittenReference {
+	/// The one-based line number of the reference.
+	pub line: usize,
+	/// The reference as it appears in the document, e.g. `T4` or `T4.2`.
+	pub reference: String,
+	/// What the reference would have been rewritten to.
+	pub suggestion: String,
+}
+
+impl TaskRenamer {
+	/// Create a renamer which does not change any ids.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Rename the task `from` to `to`.
+	///
+	/// Explicit renames take precedence over shifts.
+	pub fn rename(mut self, from: u8, to: u8) -> Self {
+		self.renames.insert(from, to);
+		self
+	}
+
+	/// Renumber every task with an id of at least `from` by adding `offset`.
+	///
+	/// This is the refactoring needed to insert a task before `from`.
+	pub fn shift_from(mut self, from: u8, offset: u8) -> Self {
+		self.shifts.push((from, offset));
+		self
+	}
+
+	/// Apply the renames to a document.
+	///
+	/// Fails if an explicitly renamed task does not exist, if an id would overflow,
+	/// or if two tasks would end up with the same id.
+	pub fn apply(&self, content: &str) -> Result<RenameReport, MarkdownParseError> {
+		let lines: Vec<&str> = content.lines().collect();
+		let ids = Self::task_ids(&lines)?;
+		let mapping = self.resolve(&ids)?;
+
+		let fragments = Self::fragment_renames(&lines, &mapping);
+		let mut unrewritten = Vec::new();
+		let mut out = Vec::with_capacity(lines.len());
+		let mut section = Section::Other;
+
+		for (index, line) in lines.iter().enumerate() {
+			let trimmed = line.trim_start();
+			let line_number = index + 1;
+
+			if trimmed.starts_with('#') {
+				section = Section::for_heading(trimmed.trim_end()).unwrap_or(section);
+				out.push(Self::rewrite_heading(line, &mapping, line_number, &mut unrewritten));
+				continue;
+			}
+
+			let structural = section != Section::Other && trimmed.starts_with("- **");
+			out.push(Self::rewrite_line(
+				line,
+				structural,
+				&mapping,
+				&fragments,
+				line_number,
+				&mut unrewritten,
+			));
+		}
+
+		let mut rewritten = out.join("\n");
+		if content.ends_with('\n') {
+			rewritten.push('\n');
+		}
+
+		let renamed = mapping
+			.iter()
+			.map(|(from, to)| (TaskId::new(*from), TaskId::new(*to)))
+			.collect();
+
+		Ok(RenameReport { content: rewritten, renamed, unrewritten })
+	}
+
+	/// Collect the ids of all task headers in the document.
+	fn task_ids(lines: &[&str]) -> Result<BTreeSet<u8>, MarkdownParseError> {
+		let mut ids = BTreeSet::new();
+
+		for line in lines {
+			if let Some((kind, id, _)) = outline::task_header(line.trim()) {
+				if !ids.insert(id.value()) {
+					return Err(MarkdownParseError::TaskIdConflict {
+						id: format!("{}{}", kind.prefix(), id.value()),
+					});
+				}
+			}
+		}
+
+		if ids.is_empty() {
+			return Err(MarkdownParseError::NoTasksFound);
+		}
+
+		Ok(ids)
+	}
+
+	/// Resolve the renames and shifts against the ids in the document.
+	///
+	/// Only ids which actually change are included in the result.
+	fn resolve(&self, ids: &BTreeSet<u8>) -> Result<BTreeMap<u8, u8>, MarkdownParseError> {
+		if let Some(from) = self.renames.keys().find(|from| !ids.contains(from)) {
+			return Err(MarkdownParseError::UnknownTaskId { id: format!("T{from}") });
+		}
+
+		let mut resolved = BTreeMap::new();
+		let mut targets = BTreeSet::new();
+
+		for id in ids {
+			let mut target = *id;
+			if let Some(renamed) = self.renames.get(id) {
+				target = *renamed;
+			} else {
+				for (from, offset) in &self.shifts {
+					if target >= *from {
+						target = target.checked_add(*offset).ok_or_else(|| {
+							MarkdownParseError::OffsetOverflow {
+								task: format!("T{id}"),
+								offset: *offset,
+							}
+						})?;
+					}
+				}
+			}
+
+			if !targets.insert(target) {
+				return Err(MarkdownParseError::TaskIdConflict { id: format!("T{target}") });
+			}
+			if target != *id {
+				resolved.insert(*id, target);
+			}
+		}
+
+		Ok(resolved)
+	}
+
+	/// Map the fragments of headings to their fragments after the renames.
+	///
+	/// Also maps the link text `T4` or `T4.1` of a renamed header to its new fragment, for
+	/// links whose anchors were already stale.
+	fn fragment_renames(lines: &[&str], mapping: &BTreeMap<u8, u8>) -> HashMap<String, String> {
+		let mut references = HashMap::new();
+		let renamed: Vec<String> = lines
+			.iter()
+			.copied()
+			.enumerate()
+			.map(|(index, line)| {
+				let heading = line.trim();
+				match Self::header(heading) {
+					Some((id, rest)) if mapping.contains_key(&id) => {
+						let prefix = &heading[..heading.len() - rest.len()];
+						let reference = prefix.trim_start_matches('#').trim().to_string();
+						references.insert(index + 1, reference);
+						format!("{}{}", Self::rewrite_tokens(prefix, mapping), rest)
+					}
+					_ => line.to_string(),
+				}
+			})
+			.collect();
+
+		// Fragments are unique across the document, so they are compared heading by heading
+		let new: HashMap<usize, String> =
+			heading_fragments(&renamed.join("\n")).into_iter().collect();
+		let mut fragments = HashMap::new();
+
+		for (line, old) in heading_fragments(&lines.join("\n")) {
+			let Some(new) = new.get(&line) else {
+				continue;
+			};
+			if let Some(reference) = references.remove(&line) {
+				fragments.insert(reference, new.clone());
+			}
+			if *new != old {
+				fragments.insert(old, new.clone());
+			}
+		}
+
+		fragments
+	}
+
+	/// Rewrite the id of a task or subtask header, reporting ids mentioned in its title.
+	fn rewrite_heading(
+		line: &str,
+		mapping: &BTreeMap<u8, u8>,
+		line_number: usize,
+		unrewritten: &mut Vec<UnrewrittenReference>,
+	) -> String {
+		let trimmed = line.trim();
+		let Some((_, rest)) = Self::header(trimmed) else {
+			Self::report_tokens(line, mapping, line_number, unrewritten);
+			return line.to_string();
+		};
+
+		let split = line.len() - line.trim_start().len() + trimmed.len() - rest.len();
+		let (prefix, title) = line.split_at(split);
+		Self::report_tokens(title, mapping, line_number, unrewritten);
+
+		format!("{}{}", Self::rewrite_tokens(prefix, mapping), title)
+	}
+
+	/// Rewrite the links and, for structural lines, the bare ids in a line.
+	///
+	/// Field lines and contents lists are structural; ids in any other text are only
+	/// reported.
+	fn rewrite_line(
+		line: &str,
+		structural: bool,
+		mapping: &BTreeMap<u8, u8>,
+		fragments: &HashMap<String, String>,
+		line_number: usize,
+		unrewritten: &mut Vec<UnrewrittenReference>,
+	) -> String {
+		let mut out = String::with_capacity(line.len());
+		let mut rest = line;
+
+		let mut plain = |text: &str, out: &mut String| {
+			if structural {
+				out.push_str(&Self::rewrite_tokens(text, mapping));
+			} else {
+				Self::report_tokens(text, mapping, line_number, unrewritten);
+				out.push_str(text);
+			}
+		};
+
+		while let Some(open) = rest.find('[') {
+			let after_open = &rest[open + 1..];
+			let Some(close) = after_open.find("](#") else {
+				break;
+			};
+			let target_start = close + 3;
+			let Some(target_len) = after_open[target_start..].find(')') else {
+				break;
+			};
+
+			let text = &after_open[..close];
+			let target = &after_open[target_start..target_start + target_len];
+			let new_target = fragments.get(target).or_else(|| {
+				Self::rewrite_tokens(text, mapping)
+					.ne(text)
+					.then(|| fragments.get(text))
+					.flatten()
+			});
+
+			plain(&rest[..open], &mut out);
+			match new_target {
+				Some(new_target) => {
+					out.push_str(&format!(
+						"[{}](#{})",
+						Self::rewrite_tokens(text, mapping),
+						new_target
+					));
+				}
+				None => {
+					out.push('[');
+					plain(text, &mut out);
+					out.push_str(&format!("](#{target})"));
+				}
+			}
+			rest = &after_open[target_start + target_len + 1..];
+		}
+
+		plain(rest, &mut out);
+		out
+	}
+
+	/// Report the renamed ids in a piece of text which is left untouched.
+	fn report_tokens(
+		text: &str,
+		mapping: &BTreeMap<u8, u8>,
+		line_number: usize,
+		unrewritten: &mut Vec<UnrewrittenReference>,
+	) {
+		for (start, end, id) in Self::tokens(text) {
+			if mapping.contains_key(&id) {
+				let reference = &text[start..end];
+				unrewritten.push(UnrewrittenReference {
+					line: line_number,
+					reference: reference.to_string(),
+					suggestion: Self::rewrite_tokens(reference, mapping),
+				});
+			}
+		}
+	}
+
+	/// Rewrite every renamed id in a piece of text.
+	fn rewrite_tokens(text: &str, mapping: &BTreeMap<u8, u8>) -> String {
+		let mut out = String::with_capacity(text.len());
+		let mut last = 0;
+
+		for (start, _, id) in Self::tokens(text) {
+			if let Some(new_id) = mapping.get(&id) {
+				out.push_str(&text[last..start]);
+				out.push_str(&format!("{}{new_id}", &text[start..start + 1]));
+				last = start + 1 + text[start + 1..].bytes().take_while(u8::is_ascii_digit).count();
+			}
+		}
+
+		out.push_str(&text[last..]);
+		out
+	}
+
+	/// Find task id tokens like `T4`, `M4` or `T4.2` in a piece of text.
+	///
+	/// Returns the byte range of each token and the task id it refers to.
+	fn tokens(text: &str) -> Vec<(usize, usize, u8)> {
+		let bytes = text.as_bytes();
+		let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+		let digits_from =
+			|start: usize| bytes[start..].iter().take_while(|b| b.is_ascii_digit()).count() + start;
+		let mut tokens = Vec::new();
+		let mut i = 0;
+
+		while i < bytes.len() {
+			let starts_token = matches!(bytes[i], b'T' | b'M')
+				&& (i == 0 || !is_word(bytes[i - 1]))
+				&& bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+			if !starts_token {
+				i += 1;
+				continue;
+			}
+
+			let id_end = digits_from(i + 1);
+			let mut end = id_end;
+			if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+				end = digits_from(end + 1);
+			}
+
+			if !bytes.get(end).is_some_and(|b| is_word(*b)) {
+				if let Ok(id) = text[i + 1..id_end].parse::<u8>() {
+					tokens.push((i, end, id));
+				}
+			}
+			i = end;
+		}
+
+		tokens
+	}
+
+	/// Parse a task or subtask header like `### T4: Title` or `#### T4.1: Title`, returning the
+	/// task id and the title part.
+	fn header(line: &str) -> Option<(u8, &str)> {
+		outline::task_header(line)
+			.map(|(_, id, rest)| (id.value(), rest))
+			.or_else(|| outline::subtask_header(line).map(|(id, _, rest)| (id.value(), rest)))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::RoadlineParser;
+
+	const DOCUMENT: &str = r#"# OROAD-X: Example
+
+## Roadmap
+- **Contents:**
+    - **[T1](#t1-first):** First
+    - **[T2](#t2-second):** Second
+
+### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\emptyset$
+- **Ends:** 1 month
+
+### T2: Second
+Builds on T1 but is not required by T3.
+
+- **Starts:** T1 + 1 month
+- **Depends-on:** [T1](#t1-first)
+- **Ends:** T1 + 2 months
+- **Contents:**
+    - **[T2.1](#t21-draft)**: Draft
+    - **T2.2**: Review
+
+#### T2.1: Draft
+See [the first task](#t1-first).
+
+### T3: Third
+- **Starts:** T2 + 1 month
+- **Depends-on:** [T2](#t2-stale-anchor), [T1](#t1-first)
+- **Ends:** 1 month
+"#;
+
+	#[test]
+	fn test_shift_rewrites_headers_and_references() -> Result<(), MarkdownParseError> {
+		let report = TaskRenamer::new().shift_from(2, 1).apply(DOCUMENT)?;
+
+		assert!(report.content.contains("- **[T3](#t3-second):** Second"));
+		assert!(report.content.contains("### T3: Second"));
+		assert!(report.content.contains("### T4: Third"));
+		assert!(report.content.contains("- **Starts:** T3 + 1 month"));
+		assert!(report.content.contains("- **Depends-on:** [T3](#t3-second), [T1](#t1-first)"));
+		assert!(report.content.contains("    - **[T3.1](#t31-draft)**: Draft"));
+		assert!(report.content.contains("    - **T3.2**: Review"));
+		assert!(report.content.contains("#### T3.1: Draft"));
+		assert!(report.content.contains("- **Starts:** T1 + 1 month"));
+
+		assert_eq!(report.renamed.get(&TaskId::new(2)), Some(&TaskId::new(3)));
+		assert_eq!(report.renamed.get(&TaskId::new(3)), Some(&TaskId::new(4)));
+		assert!(!report.renamed.contains_key(&TaskId::new(1)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_reports_prose_references() -> Result<(), MarkdownParseError> {
+		let report = TaskRenamer::new().shift_from(2, 1).apply(DOCUMENT)?;
+
+		assert!(report.content.contains("Builds on T1 but is not required by T3."));
+		assert_eq!(
+			report.unrewritten,
+			vec![UnrewrittenReference {
+				line: 14,
+				reference: "T3".to_string(),
+				suggestion: "T4".to_string(),
+			}]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rename_rewrites_anchors_with_custom_text() -> Result<(), MarkdownParseError> {
+		let report = TaskRenamer::new().rename(1, 9).apply(DOCUMENT)?;
+
+		assert!(report.content.contains("See [the first task](#t9-first)."));
+		assert!(report.content.contains("- **Starts:** T9 + 0 months"));
+		assert!(report.content.contains("- **Ends:** T9 + 2 months"));
+
+		let tasks = RoadlineParser::new().parse_tasks(&report.content)?;
+		let ids: Vec<u8> = tasks.iter().map(|task| task.id().value()).collect();
+		assert_eq!(ids, vec![9, 2, 3]);
+		assert!(tasks[2].depends_on().contains(&TaskId::new(9)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_shift_renames_milestones() -> Result<(), MarkdownParseError> {
+		let content = "### T1: First\n- **Starts:** T1 + 0 months\n- **Ends:** 1 month\n\n### M2: Launch\n- **Starts:** T1 + 1 month\n- **Depends-on:** [T1](#t1-first)\n\n### T3: Follow-up\n- **Starts:** M2 + 1 week\n- **Depends-on:** [M2](#m2-launch)\n- **Ends:** 1 week\n";
+		let report = TaskRenamer::new().shift_from(2, 1).apply(content)?;
+
+		assert!(report.content.contains("### M3: Launch\n"));
+		assert!(report
+			.content
+			.contains("- **Starts:** M3 + 1 week\n- **Depends-on:** [M3](#m3-launch)\n"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_rejects_conflicts_and_unknown_tasks() {
+		assert!(matches!(
+			TaskRenamer::new().rename(1, 2).apply(DOCUMENT),
+			Err(MarkdownParseError::TaskIdConflict { .. })
+		));
+		assert!(matches!(
+			TaskRenamer::new().rename(7, 8).apply(DOCUMENT),
+			Err(MarkdownParseError::UnknownTaskId { .. })
+		));
+		assert!(matches!(
+			TaskRenamer::new().shift_from(2, 255).apply(DOCUMENT),
+			Err(MarkdownParseError::OffsetOverflow { offset: 255, .. })
+		));
+	}
+}
@@ -0,0 +1,336 @@
+//! Rich text parsing functionality for markdown roadmap documents.
+//!
+//! Titles, summaries and subtask content keep their markdown as a [`RichText`] tree so
+//! that renderers can show links, code spans, emphasis, callouts and nested lists.
+
+use pulldown_cmark::{BlockQuoteKind, CodeBlockKind, Event, Options, Parser, Tag};
+use roadline_util::markdown::{Block, CalloutKind, Inline, RichText};
+
+/// Parser for the markdown of titles, summaries and subtask content.
+#[derive(Debug, Clone)]
+pub struct RichTextParser {
+	options: Options,
+}
+
+impl Default for RichTextParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl RichTextParser {
+	/// Create a new rich text parser.
+	///
+	/// GitHub flavored callouts, strikethrough and math are enabled.
+	pub fn new() -> Self {
+		Self { options: Options::ENABLE_GFM | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_MATH }
+	}
+
+	/// Parse a markdown fragment into blocks.
+	pub fn parse(&self, source: &str) -> RichText {
+		let mut stack = vec![Frame::new(FrameKind::Root)];
+
+		for event in Parser::new_ext(source, self.options) {
+			match event {
+				Event::Start(tag) => stack.push(Frame::new(FrameKind::from(tag))),
+				Event::End(_) => {
+					if stack.len() > 1 {
+						if let Some(frame) = stack.pop() {
+							Self::close(frame, &mut stack);
+						}
+					}
+				}
+				Event::Text(text) => match stack.last_mut() {
+					Some(Frame { kind: FrameKind::CodeBlock(_), code, .. }) => code.push_str(&text),
+					Some(frame) => frame.push_text(&text),
+					None => {}
+				},
+				Event::Code(code) => Self::push_inline(&mut stack, Inline::Code(code.to_string())),
+				Event::InlineMath(math) | Event::DisplayMath(math) => {
+					Self::push_inline(&mut stack, Inline::Math(math.to_string()));
+				}
+				Event::SoftBreak | Event::HardBreak => Self::push_inline(&mut stack, Inline::Break),
+				Event::Rule => {
+					if let Some(frame) = stack.last_mut() {
+						frame.push_block(Block::Rule);
+					}
+				}
+				// HTML, footnotes and task list markers carry no text worth keeping
+				_ => {}
+			}
+		}
+
+		// Every tag is closed by the end of the input, so only the root is left
+		let blocks = stack
+			.into_iter()
+			.next()
+			.map(|mut root| {
+				root.flush_inlines();
+				root.blocks
+			})
+			.unwrap_or_default();
+
+		RichText::new(blocks)
+	}
+
+	/// Parse a single line of markdown, such as a title, into inline elements.
+	///
+	/// Block structure is dropped; separate blocks are joined by a break.
+	pub fn parse_inline(&self, source: &str) -> Vec<Inline> {
+		let mut inlines = Vec::new();
+
+		for block in self.parse(source).blocks {
+			if !inlines.is_empty() {
+				inlines.push(Inline::Break);
+			}
+			Self::collect_inlines(block, &mut inlines);
+		}
+
+		inlines
+	}
+
+	/// Append the inline content of a block and its nested blocks.
+	fn collect_inlines(block: Block, inlines: &mut Vec<Inline>) {
+		match block {
+			Block::Paragraph(content) | Block::Heading { content, .. } => inlines.extend(content),
+			Block::List { items, .. } => {
+				for block in items.into_iter().flatten() {
+					Self::collect_inlines(block, inlines);
+				}
+			}
+			Block::Quote { blocks, .. } => {
+				for block in blocks {
+					Self::collect_inlines(block, inlines);
+				}
+			}
+			Block::Code { code, .. } => inlines.push(Inline::Code(code)),
+			Block::Rule => {}
+		}
+	}
+
+	fn push_inline(stack: &mut [Frame], inline: Inline) {
+		if let Some(frame) = stack.last_mut() {
+			frame.inlines.push(inline);
+		}
+	}
+
+	/// Close a frame, adding what it built to its parent.
+	fn close(mut frame: Frame, stack: &mut [Frame]) {
+		let Some(parent) = stack.last_mut() else {
+			return;
+		};
+
+		match frame.kind {
+			FrameKind::Paragraph => parent.push_block(Block::Paragraph(frame.inlines)),
+			FrameKind::Heading(level) => {
+				parent.push_block(Block::Heading { level, content: frame.inlines });
+			}
+			FrameKind::Quote(callout) => {
+				frame.flush_inlines();
+				parent.push_block(Block::Quote { callout, blocks: frame.blocks });
+			}
+			FrameKind::List(start) => parent.push_block(Block::List { start, items: frame.items }),
+			FrameKind::Item => {
+				frame.flush_inlines();
+				parent.items.push(frame.blocks);
+			}
+			FrameKind::CodeBlock(language) => {
+				parent.push_block(Block::Code { language, code: frame.code });
+			}
+			FrameKind::Emphasis => parent.inlines.push(Inline::Emphasis(frame.inlines)),
+			FrameKind::Strong => parent.inlines.push(Inline::Strong(frame.inlines)),
+			FrameKind::Strikethrough => parent.inlines.push(Inline::Strikethrough(frame.inlines)),
+			FrameKind::Link(destination) => {
+				parent.inlines.push(Inline::Link { destination, content: frame.inlines });
+			}
+			FrameKind::Root | FrameKind::Other => {
+				parent.inlines.append(&mut frame.inlines);
+				parent.blocks.append(&mut frame.blocks);
+			}
+		}
+	}
+}
+
+/// What a [`Frame`] is building.
+#[derive(Debug)]
+enum FrameKind {
+	Root,
+	Paragraph,
+	Heading(u8),
+	Quote(Option<CalloutKind>),
+	List(Option<u64>),
+	Item,
+	CodeBlock(Option<String>),
+	Emphasis,
+	Strong,
+	Strikethrough,
+	Link(String),
+	/// Tags without a counterpart, whose content is passed on to the parent.
+	Other,
+}
+
+impl From<Tag<'_>> for FrameKind {
+	fn from(tag: Tag<'_>) -> Self {
+		match tag {
+			Tag::Paragraph => Self::Paragraph,
+			Tag::Heading { level, .. } => Self::Heading(level as u8),
+			Tag::BlockQuote(kind) => Self::Quote(kind.map(|kind| match kind {
+				BlockQuoteKind::Note => CalloutKind::Note,
+				BlockQuoteKind::Tip => CalloutKind::Tip,
+				BlockQuoteKind::Important => CalloutKind::Important,
+				BlockQuoteKind::Warning => CalloutKind::Warning,
+				BlockQuoteKind::Caution => CalloutKind::Caution,
+			})),
+			Tag::List(start) => Self::List(start),
+			Tag::Item => Self::Item,
+			Tag::CodeBlock(CodeBlockKind::Fenced(language)) if !language.is_empty() => {
+				Self::CodeBlock(Some(language.to_string()))
+			}
+			Tag::CodeBlock(_) => Self::CodeBlock(None),
+			Tag::Emphasis => Self::Emphasis,
+			Tag::Strong => Self::Strong,
+			Tag::Strikethrough => Self::Strikethrough,
+			Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+				Self::Link(dest_url.to_string())
+			}
+			_ => Self::Other,
+		}
+	}
+}
+
+/// An open element while parsing.
+#[derive(Debug)]
+struct Frame {
+	kind: FrameKind,
+	blocks: Vec<Block>,
+	inlines: Vec<Inline>,
+	items: Vec<Vec<Block>>,
+	code: String,
+}
+
+impl Frame {
+	fn new(kind: FrameKind) -> Self {
+		Self {
+			kind,
+			blocks: Vec::new(),
+			inlines: Vec::new(),
+			items: Vec::new(),
+			code: String::new(),
+		}
+	}
+
+	/// Append text, merging it with preceding text.
+	fn push_text(&mut self, text: &str) {
+		match self.inlines.last_mut() {
+			Some(Inline::Text(previous)) => previous.push_str(text),
+			_ => self.inlines.push(Inline::Text(text.to_string())),
+		}
+	}
+
+	fn push_block(&mut self, block: Block) {
+		self.flush_inlines();
+		self.blocks.push(block);
+	}
+
+	/// Turn loose inline content, as in tight list items, into a paragraph.
+	fn flush_inlines(&mut self) {
+		if !self.inlines.is_empty() {
+			let inlines = std::mem::take(&mut self.inlines);
+			self.blocks.push(Block::Paragraph(inlines));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn text(text: &str) -> Inline {
+		Inline::Text(text.to_string())
+	}
+
+	#[test]
+	fn test_parse_inline_title() {
+		let parser = RichTextParser::new();
+		let title = parser.parse_inline(
+			"Continued Validation and [`fuste`](https://github.com/ramate-io/fuste) MVP",
+		);
+
+		assert_eq!(
+			title,
+			vec![
+				text("Continued Validation and "),
+				Inline::Link {
+					destination: "https://github.com/ramate-io/fuste".to_string(),
+					content: vec![Inline::Code("fuste".to_string())],
+				},
+				text(" MVP"),
+			]
+		);
+		assert_eq!(
+			title.iter().map(Inline::plain_text).collect::<String>(),
+			"Continued Validation and fuste MVP"
+		);
+	}
+
+	#[test]
+	fn test_parse_callouts_and_nested_lists() {
+		let parser = RichTextParser::new();
+		let rich_text = parser.parse(
+			"> [!IMPORTANT]\n> **T1** focuses on *validation*.\n\n- First\n    - Nested ~~old~~\n- Second\n",
+		);
+
+		assert_eq!(
+			rich_text.blocks,
+			vec![
+				Block::Quote {
+					callout: Some(CalloutKind::Important),
+					blocks: vec![Block::Paragraph(vec![
+						Inline::Strong(vec![text("T1")]),
+						text(" focuses on "),
+						Inline::Emphasis(vec![text("validation")]),
+						text("."),
+					])],
+				},
+				Block::List {
+					start: None,
+					items: vec![
+						vec![
+							Block::Paragraph(vec![text("First")]),
+							Block::List {
+								start: None,
+								items: vec![vec![Block::Paragraph(vec![
+									text("Nested "),
+									Inline::Strikethrough(vec![text("old")]),
+								])]],
+							},
+						],
+						vec![Block::Paragraph(vec![text("Second")])],
+					],
+				},
+			]
+		);
+		assert_eq!(rich_text.plain_text(), "T1 focuses on validation.\nFirst\nNested old\nSecond");
+	}
+
+	#[test]
+	fn test_parse_code_and_math() {
+		let parser = RichTextParser::new();
+		let rich_text = parser.parse("Depends on $\\emptyset$.\n\n```rust\nfn main() {}\n```\n");
+
+		assert_eq!(
+			rich_text.blocks,
+			vec![
+				Block::Paragraph(vec![
+					text("Depends on "),
+					Inline::Math("\\emptyset".to_string()),
+					text("."),
+				]),
+				Block::Code {
+					language: Some("rust".to_string()),
+					code: "fn main() {}\n".to_string()
+				},
+			]
+		);
+	}
+}
@@ -1,10 +1,19 @@
 //! Subtask parsing functionality for markdown roadmap documents.
 
 use super::error::MarkdownParseError;
+use super::rich_text::RichTextParser;
+use chrono::NaiveDate;
+use roadline_representation_core::timezone::Timezone;
 use roadline_util::task::subtask::{
-    Subtask, Id as SubtaskId, Position, Title, Content, Status, Lead
+    Subtask, Id as SubtaskId, Position, Title, Content, Status, StatusDate, Lead
 };
 
+/// The marker for a subtask which is being worked on, e.g. "Draft (in progress)".
+const IN_PROGRESS_MARKER: &str = "(in progress)";
+
+/// The marker for a completed subtask, optionally followed by a date, e.g. "Draft ✅ 2025-02-14".
+const COMPLETE_MARKER: char = '\u{2705}';
+
 /// Parser for individual subtasks in markdown documents.
 ///
 /// This parser handles the parsing of subtask entries from the Contents
@@ -14,6 +23,9 @@ pub struct SubtaskParser {
     // Configuration for subtask parsing
     default_status: Status,
     default_lead: Lead,
+    rich_text_parser: RichTextParser,
+    /// The timezone completion dates are days of.
+    timezone: Timezone,
 }
 
 impl Default for SubtaskParser {
@@ -27,39 +39,126 @@ impl SubtaskParser {
     pub fn new() -> Self {
         Self {
             default_status: Status::Incomplete,
-            default_lead: Lead::new("Unknown".to_string(), "unknown@example.com".to_string()),
+            default_lead: Lead::unknown(),
+            rich_text_parser: RichTextParser::new(),
+            timezone: Timezone::utc(),
         }
     }
 
+    /// Sets the timezone completion dates are days of, so that they start at local midnight.
+    ///
+    /// Defaults to UTC.
+    pub fn with_timezone(self, timezone: Timezone) -> Self {
+        Self { timezone, ..self }
+    }
+
     /// Parse a subtask line from the Contents section.
     ///
     /// Expected format: "- **[T1.1](#t11-title)**: Description"
+    ///
+    /// The line may be a GitHub task-list item such as "- [x] **[T1.1](#t11-title)**: Description",
+    /// and the description may carry a status marker, see [`SubtaskParser::parse_status`].
     pub fn parse_subtask_line(&self, line: &str) -> Result<Option<Subtask>, MarkdownParseError> {
-        let line = line.trim();
+        let (checked, line) = match self.split_checkbox(line.trim()) {
+            Some((checked, rest)) => (Some(checked), format!("- {rest}")),
+            None => (None, line.trim().to_string()),
+        };
         
-        if !line.starts_with("- **") || !line.contains(":**") {
+        if !line.starts_with("- **") || !(line.contains(":**") || line.contains("**:")) {
             return Ok(None);
         }
 
         // Extract the subtask ID and title from the markdown link
-        let (subtask_id, title) = self.parse_subtask_header(line)?;
+        let (subtask_id, title) = self.parse_subtask_header(&line)?;
         
-        // Extract the description
-        let description = self.extract_description(line)?;
+        // Extract the description and its status
+        let description = self.extract_description(&line)?;
+        let (status, description) = self.parse_status(&description, checked)?;
         
         // Create the subtask
         let subtask = Subtask::new(
             subtask_id.clone(),
             Position::new(0), // Use 0 as default position for now
             title,
-            Content { text: description },
-            self.default_status.clone(),
+            Content::new(description.clone()).with_markdown(self.rich_text_parser.parse(&description)),
+            status,
             self.default_lead.clone(),
         );
 
         Ok(Some(subtask))
     }
 
+    /// Split a GitHub task-list checkbox off a list item.
+    ///
+    /// Returns whether the box is checked and the rest of the item,
+    /// e.g. `(true, "**[T1.1](#t11-title)**: Description")` for "- [x] **[T1.1](#t11-title)**: Description".
+    pub fn split_checkbox<'a>(&self, line: &'a str) -> Option<(bool, &'a str)> {
+        let rest = line.trim_start().strip_prefix("- [")?;
+        let checked = match rest.chars().next()? {
+            'x' | 'X' => true,
+            ' ' => false,
+            _ => return None,
+        };
+        let rest = rest[1..].strip_prefix(']')?;
+
+        (rest.is_empty() || rest.starts_with(' ')).then(|| (checked, rest.trim_start()))
+    }
+
+    /// Parse the status markers of a subtask title or description.
+    ///
+    /// A completion marker like "✅ 2025-02-14" yields [`Status::Complete`] on that date, and
+    /// "(in progress)" yields [`Status::InProgress`]. `checked` is the state of the task-list
+    /// checkbox of the entry, if it has one; a checked entry without a completion date is
+    /// complete on an unknown date.
+    ///
+    /// Returns the status and the text with the markers removed.
+    pub fn parse_status(
+        &self,
+        text: &str,
+        checked: Option<bool>,
+    ) -> Result<(Status, String), MarkdownParseError> {
+        let mut text = text.to_string();
+        let mut completed = None;
+        let mut in_progress = false;
+
+        if let Some(position) = text.find(COMPLETE_MARKER) {
+            let after = &text[position + COMPLETE_MARKER.len_utf8()..];
+            let token = after.split_whitespace().next().unwrap_or_default();
+
+            let (date, consumed) = match NaiveDate::parse_from_str(token, "%Y-%m-%d") {
+                Ok(date) => (Some(date), after.find(token).map_or(0, |start| start + token.len())),
+                Err(_) if token.starts_with(|c: char| c.is_ascii_digit()) => {
+                    return Err(MarkdownParseError::InvalidDateExpression {
+                        expression: token.to_string(),
+                    });
+                }
+                Err(_) => (None, 0),
+            };
+
+            completed = Some(date);
+            text.replace_range(position..position + COMPLETE_MARKER.len_utf8() + consumed, "");
+        }
+
+        if let Some(position) = text.to_ascii_lowercase().find(IN_PROGRESS_MARKER) {
+            in_progress = true;
+            text.replace_range(position..position + IN_PROGRESS_MARKER.len(), "");
+        }
+
+        let status = match (completed, checked) {
+            (Some(Some(date)), _) => Status::Complete(self.status_date(date)),
+            (Some(None), _) | (None, Some(true)) => Status::Complete(StatusDate::undated()),
+            _ if in_progress => Status::InProgress,
+            _ => self.default_status.clone(),
+        };
+
+        Ok((status, text.split_whitespace().collect::<Vec<_>>().join(" ")))
+    }
+
+    /// Midnight on a completion date, in the timezone of the parser.
+    fn status_date(&self, date: NaiveDate) -> StatusDate {
+        StatusDate::on(self.timezone.midnight(date).inner())
+    }
+
     /// Parse the subtask header to extract ID and title.
     ///
     /// Expected format: "**[T1.1](#t11-title)**:"
@@ -90,34 +189,167 @@ impl SubtaskParser {
 
     /// Parse a subtask ID string into a SubtaskId.
     ///
-    /// Expected format: "T1.1", "T1.2", etc.
-    fn parse_subtask_id(&self, id_str: &str) -> Result<SubtaskId, MarkdownParseError> {
-        // For now, create a simple ID based on the string
-        // In a real implementation, this would parse the actual ID structure
-        let hash = self.hash_string(id_str);
-        Ok(SubtaskId::new(hash))
+    /// Expected format: "T1.1", "T1.2", etc. The id is the number after the dot, so that
+    /// references such as `[T1.3](#t13-title)` in other tasks resolve to the same subtask.
+    pub fn parse_subtask_id(&self, id_str: &str) -> Result<SubtaskId, MarkdownParseError> {
+        id_str
+            .trim()
+            .strip_prefix('T')
+            .and_then(|rest| rest.split_once('.'))
+            .and_then(|(_, number)| number.parse::<u8>().ok())
+            .map(SubtaskId::new)
+            .ok_or_else(|| MarkdownParseError::InvalidSubtaskId { id: id_str.to_string() })
     }
 
     /// Extract the description from a subtask line.
     ///
-    /// This is everything after the ":**" part.
+    /// This is everything after the ":**" or "**:" part.
     fn extract_description(&self, line: &str) -> Result<String, MarkdownParseError> {
         let colon_pos = line.find(":**")
+            .or_else(|| line.find("**:"))
             .ok_or_else(|| MarkdownParseError::InvalidSubtaskTitle {
                 title: line.to_string(),
             })?;
 
-        let description = line[colon_pos + 4..].trim().to_string();
+        let description = line[colon_pos + 3..].trim().to_string();
         Ok(description)
     }
+}
 
-    /// Simple hash function for string to u8 conversion.
-    fn hash_string(&self, s: &str) -> u8 {
-        let mut hash = 0u8;
-        for byte in s.bytes() {
-            hash = hash.wrapping_add(byte);
-        }
-        hash
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RoadlineParser;
+    fn completed_on(year: i32, month: u32, day: u32) -> Status {
+        let date = NaiveDate::from_ymd_opt(year, month, day).unwrap_or_default();
+        Status::Complete(SubtaskParser::new().status_date(date))
     }
-}
 
+    fn undated() -> Status {
+        Status::Complete(StatusDate::undated())
+    }
+
+    #[test]
+    fn test_split_checkbox() {
+        let parser = SubtaskParser::new();
+
+        assert_eq!(parser.split_checkbox("- [x] **[T1.1](#t11)**: Draft"), Some((true, "**[T1.1](#t11)**: Draft")));
+        assert_eq!(parser.split_checkbox("- [X] Done"), Some((true, "Done")));
+        assert_eq!(parser.split_checkbox("- [ ] Todo"), Some((false, "Todo")));
+        assert_eq!(parser.split_checkbox("- **[T1.1](#t11)**: Draft"), None);
+        assert_eq!(parser.split_checkbox("- [T1.1](#t11)"), None);
+    }
+
+    #[test]
+    fn test_parse_status_markers() -> Result<(), MarkdownParseError> {
+        let parser = SubtaskParser::new();
+
+        assert_eq!(parser.parse_status("Draft", None)?, (Status::Incomplete, "Draft".to_string()));
+        assert_eq!(parser.parse_status("Draft (in progress)", None)?, (Status::InProgress, "Draft".to_string()));
+        assert_eq!(parser.parse_status("Draft (In Progress)", Some(false))?, (Status::InProgress, "Draft".to_string()));
+        assert_eq!(parser.parse_status("Draft \u{2705} 2025-02-14", None)?, (completed_on(2025, 2, 14), "Draft".to_string()));
+        assert_eq!(parser.parse_status("Draft \u{2705}", None)?, (undated(), "Draft".to_string()));
+        assert_eq!(parser.parse_status("Draft", Some(true))?, (undated(), "Draft".to_string()));
+        assert_eq!(parser.parse_status("Draft", Some(false))?, (Status::Incomplete, "Draft".to_string()));
+
+        assert!(matches!(
+            parser.parse_status("Draft \u{2705} 2025-02-30", None),
+            Err(MarkdownParseError::InvalidDateExpression { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_subtask_statuses() -> Result<(), MarkdownParseError> {
+        let content = "### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** 1 month
+- **Contents:**
+    - [x] **[T1.1](#t11-draft)**: Draft \u{2705} 2025-02-14
+    - [ ] **[T1.2](#t12-review)**: Review (in progress)
+    - [ ] **[T1.3](#t13-publish)**: Publish
+    - [x] **[T1.4](#t14-announce)**: Announce
+
+#### T1.2: Review
+
+#### T1.3: Publish
+- [x] Proofread \u{2705} 2025-03-01
+- [ ] Upload
+
+#### T1.5: Archive
+- [x] Export \u{2705} 2025-03-01
+- [x] Store \u{2705} 2025-03-04
+";
+        let tasks = RoadlineParser::new().parse_tasks(content)?;
+        let mut statuses: Vec<(String, Status)> = tasks[0]
+            .subtasks()
+            .iter()
+            .map(|subtask| (subtask.title.text.clone(), subtask.status.clone()))
+            .collect();
+        statuses.sort();
+
+        assert_eq!(
+            statuses,
+            vec![
+                ("Archive".to_string(), completed_on(2025, 3, 4)),
+                ("Publish".to_string(), Status::InProgress),
+                ("Review".to_string(), Status::InProgress),
+                ("T1.1".to_string(), completed_on(2025, 2, 14)),
+                ("T1.4".to_string(), undated()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_subtask_dependencies_and_ends() -> Result<(), MarkdownParseError> {
+        use roadline_util::task::{SubtaskDependency, Id as TaskId};
+
+        let content = "### T1: First
+- **Starts:** T1 + 0 months
+- **Depends-on:** $\\emptyset$
+- **Ends:** 1 month
+- **Contents:**
+    - **[T1.1](#t11-draft)**: Draft
+    - **[T1.2](#t12-review)**: Review
+
+#### T1.2: Review
+- **Ends:** 2 weeks
+
+Review the draft.
+
+### T2: Second
+- **Starts:** T1 + 2 weeks
+- **Depends-on:** [T1.2](#t12-review)
+- **Ends:** 1 month
+";
+        let parser = RoadlineParser::new();
+        let tasks = parser.parse_tasks(content)?;
+
+        assert_eq!(tasks[0].range.end.duration().0.as_secs(), 86400 * 30);
+        let review = tasks[0].subtask(&SubtaskId::new(2));
+        assert_eq!(review.and_then(|subtask| subtask.end()).map(|end| end.duration().0.as_secs()), Some(86400 * 14));
+        assert_eq!(review.map(|subtask| subtask.content.text.as_str()), Some("Review the draft."));
+
+        assert!(tasks[1].depends_on().is_empty());
+        assert_eq!(
+            tasks[1].subtask_dependencies().iter().copied().collect::<Vec<_>>(),
+            vec![SubtaskDependency::new(TaskId::new(1), SubtaskId::new(2))]
+        );
+
+        // T2 starts as the review is done, halfway through T1
+        let roadline = parser.parse_and_build(content)?;
+        let start = |id: &TaskId| roadline.range_algebra().span(id).map(|span| span.start.inner().inner());
+        let (first, second) = (start(&TaskId::new(1)), start(&TaskId::new(2)));
+        assert_eq!(
+            first.zip(second).map(|(first, second)| (second - first).num_days()),
+            Some(14)
+        );
+
+        Ok(())
+    }
+}
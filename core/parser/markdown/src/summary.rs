@@ -1,14 +1,18 @@
 //! Summary parsing functionality for markdown roadmap documents.
 
+use super::rich_text::RichTextParser;
 use roadline_util::task::Summary;
 
+/// The GitHub callout markers, which start a quote that becomes an annotation.
+const CALLOUT_MARKERS: [&str; 5] = ["[!NOTE]", "[!TIP]", "[!IMPORTANT]", "[!WARNING]", "[!CAUTION]"];
+
 /// Parser for task summaries in markdown documents.
 ///
 /// This parser handles the extraction of summary text from task content
 /// before any subsections or metadata fields.
 #[derive(Debug, Clone)]
 pub struct SummaryParser {
-    // Configuration for summary parsing
+    rich_text_parser: RichTextParser,
 }
 
 impl Default for SummaryParser {
@@ -20,13 +24,16 @@ impl Default for SummaryParser {
 impl SummaryParser {
     /// Create a new summary parser.
     pub fn new() -> Self {
-        Self {}
+        Self { rich_text_parser: RichTextParser::new() }
     }
 
     /// Create a summary from the task content before any subsections.
     ///
     /// This method extracts descriptive text from the task content,
-    /// stopping at the Contents section and skipping metadata fields.
+    /// stopping at the Contents section and skipping metadata fields and callouts,
+    /// which are parsed into annotations instead.
+    /// Line breaks and indentation are kept, and the text is also parsed
+    /// into structured markdown.
     ///
     /// # Arguments
     /// * `content` - The content lines of the task section
@@ -35,9 +42,20 @@ impl SummaryParser {
     /// A `Summary` object containing the extracted text.
     pub fn parse(&self, content: &[String]) -> Summary {
         let mut summary_lines = Vec::new();
+        let mut in_callout = false;
+
+        for raw_line in content {
+            let line = raw_line.trim();
 
-        for line in content {
-            let line = line.trim();
+            // Skip callouts up to the end of their quote
+            if let Some(quoted) = line.strip_prefix('>') {
+                in_callout |= Self::is_callout_marker(quoted);
+                if in_callout {
+                    continue;
+                }
+            } else {
+                in_callout = false;
+            }
 
             // Stop at the Contents section
             if line == "- **Contents:**" {
@@ -54,13 +72,18 @@ impl SummaryParser {
                 continue;
             }
 
-            // Add content lines to summary
-            if !line.is_empty() {
-                summary_lines.push(line.to_string());
-            }
+            // Add content lines to summary, keeping blank lines between paragraphs
+            summary_lines.push(raw_line.trim_end());
         }
 
-        let summary_text = summary_lines.join(" ").trim().to_string();
-        Summary { text: summary_text }
+        let summary_text = summary_lines.join("\n").trim_end().to_string();
+        let markdown = self.rich_text_parser.parse(&summary_text);
+        Summary::new(summary_text).with_markdown(markdown)
+    }
+
+    /// Whether the quoted text of a line opens a callout, e.g. `[!NOTE]`.
+    fn is_callout_marker(quoted: &str) -> bool {
+        let quoted = quoted.trim();
+        CALLOUT_MARKERS.iter().any(|marker| quoted.eq_ignore_ascii_case(marker))
     }
 }
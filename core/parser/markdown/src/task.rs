@@ -1,11 +1,68 @@
 //! Task parsing functionality for markdown roadmap documents.
 
+use super::annotation::AnnotationParser;
 use super::error::MarkdownParseError;
-use super::range::RangeParser;
+use super::lead::LeadParser;
+use super::range::{EndDate, RangeParser};
+use super::rich_text::RichTextParser;
 use super::subtask::SubtaskParser;
 use super::summary::SummaryParser;
-use roadline_util::task::{EmbeddedSubtask, Id as TaskId, Task, Title};
-use std::collections::BTreeSet;
+use chrono::{DateTime, NaiveDate, Utc};
+use roadline_representation_core::timezone::Timezone;
+use roadline_util::task::subtask::{Lead, Status, Subtask};
+use roadline_util::task::{
+	EmbeddedSubtask, FieldValue, Id as TaskId, Kind, Label, Progress, Task, Title,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The task fields understood by the parser.
+///
+/// Any other field of a task, e.g. `Priority: P1`, is kept as a custom field of the task.
+pub const KNOWN_FIELDS: &[&str] = &[
+	"Starts",
+	"Depends-on",
+	"Ends",
+	"Lead",
+	"Leads",
+	"Type",
+	"Started",
+	"Finished",
+	"Progress",
+	"Tags",
+	"Labels",
+	"Contents",
+	"Sub-roadmaps",
+	"Sub-roadmap",
+];
+
+/// Parse a task reference such as `T3`, or `M3` for a milestone, into its kind and id.
+///
+/// Milestones share the numbering of tasks, so `M3` and `T3` name the same task.
+pub fn parse_task_reference(reference: &str) -> Option<(Kind, TaskId)> {
+	let reference = reference.trim();
+	let kind = match reference.chars().next()? {
+		'T' => Kind::Task,
+		'M' => Kind::Milestone,
+		_ => return None,
+	};
+
+	let number = &reference[1..];
+	if !number.bytes().all(|byte| byte.is_ascii_digit()) {
+		return None;
+	}
+	number.parse().ok().map(|number| (kind, TaskId::new(number)))
+}
+
+/// Whether a line is a task header such as `### T1: Title` or `### M1: Title`.
+///
+/// The prefix must be followed by a digit, so that headings such as `### Motivation: why`
+/// are not taken for tasks.
+pub fn is_task_header(line: &str) -> bool {
+	let mut reference = line.strip_prefix("### ").unwrap_or_default().chars();
+	matches!(reference.next(), Some('T' | 'M'))
+		&& reference.next().is_some_and(|c| c.is_ascii_digit())
+		&& line.contains(':')
+}
 
 /// Parser for individual tasks in markdown documents.
 ///
@@ -15,7 +72,13 @@ use std::collections::BTreeSet;
 pub struct TaskParser {
 	subtask_parser: SubtaskParser,
 	pub range_parser: RangeParser,
+	end_parser: EndDate,
 	summary_parser: SummaryParser,
+	lead_parser: LeadParser,
+	rich_text_parser: RichTextParser,
+	annotation_parser: AnnotationParser,
+	/// The timezone actual dates are days of.
+	timezone: Timezone,
 }
 
 impl Default for TaskParser {
@@ -30,34 +93,56 @@ impl TaskParser {
 		Self {
 			subtask_parser: SubtaskParser::new(),
 			range_parser: RangeParser::new(),
+			end_parser: EndDate::new(),
 			summary_parser: SummaryParser::new(),
+			lead_parser: LeadParser::new(),
+			rich_text_parser: RichTextParser::new(),
+			annotation_parser: AnnotationParser::new(),
+			timezone: Timezone::utc(),
 		}
 	}
 
+	/// Sets the timezone actual dates and subtask completion dates are days of, so that they
+	/// start at local midnight.
+	///
+	/// Defaults to UTC.
+	pub fn with_timezone(self, timezone: Timezone) -> Self {
+		Self { subtask_parser: self.subtask_parser.with_timezone(timezone), timezone, ..self }
+	}
+
 	/// Parse a complete task section from markdown.
 	///
 	/// This method parses the task header, extracts metadata fields,
 	/// and processes all subtasks.
 	pub fn parse_task_section(&self, section: &TaskSection) -> Result<Task, MarkdownParseError> {
 		// Parse the task header to get ID and title
-		let (task_id, title) = self.parse_task_header(&section.header)?;
+		let (header_kind, task_id, title) = self.parse_task_header(&section.header)?;
 
 		// Parse metadata fields
 		let metadata = self.parse_metadata(&section.content)?;
 
-		// Parse subtasks from the Contents section
-		let subtasks = self.parse_subtasks(&section.content)?;
+		// A `Type` field overrides the kind given by the header
+		let kind = metadata.kind.unwrap_or(header_kind);
 
-		// Create the task range
-		let range = self.range_parser.parse(
-			metadata.starts.as_deref(),
-			metadata.ends.as_deref(),
-			&task_id,
-		)?;
+		// Parse subtasks from the Contents section, led by the task's lead unless they name their own
+		let subtasks = self.parse_subtasks(&section.content, metadata.leads.first())?;
+
+		// Create the task range; milestones need no end, and take no time if they declare one
+		let ends = metadata.ends.as_deref().or(kind.is_milestone().then_some("0 days"));
+		let range = self.range_parser.parse(metadata.starts.as_deref(), ends, &task_id)?;
+		if kind.is_milestone() && !range.end.duration().0.is_zero() {
+			return Err(MarkdownParseError::MilestoneDuration {
+				id: format!("{}{}", kind.prefix(), task_id.value()),
+				ends: ends.unwrap_or_default().to_string(),
+			});
+		}
 
 		// Create summary from content before subsections
 		let summary = self.summary_parser.parse(&section.content);
 
+		// Collect the callouts of the task and its subsections
+		let annotations = self.annotation_parser.parse(&section.content);
+
 		Ok(Task::new(
 			task_id,
 			title,
@@ -65,16 +150,22 @@ impl TaskParser {
 			subtasks,
 			summary,
 			range,
-		))
+		)
+		.with_kind(kind)
+		.with_leads(metadata.leads)
+		.with_annotations(annotations)
+		.with_labels(metadata.labels)
+		.with_fields(metadata.fields)
+		.with_progress(metadata.progress))
 	}
 
-	/// Parse the task header to extract ID and title.
+	/// Parse the task header to extract kind, ID and title.
 	///
-	/// Expected format: "### T1: Task Title"
-	fn parse_task_header(&self, header: &str) -> Result<(TaskId, Title), MarkdownParseError> {
+	/// Expected format: "### T1: Task Title", or "### M1: Milestone Title" for a milestone.
+	fn parse_task_header(&self, header: &str) -> Result<(Kind, TaskId, Title), MarkdownParseError> {
 		let header = header.trim();
 
-		if !header.starts_with("### T") {
+		if !is_task_header(header) {
 			return Err(MarkdownParseError::InvalidTaskId { header: header.to_string() });
 		}
 
@@ -86,55 +177,117 @@ impl TaskParser {
 			.find(':')
 			.ok_or_else(|| MarkdownParseError::InvalidTaskTitle { header: header.to_string() })?;
 
-		// Extract task ID (e.g., "T1")
+		// Extract task ID (e.g., "T1" or "M1")
 		let task_id_str = content[..colon_pos].trim();
-		let task_id = self.parse_task_id(task_id_str)?;
+		let (kind, task_id) = parse_task_reference(task_id_str)
+			.ok_or_else(|| MarkdownParseError::InvalidTaskId { header: task_id_str.to_string() })?;
 
 		// Extract title (everything after the colon)
 		let title_str = content[colon_pos + 1..].trim();
-		let title = Title { text: title_str.to_string() };
-
-		Ok((task_id, title))
-	}
-
-	/// Parse a task ID string into a TaskId.
-	///
-	/// Expected format: "T1", "T2", etc.
-	fn parse_task_id(&self, id_str: &str) -> Result<TaskId, MarkdownParseError> {
-		if !id_str.starts_with('T') {
-			return Err(MarkdownParseError::InvalidTaskId { header: id_str.to_string() });
-		}
-
-		let number_str = &id_str[1..];
-		let number: u8 = number_str
-			.parse()
-			.map_err(|_| MarkdownParseError::InvalidTaskId { header: id_str.to_string() })?;
+		let title = Title::new(title_str.to_string())
+			.with_markdown(self.rich_text_parser.parse_inline(title_str));
 
-		Ok(TaskId::new(number))
+		Ok((kind, task_id, title))
 	}
 
 	/// Parse metadata fields from the task content.
 	///
-	/// This includes fields like "Starts:", "Depends-on:", "Ends:", etc.
+	/// This includes fields like "Starts:", "Depends-on:", "Ends:", "Type:", etc.
+	/// `Started:`, `Finished:` and `Progress:` record the actual progress of the task.
+	/// `Tags:` and `Labels:` become labels, and any other field of the task a custom field.
+	/// `Lead:`, `Leads:` and `Ends:` fields inside `#### T1.1:` subsections belong to the subtask.
 	fn parse_metadata(&self, content: &[String]) -> Result<TaskMetadata, MarkdownParseError> {
 		let mut metadata = TaskMetadata::default();
+		let mut in_subsection = false;
 
 		for line in content {
 			let line = line.trim();
+			in_subsection |= line.starts_with("#### ");
 
 			if let Some((field, value)) = self.parse_field_line(line) {
 				match field.as_str() {
 					"Starts" => metadata.starts = Some(value),
 					"Depends-on" => metadata.depends_on = Some(value),
-					"Ends" => metadata.ends = Some(value),
-					_ => {} // Ignore unknown fields
+					"Ends" if !in_subsection => metadata.ends = Some(value),
+					"Lead" | "Leads" if !in_subsection => {
+						metadata.leads.extend(self.lead_parser.parse_leads(&value));
+					}
+					"Type" if !in_subsection => {
+						let kind = Kind::from_name(&value).ok_or_else(|| {
+							MarkdownParseError::InvalidFieldFormat {
+								field: field.clone(),
+								value: value.clone(),
+							}
+						})?;
+						metadata.kind = Some(kind);
+					}
+					"Started" if !in_subsection => {
+						metadata.progress.started = Some(self.parse_actual_date(&value)?);
+					}
+					"Finished" if !in_subsection => {
+						metadata.progress.finished = Some(self.parse_actual_date(&value)?);
+					}
+					"Progress" if !in_subsection => {
+						let percent = value
+							.trim()
+							.trim_end_matches('%')
+							.trim()
+							.parse::<u8>()
+							.ok()
+							.filter(|percent| *percent <= 100)
+							.ok_or_else(|| {
+								MarkdownParseError::invalid_field_format(&field, &value)
+							})?;
+						metadata.progress.percent_complete = Some(percent);
+					}
+					"Tags" | "Labels" if !in_subsection => {
+						metadata.labels.extend(Self::parse_labels(&value));
+					}
+					_ if !in_subsection && !KNOWN_FIELDS.contains(&field.as_str()) => {
+						metadata.fields.insert(field, FieldValue::parse(&value));
+					}
+					_ => {} // Subtask fields are read with the subtask
 				}
 			}
 		}
 
+		// A task cannot finish before it started
+		let progress = &metadata.progress;
+		if let Some((started, finished)) = progress.started.zip(progress.finished) {
+			if finished < started {
+				return Err(MarkdownParseError::invalid_field_format(
+					"Finished",
+					format!(
+						"{} is before the start on {}",
+						finished.date_naive(),
+						started.date_naive()
+					),
+				));
+			}
+		}
+
 		Ok(metadata)
 	}
 
+	/// Parse an actual start or finish date, e.g. "2025-02-03", as midnight in the timezone of
+	/// the parser.
+	fn parse_actual_date(&self, value: &str) -> Result<DateTime<Utc>, MarkdownParseError> {
+		let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|_| {
+			MarkdownParseError::InvalidDateExpression { expression: value.to_string() }
+		})?;
+
+		Ok(self.timezone.midnight(date).inner())
+	}
+
+	/// Parse a comma-separated list of labels, e.g. "infra, `security`, #auth".
+	fn parse_labels(value: &str) -> impl Iterator<Item = Label> + '_ {
+		value
+			.split(',')
+			.map(|label| label.trim().trim_matches('`').trim_start_matches('#').trim())
+			.filter(|label| !label.is_empty())
+			.map(Label::from)
+	}
+
 	/// Parse a field line in the format "- **Field:** Value".
 	fn parse_field_line(&self, line: &str) -> Option<(String, String)> {
 		if !line.starts_with("- **") || !line.contains(":**") {
@@ -156,12 +309,15 @@ impl TaskParser {
 	}
 
 	/// Parse subtasks from the Contents section and subsections.
+	///
+	/// A `#### T1.1:` subsection takes precedence over the Contents entry of the same subtask,
+	/// except that the entry's status is kept when the subsection does not declare one.
 	fn parse_subtasks(
 		&self,
 		content: &[String],
+		task_lead: Option<&Lead>,
 	) -> Result<BTreeSet<EmbeddedSubtask>, MarkdownParseError> {
-		let mut subtasks = BTreeSet::new();
-		let mut seen_subtask_ids = std::collections::HashSet::new();
+		let mut subtasks: HashMap<String, Subtask> = HashMap::new();
 		let mut in_contents = false;
 
 		// First, parse subtasks from Contents section
@@ -174,50 +330,57 @@ impl TaskParser {
 			}
 
 			if in_contents {
-				// Check for subtask lines (both direct and indented)
-				if (line.starts_with("- **") && line.contains(":**"))
-					|| (line.starts_with("    - **") && line.contains(":**"))
+				// Task-list entries carry a checkbox in front of the bold link
+				let entry = match self.subtask_parser.split_checkbox(line) {
+					Some((_, rest)) => rest,
+					None => line.strip_prefix("- ").unwrap_or(line),
+				};
+
+				// Check for subtask lines, "**[T1.1](#t11-title):** ..." or "**[T1.1](#t11-title)**: ..."
+				if line.starts_with('-')
+					&& entry.starts_with("**")
+					&& (entry.contains(":**")
+						|| (entry.starts_with("**[") && entry.contains("**:")))
 				{
-					// Remove indentation for parsing
-					let clean_line = if line.starts_with("    - **") {
-						&line[4..] // Remove "    " prefix
-					} else {
-						line
-					};
-
-					if let Some(subtask) = self.subtask_parser.parse_subtask_line(clean_line)? {
+					if let Some(subtask) = self.subtask_parser.parse_subtask_line(line)? {
 						// Extract subtask ID for deduplication
-						let subtask_id = self.extract_subtask_id_from_line(clean_line)?;
-						if !seen_subtask_ids.contains(&subtask_id) {
-							seen_subtask_ids.insert(subtask_id);
-							subtasks.insert(EmbeddedSubtask::new(subtask));
-						}
+						let subtask_id = self.extract_subtask_id_from_line(line)?;
+						subtasks.entry(subtask_id).or_insert(subtask);
 					}
-				} else if !line.starts_with("    -") && !line.is_empty() {
-					// End of contents section when we hit a non-indented, non-empty line
+				} else if !line.is_empty() {
+					// End of contents section when we hit a non-subtask, non-empty line
 					break;
 				}
 			}
 		}
 
-		// Then, parse subsections (#### T1.1: Title format) with deduplication
+		// Then, parse subsections (#### T1.1: Title format)
 		for line in content {
 			let line = line.trim();
 
 			// Look for subsection headers like "#### T1.1: Title"
 			if line.starts_with("#### T") && line.contains(":") {
-				// Extract subtask ID for deduplication
 				let subtask_id = self.extract_subtask_id_from_subsection_header(line)?;
-				if !seen_subtask_ids.contains(&subtask_id) {
-					seen_subtask_ids.insert(subtask_id);
-					if let Some(subtask) = self.parse_subsection_subtask(line, content)? {
-						subtasks.insert(EmbeddedSubtask::new(subtask));
+				if let Some(mut subtask) = self.parse_subsection_subtask(line, content)? {
+					if let Some(entry) = subtasks.get(&subtask_id) {
+						if subtask.status == Status::Incomplete {
+							subtask.status = entry.status.clone();
+						}
 					}
+					subtasks.insert(subtask_id, subtask);
 				}
 			}
 		}
 
-		Ok(subtasks)
+		Ok(subtasks
+			.into_values()
+			.map(|mut subtask| {
+				if let Some(lead) = task_lead.filter(|_| subtask.lead.is_unknown()) {
+					subtask.lead = lead.clone();
+				}
+				EmbeddedSubtask::new(subtask)
+			})
+			.collect())
 	}
 
 	/// Extract subtask ID from a Contents line for deduplication.
@@ -263,19 +426,23 @@ impl TaskParser {
 			.ok_or_else(|| MarkdownParseError::InvalidSubtaskId { id: header.to_string() })?;
 
 		let subtask_id_str = header_content[..colon_pos].trim();
-		let title_str = header_content[colon_pos + 1..].trim();
+		let (mut status, title_str) =
+			self.subtask_parser.parse_status(&header_content[colon_pos + 1..], None)?;
 
 		// Parse the subtask ID
-		let subtask_id = self.parse_subtask_id(subtask_id_str)?;
-		let title = roadline_util::task::subtask::Title { text: title_str.to_string() };
+		let subtask_id = self.subtask_parser.parse_subtask_id(subtask_id_str)?;
+		let title = roadline_util::task::subtask::Title { text: title_str };
 
 		// Find the content for this subsection
-		let mut subsection_content = String::new();
+		let mut subsection_lines = Vec::new();
 		let mut found_header = false;
 		let mut in_subsection = false;
+		let mut checklist = Vec::new();
+		let mut leads = Vec::new();
+		let mut end = None;
 
-		for line in content {
-			let line = line.trim();
+		for raw_line in content {
+			let line = raw_line.trim();
 
 			if line == header {
 				found_header = true;
@@ -289,51 +456,71 @@ impl TaskParser {
 					break;
 				}
 
-				// Add content lines
-				if !line.is_empty() {
-					if !subsection_content.is_empty() {
-						subsection_content.push(' ');
+				// Track task-list items, which determine the status unless the header declares one
+				if let Some((checked, item)) = self.subtask_parser.split_checkbox(line) {
+					checklist.push(self.subtask_parser.parse_status(item, Some(checked))?.0);
+				}
+
+				// The leads of the subtask's own `Lead:` field and its `Ends:` offset from the
+				// start of the task, which are not part of its content
+				if let Some((field, value)) = self.parse_field_line(line) {
+					if field == "Lead" || field == "Leads" {
+						leads.extend(self.lead_parser.parse_leads(&value));
+						continue;
 					}
-					subsection_content.push_str(line);
+					if field == "Ends" {
+						end = Some(self.end_parser.parse(&value)?);
+						continue;
+					}
+				}
+
+				// Add content lines, keeping blank lines and indentation for the markdown structure
+				if !line.is_empty() || !subsection_lines.is_empty() {
+					subsection_lines.push(raw_line.as_str());
 				}
 			}
 		}
 
-		// Create the subtask
+		let subsection_content = subsection_lines.join("\n").trim_end().to_string();
+		let markdown = self.rich_text_parser.parse(&subsection_content);
+
+		if status == Status::Incomplete {
+			status = Self::checklist_status(checklist);
+		}
+
+		// Create the subtask, led by the first of its leads
+		let mut leads = leads.into_iter();
 		let subtask = roadline_util::task::subtask::Subtask::new(
 			subtask_id,
 			roadline_util::task::subtask::Position::new(0), // Default position
 			title,
-			roadline_util::task::subtask::Content { text: subsection_content },
-			roadline_util::task::subtask::Status::Incomplete, // Default status
-			roadline_util::task::subtask::Lead::new(
-				"Unknown".to_string(),
-				"unknown@example.com".to_string(),
-			), // Default lead
-		);
-
-		Ok(Some(subtask))
+			roadline_util::task::subtask::Content::new(subsection_content).with_markdown(markdown),
+			status,
+			leads.next().unwrap_or_else(Lead::unknown),
+		)
+		.with_co_leads(leads.collect());
+
+		Ok(Some(match end {
+			Some(end) => subtask.with_end(end),
+			None => subtask,
+		}))
 	}
 
-	/// Parse a subtask ID from a string like "T1.1".
-	fn parse_subtask_id(
-		&self,
-		id_str: &str,
-	) -> Result<roadline_util::task::subtask::Id, MarkdownParseError> {
-		// For now, create a simple ID based on the string
-		// In a real implementation, this would parse the actual ID structure
-		let hash = self.hash_string(id_str);
-		Ok(roadline_util::task::subtask::Id::new(hash as u8))
-	}
-
-	/// Hash a string to create a unique ID.
-	fn hash_string(&self, s: &str) -> u64 {
-		use std::collections::hash_map::DefaultHasher;
-		use std::hash::{Hash, Hasher};
-
-		let mut hasher = DefaultHasher::new();
-		s.hash(&mut hasher);
-		hasher.finish()
+	/// The status of a subsection from the statuses of its task-list items.
+	///
+	/// A fully checked list is complete as of its latest completion date, a partially
+	/// checked list is in progress, and an empty or unchecked list is incomplete.
+	fn checklist_status(checklist: Vec<Status>) -> Status {
+		let completed =
+			checklist.iter().filter(|status| matches!(status, Status::Complete(_))).count();
+
+		if completed > 0 && completed == checklist.len() {
+			checklist.into_iter().max().unwrap_or(Status::Incomplete)
+		} else if completed > 0 || checklist.contains(&Status::InProgress) {
+			Status::InProgress
+		} else {
+			Status::Incomplete
+		}
 	}
 }
 
@@ -343,6 +530,11 @@ struct TaskMetadata {
 	starts: Option<String>,
 	depends_on: Option<String>,
 	ends: Option<String>,
+	leads: Vec<Lead>,
+	kind: Option<Kind>,
+	labels: Vec<Label>,
+	fields: BTreeMap<String, FieldValue>,
+	progress: Progress,
 }
 
 /// Represents a task section in the markdown document.
@@ -361,3 +553,182 @@ impl TaskSection {
 		result.join("\n")
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use roadline_util::task::ProgressStatus;
+
+	fn section(header: &str, content: &[&str]) -> TaskSection {
+		TaskSection {
+			header: header.to_string(),
+			content: content.iter().map(ToString::to_string).collect(),
+			line_number: 1,
+		}
+	}
+
+	#[test]
+	fn test_parse_milestones() -> Result<(), MarkdownParseError> {
+		let parser = TaskParser::new();
+
+		// A milestone header needs no end
+		let task = parser.parse_task_section(&section(
+			"### M3: Launch",
+			&["- **Starts:** T2 + 1 week", "- **Depends-on:** [T2](#t2-build)"],
+		))?;
+		assert_eq!(task.id(), &TaskId::new(3));
+		assert!(task.is_milestone());
+		assert_eq!(task.range().end().duration().0.as_secs(), 0);
+
+		// A `Type` field makes a milestone of a task header
+		let task = parser.parse_task_section(&section(
+			"### T4: Review",
+			&["- **Starts:** M3 + 0 weeks", "- **Type:** Milestone", "- **Ends:** 0 weeks"],
+		))?;
+		assert_eq!(task.kind(), Kind::Milestone);
+		assert_eq!(task.range().start().point_of_reference().0.value(), 3);
+		assert_eq!(task.range().end().duration().0.as_secs(), 0);
+
+		// Milestones take no time, so a later end is rejected rather than dropped
+		assert!(matches!(
+			parser.parse_task_section(&section(
+				"### T4: Review",
+				&["- **Starts:** M3 + 0 weeks", "- **Type:** Milestone", "- **Ends:** 1 month"],
+			)),
+			Err(MarkdownParseError::MilestoneDuration { id, .. }) if id == "M4"
+		));
+
+		// A milestone shares the numbering of tasks, and other headings are not tasks
+		let parser = crate::RoadlineParser::new();
+		let document = "### T1: Build
+- **Starts:** T1 + 0 weeks
+- **Ends:** 1 week
+
+### Motivation: why
+Because.
+
+### M1: Launch
+- **Starts:** T1 + 1 week
+";
+		assert!(matches!(
+			parser.parse_tasks(document),
+			Err(MarkdownParseError::SharedTaskId { task, milestone }) if task == "T1" && milestone == "M1"
+		));
+		let tasks = parser.parse_tasks(&document.replace("M1", "M2"))?;
+		assert_eq!(tasks.len(), 2);
+		assert!(tasks[0].summary().text.contains("### Motivation: why"));
+		let parser = TaskParser::new();
+
+		// Tasks still need an end, and unknown types are rejected
+		assert!(parser
+			.parse_task_section(&section("### T5: Build", &["- **Starts:** T0 + 0 weeks"]))
+			.is_err());
+		assert!(parser
+			.parse_task_section(&section(
+				"### T5: Build",
+				&["- **Starts:** T0 + 0 weeks", "- **Ends:** 1 month", "- **Type:** epic"],
+			))
+			.is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_labels_and_custom_fields() -> Result<(), MarkdownParseError> {
+		let task = TaskParser::new().parse_task_section(&section(
+			"### T1: Harden",
+			&[
+				"- **Starts:** T0 + 0 weeks",
+				"- **Ends:** 1 month",
+				"- **Tags:** infra, `security`, #auth",
+				"- **Priority:** P1",
+				"- **Estimate:** 3",
+				"- **Reviewers:** Ada, Grace",
+				"- **Ticket:** 0042",
+				"- **Notes:** Slow, but steady progress so far",
+				"- **Contents:**",
+				"    - **[T1.1](#t11-audit)**: Audit",
+				"",
+				"#### T1.1: Audit",
+				"- **Reviewer:** Linus",
+			],
+		))?;
+
+		let labels: Vec<_> = task.labels().iter().map(Label::as_str).collect();
+		assert_eq!(labels, vec!["auth", "infra", "security"]);
+		assert!(task.has_label("security"));
+
+		assert_eq!(task.field("Priority"), Some(&FieldValue::Text("P1".to_string())));
+		assert_eq!(task.field("Estimate").and_then(FieldValue::as_integer), Some(3));
+		assert_eq!(
+			task.field("Reviewers").and_then(FieldValue::as_list),
+			Some(&["Ada".to_string(), "Grace".to_string()][..])
+		);
+
+		// Text is only typed when it clearly is a number or a list
+		assert_eq!(task.field("Ticket").and_then(FieldValue::as_text), Some("0042"));
+		assert_eq!(
+			task.field("Notes").and_then(FieldValue::as_text),
+			Some("Slow, but steady progress so far")
+		);
+
+		// Known fields and subtask fields are not custom fields
+		assert_eq!(task.fields().len(), 5);
+		assert!(task.field("Reviewer").is_none());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_progress() -> Result<(), MarkdownParseError> {
+		let parser = TaskParser::new();
+		let task = parser.parse_task_section(&section(
+			"### T1: Migrate",
+			&[
+				"- **Starts:** T0 + 0 weeks",
+				"- **Ends:** 1 month",
+				"- **Started:** 2025-02-03",
+				"- **Progress:** 40%",
+			],
+		))?;
+
+		let started =
+			NaiveDate::from_ymd_opt(2025, 2, 3).and_then(|date| date.and_hms_opt(0, 0, 0));
+		assert_eq!(task.progress().started().map(|date| date.naive_utc()), started);
+		assert_eq!(task.progress().percent_complete(), 40);
+		assert_eq!(task.progress().status(), ProgressStatus::InProgress);
+		assert!(task.fields().is_empty());
+
+		let task = parser.parse_task_section(&section(
+			"### T2: Cut over",
+			&["- **Starts:** T0 + 0 weeks", "- **Ends:** 1 week", "- **Finished:** 2025-03-01"],
+		))?;
+		assert_eq!(task.progress().status(), ProgressStatus::Complete);
+
+		for (field, value) in [("Progress", "140%"), ("Started", "soon")] {
+			let line = format!("- **{field}:** {value}");
+			let result = parser.parse_task_section(&section(
+				"### T3: Invalid",
+				&["- **Starts:** T0 + 0 weeks", "- **Ends:** 1 week", &line],
+			));
+			assert!(result.is_err(), "{line} should not parse");
+		}
+
+		// A task cannot finish before it started
+		let result = parser.parse_task_section(&section(
+			"### T4: Backwards",
+			&[
+				"- **Starts:** T0 + 0 weeks",
+				"- **Ends:** 1 week",
+				"- **Started:** 2025-03-01",
+				"- **Finished:** 2025-02-28",
+			],
+		));
+		assert!(matches!(
+			result,
+			Err(MarkdownParseError::InvalidFieldFormat { field, .. }) if field == "Finished"
+		));
+
+		Ok(())
+	}
+}
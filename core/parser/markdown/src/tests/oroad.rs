@@ -501,13 +501,16 @@ mod tests {
 		// Should parse 9 tasks (T1 through T9)
 		assert_eq!(tasks.len(), 9);
 
-		// Test T1: Push Towards Validation
+		// Test T1: Push Towards Validation, whose callouts are annotations rather than summary
 		let t1 = &tasks[0];
 		assert_eq!(t1.id().value(), 1);
 		assert_eq!(t1.title().text, "Push Towards Validation");
 		assert!(t1.depends_on().is_empty()); // Depends on $\emptyset$
 		assert_eq!(t1.subtasks().len(), 5); // T1.1 through T1.5
-		assert!(t1.summary().text.contains("**T1** focuses on readying OAC for validation"));
+		assert!(t1.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T1 focuses on readying OAC for validation")));
 
 		// Test T2: Validation and Accepting Contributions
 		let t2 = &tasks[1];
@@ -516,7 +519,10 @@ mod tests {
 		assert_eq!(t2.depends_on().len(), 1);
 		assert!(t2.depends_on().contains(&roadline_util::task::Id::new(1))); // Depends on T1
 		assert_eq!(t2.subtasks().len(), 7); // T2.1 through T2.6 + subsection
-		assert!(t2.summary().text.contains("**T2** focuses on beginning validation of OAC"));
+		assert!(t2.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T2 focuses on beginning validation of OAC")));
 
 		// Test T3: Continued Validation and Fuste MVP
 		let t3 = &tasks[2];
@@ -528,10 +534,10 @@ mod tests {
 		assert_eq!(t3.depends_on().len(), 1);
 		assert!(t3.depends_on().contains(&roadline_util::task::Id::new(2))); // Depends on T2
 		assert_eq!(t3.subtasks().len(), 4); // T3.1 through T3.4
-		assert!(t3
-			.summary()
-			.text
-			.contains("**T3** continues validation and pushes for first proper application"));
+		assert!(t3.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T3 continues validation and pushes for first proper application")));
 
 		// Test T4: Exotic Execution
 		let t4 = &tasks[3];
@@ -540,10 +546,10 @@ mod tests {
 		assert_eq!(t4.depends_on().len(), 1);
 		assert!(t4.depends_on().contains(&roadline_util::task::Id::new(3))); // Depends on T3
 		assert_eq!(t4.subtasks().len(), 3); // T4.1 through T4.3
-		assert!(t4
-			.summary()
-			.text
-			.contains("**T4** departs from the drive of previous milestones"));
+		assert!(t4.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T4 departs from the drive of previous milestones")));
 
 		// Test T5: DLT Push
 		let t5 = &tasks[4];
@@ -552,7 +558,10 @@ mod tests {
 		assert_eq!(t5.depends_on().len(), 1);
 		assert!(t5.depends_on().contains(&roadline_util::task::Id::new(4))); // Depends on T4
 		assert_eq!(t5.subtasks().len(), 6); // T5.1 through T5.6
-		assert!(t5.summary().text.contains("**T5** seeks to bring up stable implementations"));
+		assert!(t5.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T5 seeks to bring up stable implementations")));
 
 		// Test T6: Killer Apps Phase 1: Traditional L1
 		let t6 = &tasks[5];
@@ -561,10 +570,10 @@ mod tests {
 		assert_eq!(t6.depends_on().len(), 1);
 		assert!(t6.depends_on().contains(&roadline_util::task::Id::new(5))); // Depends on T5
 		assert_eq!(t6.subtasks().len(), 4); // T6.1 through T6.4
-		assert!(t6
-			.summary()
-			.text
-			.contains("**T6** emphasizes the support of the first killer app"));
+		assert!(t6.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T6 emphasizes the support of the first killer app")));
 
 		// Test T7: Killer Apps Phase 2: Content Sharing
 		let t7 = &tasks[6];
@@ -573,10 +582,10 @@ mod tests {
 		assert_eq!(t7.depends_on().len(), 1);
 		assert!(t7.depends_on().contains(&roadline_util::task::Id::new(6))); // Depends on T6
 		assert_eq!(t7.subtasks().len(), 3); // T7.1 through T7.3
-		assert!(t7
-			.summary()
-			.text
-			.contains("**T7** emphasizes the support of a content sharing application"));
+		assert!(t7.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T7 emphasizes the support of a content sharing application")));
 
 		// Test T8: Killer Apps Phase 3: Content Sharing Continued
 		let t8 = &tasks[7];
@@ -585,7 +594,10 @@ mod tests {
 		assert_eq!(t8.depends_on().len(), 1);
 		assert!(t8.depends_on().contains(&roadline_util::task::Id::new(7))); // Depends on T7
 		assert_eq!(t8.subtasks().len(), 3); // T8.1 through T8.3
-		assert!(t8.summary().text.contains("**T8** emphasizes completion of an MVP"));
+		assert!(t8.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T8 emphasizes completion of an MVP")));
 
 		// Test T9: An Interlude
 		let t9 = &tasks[8];
@@ -594,10 +606,10 @@ mod tests {
 		assert_eq!(t9.depends_on().len(), 1);
 		assert!(t9.depends_on().contains(&roadline_util::task::Id::new(8))); // Depends on T8
 		assert_eq!(t9.subtasks().len(), 5); // T9.1 through T9.5
-		assert!(t9
-			.summary()
-			.text
-			.contains("**T9** is a milestone conditional on a positive decision"));
+		assert!(t9.annotations().iter().any(|annotation| annotation
+			.body()
+			.plain_text()
+			.contains("T9 is a milestone conditional on a positive decision")));
 
 		// Test that all tasks have valid ranges
 		for (i, task) in tasks.iter().enumerate() {
@@ -621,4 +633,121 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_parse_and_build_uses_declared_start() -> Result<(), MarkdownParseError> {
+		let parser = RoadlineParser::new();
+		let content = format!("---\nstart: 2025-01-06\ntimezone: UTC\n---\n{OROAD_0}");
+
+		let document = parser.parse_document(&content)?;
+		assert_eq!(document.title.as_deref(), Some("OROAD-0: The Attempt"));
+		assert_eq!(document.tasks.len(), 9);
+
+		let roadline = parser.parse_and_build(&content)?;
+		let t1 = roadline
+			.range_algebra()
+			.span(&roadline_util::task::Id::new(1))
+			.map(|span| span.start.inner().inner());
+		assert_eq!(t1.map(|date| date.to_rfc3339()), Some("2025-01-06T00:00:00+00:00".to_string()));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_oroad_0_rich_text() -> Result<(), MarkdownParseError> {
+		use roadline_util::markdown::{Block, CalloutKind};
+
+		let tasks = RoadlineParser::new().parse_tasks(OROAD_0)?;
+
+		let t3 = &tasks[2];
+		assert_eq!(t3.title().plain_text(), "Continued Validation and fuste MVP");
+
+		// Callouts become annotations rather than part of the summary
+		let t1 = &tasks[0];
+		assert!(!t1
+			.summary()
+			.markdown
+			.blocks
+			.iter()
+			.any(|block| matches!(block, Block::Quote { callout: Some(_), .. })));
+		assert!(!t1.summary().text.contains("[!IMPORTANT]"));
+		let important = t1
+			.annotations()
+			.iter()
+			.find(|annotation| annotation.kind == CalloutKind::Important)
+			.map(|annotation| annotation.body().plain_text());
+		assert_eq!(important.as_deref(), Some("T1 focuses on readying OAC for validation."));
+
+		let t1_1 = t1
+			.subtasks()
+			.into_iter()
+			.find(|subtask| subtask.title.text.starts_with("Complete draft of **OART-1"))
+			.map(|subtask| subtask.content.plain_text());
+		assert!(t1_1.is_some_and(|content| content.starts_with(
+			"A complete draft of OART-1: BFA is essential for beginning work on gwrdfa."
+		)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_oroad_0_annotations() -> Result<(), MarkdownParseError> {
+		use roadline_util::markdown::CalloutKind;
+
+		let tasks = RoadlineParser::new().parse_tasks(OROAD_0)?;
+
+		let t1 = &tasks[0];
+		let kinds: Vec<CalloutKind> =
+			t1.annotations().iter().map(|annotation| annotation.kind).collect();
+		assert_eq!(kinds.first(), Some(&CalloutKind::Important));
+		assert!(t1.has_annotation(CalloutKind::Note));
+
+		let note = t1.annotations().iter().find(|annotation| annotation.kind == CalloutKind::Note);
+		assert_eq!(
+			note.and_then(|note| note.author()).map(|author| author.email()),
+			Some("liam@ramate.io")
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_explain_task_starting_too_early() -> Result<(), MarkdownParseError> {
+		let parser = RoadlineParser::new();
+		let content = "---
+start: 2025-01-06
+---
+# Too early
+
+### T1: Draft
+- **Starts:** T0 + 0 days
+- **Depends-on:** $\\emptyset$
+- **Ends:** T1 + 10 days
+
+### T2: Review
+- **Starts:** T1 + 5 days
+- **Depends-on:** [T1](#t1-draft)
+- **Ends:** T2 + 5 days
+";
+
+		let lines = parser.task_lines(content)?;
+		assert_eq!(lines.get(&roadline_util::task::Id::new(1)), Some(&6));
+		assert_eq!(lines.get(&roadline_util::task::Id::new(2)), Some(&11));
+
+		let error = match parser.parse_and_build(content) {
+			Ok(_) => panic!("T2 should start before T1 ends"),
+			Err(error) => error,
+		};
+		let explanations = parser.explain(content, &error);
+		assert_eq!(explanations.len(), 1);
+		assert_eq!(
+			explanations[0].to_string(),
+			"T2 \"Review\" (line 11) starts 5 days before T1 \"Draft\" (line 6) ends
+  - T2 starts on 2025-01-11
+  - T1 \"Draft\" (line 6) ends on 2025-01-16
+  help: start T2 at `T1 + 10 days` or later"
+		);
+
+		Ok(())
+	}
 }
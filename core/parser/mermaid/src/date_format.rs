@@ -0,0 +1,164 @@
+//! Mermaid `dateFormat` patterns.
+//!
+//! Mermaid writes date patterns with day.js tokens such as `YYYY-MM-DD HH:mm`. These are
+//! translated to chrono format strings, which are used both to read and to write dates.
+
+use super::error::MermaidParseError;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use std::fmt;
+
+/// The day.js tokens which are supported, longest first, with their chrono equivalents.
+const TOKENS: [(&str, &str); 14] = [
+	("YYYY", "%Y"),
+	("YY", "%y"),
+	("MMMM", "%B"),
+	("MMM", "%b"),
+	("MM", "%m"),
+	("M", "%-m"),
+	("DD", "%d"),
+	("D", "%-d"),
+	("HH", "%H"),
+	("H", "%-H"),
+	("mm", "%M"),
+	("m", "%-M"),
+	("ss", "%S"),
+	("s", "%-S"),
+];
+
+/// A Mermaid `dateFormat`, such as `YYYY-MM-DD`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DateFormat {
+	/// The pattern as written in the chart.
+	pattern: String,
+	/// The pattern as a chrono format string.
+	chrono: String,
+	/// Whether the pattern includes a time of day.
+	has_time: bool,
+}
+
+impl Default for DateFormat {
+	fn default() -> Self {
+		Self::date()
+	}
+}
+
+impl DateFormat {
+	/// `YYYY-MM-DD`, the default format of Mermaid.
+	pub fn date() -> Self {
+		Self { pattern: "YYYY-MM-DD".to_string(), chrono: "%Y-%m-%d".to_string(), has_time: false }
+	}
+
+	/// `YYYY-MM-DD HH:mm`, for charts whose tasks do not start at midnight.
+	pub fn date_time() -> Self {
+		Self {
+			pattern: "YYYY-MM-DD HH:mm".to_string(),
+			chrono: "%Y-%m-%d %H:%M".to_string(),
+			has_time: true,
+		}
+	}
+
+	/// Translate a day.js pattern such as `DD.MM.YYYY` or `YYYY-MM-DD[T]HH:mm`.
+	///
+	/// The pattern must name a year, a month and a day; text in brackets is kept literally.
+	pub fn parse(pattern: &str) -> Result<Self, MermaidParseError> {
+		let unsupported =
+			|| MermaidParseError::UnsupportedDateFormat { format: pattern.to_string() };
+		let mut chrono = String::new();
+		let mut has_time = false;
+		let mut fields = [false; 3];
+		let mut rest = pattern.trim();
+
+		while let Some(c) = rest.chars().next() {
+			if c == '[' {
+				let (literal, after) = rest[1..].split_once(']').ok_or_else(unsupported)?;
+				chrono.push_str(&literal.replace('%', "%%"));
+				rest = after;
+			} else if c.is_ascii_alphabetic() {
+				let (token, format) = TOKENS
+					.iter()
+					.find(|(token, _)| rest.starts_with(token))
+					.ok_or_else(unsupported)?;
+				match token.chars().next() {
+					Some('Y') => fields[0] = true,
+					Some('M') => fields[1] = true,
+					Some('D') => fields[2] = true,
+					_ => has_time = true,
+				}
+				chrono.push_str(format);
+				rest = &rest[token.len()..];
+			} else {
+				if c == '%' {
+					chrono.push('%');
+				}
+				chrono.push(c);
+				rest = &rest[c.len_utf8()..];
+			}
+		}
+
+		if fields.contains(&false) {
+			return Err(unsupported());
+		}
+
+		Ok(Self { pattern: pattern.trim().to_string(), chrono, has_time })
+	}
+
+	/// The pattern as written in the chart.
+	pub fn pattern(&self) -> &str {
+		&self.pattern
+	}
+
+	/// Whether the pattern includes a time of day.
+	pub fn has_time(&self) -> bool {
+		self.has_time
+	}
+
+	/// Read a date in this format; dates without a time of day are at midnight.
+	pub fn parse_date(&self, date: &str) -> Option<NaiveDateTime> {
+		if self.has_time {
+			NaiveDateTime::parse_from_str(date.trim(), &self.chrono).ok()
+		} else {
+			NaiveDate::parse_from_str(date.trim(), &self.chrono)
+				.ok()
+				.map(|date| date.and_time(NaiveTime::MIN))
+		}
+	}
+
+	/// Write a date in this format.
+	pub fn format(&self, date: NaiveDateTime) -> String {
+		date.format(&self.chrono).to_string()
+	}
+}
+
+impl fmt::Display for DateFormat {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.pattern)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_date_formats() -> Result<(), anyhow::Error> {
+		let date = NaiveDate::from_ymd_opt(2025, 1, 6)
+			.and_then(|date| date.and_hms_opt(9, 30, 0))
+			.ok_or_else(|| anyhow::anyhow!("invalid date"))?;
+
+		let format = DateFormat::parse("DD.MM.YYYY")?;
+		assert!(!format.has_time());
+		assert_eq!(format.format(date), "06.01.2025");
+		assert_eq!(format.parse_date("06.01.2025"), Some(date.date().and_time(NaiveTime::MIN)));
+
+		let format = DateFormat::parse("YYYY-MM-DD[T]HH:mm")?;
+		assert_eq!(format.format(date), "2025-01-06T09:30");
+		assert_eq!(format.parse_date("2025-01-06T09:30"), Some(date));
+		assert_eq!(format.parse_date("2025-01-06"), None);
+
+		for pattern in ["HH:mm", "YYYY-MM-DD Q", "YYYY-MM-DD [T"] {
+			assert!(DateFormat::parse(pattern).is_err(), "{pattern}");
+		}
+
+		Ok(())
+	}
+}
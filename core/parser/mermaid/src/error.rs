@@ -0,0 +1,77 @@
+//! Error types for Mermaid gantt parsing.
+
+use roadline_parser_markdown::MarkdownParseError;
+use roadline_representation_core::roadline::RoadlineBuilderError;
+use thiserror::Error;
+
+/// Error type for Mermaid gantt parsing operations.
+#[derive(Debug, Error)]
+pub enum MermaidParseError {
+	/// The content holds no `gantt` chart, or a markdown document no ```` ```mermaid ```` gantt block.
+	#[error("No Mermaid gantt chart found")]
+	NoGanttChart,
+
+	/// The chart declares no tasks.
+	#[error("No tasks found in gantt chart")]
+	NoTasksFound,
+
+	/// A line is neither a keyword such as `section` nor a task of the form `Title : metadata`.
+	#[error(
+		"Line {line}: expected a keyword or a task like 'Title : 2025-01-06, 2w', found '{text}'"
+	)]
+	InvalidLine { line: usize, text: String },
+
+	/// A `dateFormat` uses tokens which are not supported.
+	#[error("Unsupported dateFormat '{format}', use tokens such as YYYY, MM, DD, HH, mm and ss")]
+	UnsupportedDateFormat { format: String },
+
+	/// A date does not match the `dateFormat` of the chart.
+	#[error("Line {line}: invalid date '{date}', expected the format '{format}'")]
+	InvalidDate { line: usize, date: String, format: String },
+
+	/// A keyword such as `excludes` has a value which is not supported.
+	#[error("Unsupported value '{value}' for '{keyword}'")]
+	InvalidDirective { keyword: String, value: String },
+
+	/// A duration is not of the form `3d`, `2w` or `1.5h`.
+	#[error("Line {line}: invalid duration '{duration}', expected e.g. '3d', '2w' or '12h'")]
+	InvalidDuration { line: usize, duration: String },
+
+	/// A task has no start date and no task before it to follow.
+	#[error("Line {line}: task '{title}' has no start date and no task before it")]
+	MissingStart { line: usize, title: String },
+
+	/// An `after` or `until` names a task which the chart does not declare.
+	#[error("Line {line}: unknown task '{id}'")]
+	UnknownTask { line: usize, id: String },
+
+	/// A task starts after or ends until itself, through the tasks it names.
+	#[error("Line {line}: task '{title}' refers back to itself through 'after' or 'until'")]
+	CyclicReference { line: usize, title: String },
+
+	/// More than one task has the same id.
+	#[error("Line {line}: task id '{id}' is used by more than one task")]
+	DuplicateTaskId { line: usize, id: String },
+
+	/// A task ends before it starts.
+	#[error("Line {line}: task '{title}' ends before it starts")]
+	EndsBeforeStart { line: usize, title: String },
+
+	/// The chart has more tasks than task ids can number.
+	#[error("Gantt chart has {count} tasks, at most {max} are supported")]
+	TooManyTasks { count: usize, max: usize },
+
+	/// The tasks could not be added to a roadline.
+	#[error(transparent)]
+	Markdown {
+		#[from]
+		source: MarkdownParseError,
+	},
+
+	/// Error from the roadline builder.
+	#[error("Roadline builder error: {source}")]
+	RoadlineBuilder {
+		#[from]
+		source: RoadlineBuilderError,
+	},
+}
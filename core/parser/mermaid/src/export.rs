@@ -0,0 +1,231 @@
+//! Writing roadlines as Mermaid gantt charts.
+
+use super::date_format::DateFormat;
+use super::gantt::{GanttChart, GanttEnd, GanttItem, GanttStart, Tag};
+use super::{CRITICAL_LABEL, SECTION_FIELD};
+use chrono::{NaiveDateTime, NaiveTime};
+use roadline_representation_core::roadline::Roadline;
+use roadline_util::task::{FieldValue, Id as TaskId, ProgressStatus, Task};
+use std::collections::HashMap;
+
+/// A task of a roadline with its span in local time.
+struct Scheduled<'a> {
+	task: &'a Task,
+	start: NaiveDateTime,
+	end: NaiveDateTime,
+}
+
+/// Exporter which writes a roadline as a Mermaid gantt chart.
+///
+/// Tasks are grouped into sections by their [`SECTION_FIELD`] field and ordered by when
+/// they start. A task which starts when the last of its dependencies ends is written with
+/// `after`, any other task with its start date. Dates are written in the timezone of the
+/// roadline, with a time of day only if some task does not start or end at midnight.
+///
+/// # Example
+///
+/// ```
+/// use roadline_parser_mermaid::{MermaidExporter, MermaidParser};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let roadline = MermaidParser::new()
+///     .parse_and_build("gantt\n    Draft :draft, 2025-01-06, 2w\n    Review :after draft, 1w\n")?;
+///
+/// let chart = MermaidExporter::new().with_title("Validation").export(&roadline);
+/// assert!(chart.contains(":t2, after t1, 7d"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MermaidExporter {
+	title: Option<String>,
+}
+
+impl MermaidExporter {
+	/// Create a new Mermaid exporter.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Constructs with a title for the chart.
+	pub fn with_title(self, title: impl Into<String>) -> Self {
+		Self { title: Some(title.into()) }
+	}
+
+	/// Write a roadline as a gantt chart.
+	pub fn export(&self, roadline: &Roadline) -> String {
+		self.chart(roadline).to_string()
+	}
+
+	/// Write a roadline as a ```` ```mermaid ```` block, e.g. for a README.
+	pub fn export_markdown(&self, roadline: &Roadline) -> String {
+		format!("```mermaid\n{}```\n", self.chart(roadline))
+	}
+
+	/// The gantt chart of a roadline.
+	pub fn chart(&self, roadline: &Roadline) -> GanttChart {
+		let timezone = roadline.timezone();
+		let mut scheduled: Vec<Scheduled> = roadline
+			.task_ids()
+			.filter_map(|id| {
+				let task = roadline.task(id)?;
+				let span = roadline.range_algebra().span(id)?;
+				Some(Scheduled {
+					task,
+					start: timezone.local(span.start.inner()).naive_local(),
+					end: timezone.local(span.end.inner()).naive_local(),
+				})
+			})
+			.collect();
+		scheduled.sort_by_key(|scheduled| (scheduled.start, *scheduled.task.id()));
+
+		// Sections in the order they first start, after the tasks without one
+		let mut sections: Vec<Option<&str>> = vec![None];
+		for scheduled in &scheduled {
+			let section = Self::section(scheduled.task);
+			if !sections.contains(&section) {
+				sections.push(section);
+			}
+		}
+		scheduled.sort_by_key(|scheduled| {
+			sections.iter().position(|section| *section == Self::section(scheduled.task))
+		});
+
+		let ids: HashMap<TaskId, String> = scheduled
+			.iter()
+			.map(|scheduled| {
+				let task = scheduled.task;
+				(
+					*task.id(),
+					format!("{}{}", task.kind().prefix(), task.id().value()).to_lowercase(),
+				)
+			})
+			.collect();
+		let ends: HashMap<TaskId, NaiveDateTime> = scheduled
+			.iter()
+			.map(|scheduled| (*scheduled.task.id(), scheduled.end))
+			.collect();
+
+		let midnight = scheduled.iter().all(|scheduled| {
+			scheduled.start.time() == NaiveTime::MIN && scheduled.end.time() == NaiveTime::MIN
+		});
+
+		GanttChart {
+			title: self.title.clone(),
+			date_format: if midnight { DateFormat::date() } else { DateFormat::date_time() },
+			directives: Vec::new(),
+			items: scheduled.iter().map(|scheduled| Self::item(scheduled, &ids, &ends)).collect(),
+		}
+	}
+
+	fn section(task: &Task) -> Option<&str> {
+		task.field(SECTION_FIELD).and_then(FieldValue::as_text)
+	}
+
+	fn item(
+		scheduled: &Scheduled,
+		ids: &HashMap<TaskId, String>,
+		ends: &HashMap<TaskId, NaiveDateTime>,
+	) -> GanttItem {
+		let task = scheduled.task;
+
+		let mut tags = Vec::new();
+		match task.progress().status() {
+			ProgressStatus::Complete => tags.push(Tag::Done),
+			ProgressStatus::InProgress => tags.push(Tag::Active),
+			ProgressStatus::NotStarted => {}
+		}
+		if task.labels().iter().any(|label| label.as_str() == CRITICAL_LABEL) {
+			tags.push(Tag::Crit);
+		}
+		if task.kind().is_milestone() {
+			tags.push(Tag::Milestone);
+		}
+
+		// Mermaid can only say that a task starts right when its dependencies end
+		let dependencies = task
+			.depends_on()
+			.iter()
+			.map(|id| Some((ids.get(id)?.clone(), *ends.get(id)?)))
+			.collect::<Option<Vec<_>>>()
+			.unwrap_or_default();
+		let start = match dependencies.iter().map(|(_, end)| *end).max() {
+			Some(end) if end == scheduled.start => {
+				GanttStart::After(dependencies.into_iter().map(|(id, _)| id).collect())
+			}
+			_ => GanttStart::Date(scheduled.start),
+		};
+
+		GanttItem {
+			line: 0,
+			section: Self::section(task).map(str::to_string),
+			title: task.title().plain_text(),
+			tags,
+			id: ids.get(task.id()).cloned(),
+			start,
+			end: GanttEnd::Duration(scheduled.end - scheduled.start),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::MermaidParser;
+	use std::collections::BTreeMap;
+
+	#[test]
+	fn test_export_round_trip() -> Result<(), anyhow::Error> {
+		let parser = MermaidParser::new();
+		let roadline = parser.parse_and_build(
+			"gantt\n    title Validation\n    Kickoff :milestone, 2025-01-06, 0d\n    section Drafting\n    Draft :done, draft, 2025-01-06, 2w\n    Figures :active, crit, figures, 2025-01-08, 5d\n    section Review\n    Review #58; internal :review, after draft figures, 1w\n    Polish :after review, 36h\n    Ship :2025-01-24, 2d\n",
+		)?;
+
+		let exported = MermaidExporter::new().with_title("Validation").export(&roadline);
+		assert!(
+			exported.starts_with("gantt\n    title Validation\n    dateFormat YYYY-MM-DD HH:mm\n")
+		);
+		assert!(exported.contains(":milestone, m1, 2025-01-06 00:00, 0d\n    section Drafting\n"));
+		assert!(exported.contains(":active, crit, t3, 2025-01-08 00:00, 5d\n"));
+		assert!(exported.contains("Review #58; internal :t4, after t2 t3, 7d\n"));
+		assert!(exported.contains(":t6, 2025-01-24 00:00, 2d\n"));
+
+		// Reading the chart back gives the same tasks with the same spans
+		assert_eq!(spans(&parser.parse_and_build(&exported)?), spans(&roadline));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_export_interleaved_sections() -> Result<(), anyhow::Error> {
+		let parser = MermaidParser::new();
+		let roadline = parser.parse_and_build(
+			"gantt\n    section Drafting\n    Draft :draft, 2025-01-06, 2d\n    section Review\n    Review :review, after draft, 2d\n    section Drafting\n    Revise :after review, 2d\n",
+		)?;
+
+		// Revise is written in its section before Review, which it follows
+		let exported = MermaidExporter::new().export(&roadline);
+		let revise = exported.find("after t2");
+		let review = exported.find(":t2, after t1");
+		assert!(revise.is_some() && review.is_some() && revise < review);
+
+		assert_eq!(spans(&parser.parse_and_build(&exported)?), spans(&roadline));
+
+		Ok(())
+	}
+
+	/// The span, status and kind of each task of a roadline, by title.
+	fn spans(roadline: &Roadline) -> BTreeMap<String, impl PartialEq + std::fmt::Debug> {
+		roadline
+			.task_ids()
+			.filter_map(|id| {
+				let span = roadline.range_algebra().span(id)?;
+				let task = roadline.task(id)?;
+				Some((
+					task.title().plain_text(),
+					(span.start.inner(), span.end.inner(), task.progress().status(), task.kind()),
+				))
+			})
+			.collect()
+	}
+}
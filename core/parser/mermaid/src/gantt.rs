@@ -0,0 +1,503 @@
+//! The syntax of Mermaid gantt charts.
+//!
+//! A chart is read into a [`GanttChart`] as written, without resolving dates, and a
+//! [`GanttChart`] is written back as Mermaid:
+//!
+//! ```text
+//! gantt
+//!     title Validation
+//!     dateFormat YYYY-MM-DD
+//!     section Drafting
+//!     Draft  :done, draft, 2025-01-06, 2w
+//!     Review :active, review, after draft, 1w
+//!     Go     :milestone, crit, go, after review, 0d
+//! ```
+//!
+//! A task is written as `Title : tags, id, start, end`, where only the end is required.
+//! Without a start, a task starts when the task before it ends. The days named by `excludes`
+//! are kept out of durations in days, see [`GanttChart::calendar`].
+
+use super::date_format::DateFormat;
+use super::error::MermaidParseError;
+use chrono::{NaiveDateTime, TimeDelta, Weekday};
+use roadline_representation_core::calendar::Calendar;
+use std::fmt;
+
+/// Keywords which are kept as written. Of these, `excludes` and `weekend` set the working
+/// calendar of a chart; the others do not affect its tasks.
+const DIRECTIVES: [&str; 13] = [
+	"axisFormat",
+	"tickInterval",
+	"excludes",
+	"includes",
+	"todayMarker",
+	"weekday",
+	"weekend",
+	"inclusiveEndDates",
+	"topAxis",
+	"displayMode",
+	"accTitle",
+	"accDescr",
+	"click",
+];
+
+/// The units durations can be written in, with their length in milliseconds.
+///
+/// Months are counted as 30 days and years as 365 days, as in markdown roadmaps.
+const UNITS: [(&str, i64); 8] = [
+	("ms", 1),
+	("s", 1_000),
+	("m", 60_000),
+	("h", 3_600_000),
+	("d", 86_400_000),
+	("w", 7 * 86_400_000),
+	("M", 30 * 86_400_000),
+	("y", 365 * 86_400_000),
+];
+
+/// A tag of a task, written before its id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Tag {
+	Active,
+	Done,
+	Crit,
+	Milestone,
+}
+
+impl Tag {
+	/// The tag with a name, e.g. `done`.
+	pub fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"active" => Some(Self::Active),
+			"done" => Some(Self::Done),
+			"crit" => Some(Self::Crit),
+			"milestone" => Some(Self::Milestone),
+			_ => None,
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			Self::Active => "active",
+			Self::Done => "done",
+			Self::Crit => "crit",
+			Self::Milestone => "milestone",
+		}
+	}
+}
+
+/// When a task starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GanttStart {
+	/// When the task before it ends.
+	Previous,
+	/// On a date.
+	Date(NaiveDateTime),
+	/// When the last of some tasks ends, e.g. `after draft review`.
+	After(Vec<String>),
+}
+
+/// When a task ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GanttEnd {
+	/// On a date.
+	Date(NaiveDateTime),
+	/// A duration after it starts, e.g. `2w`.
+	Duration(TimeDelta),
+	/// When the first of some tasks starts, e.g. `until review`.
+	Until(Vec<String>),
+}
+
+/// A task of a gantt chart.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GanttItem {
+	/// The line the task is declared on.
+	pub line: usize,
+	/// The section the task is declared in.
+	pub section: Option<String>,
+	pub title: String,
+	pub tags: Vec<Tag>,
+	/// The id other tasks refer to the task by, e.g. `draft`.
+	pub id: Option<String>,
+	pub start: GanttStart,
+	pub end: GanttEnd,
+}
+
+impl GanttItem {
+	/// Whether the task has a tag.
+	pub fn has_tag(&self, tag: Tag) -> bool {
+		self.tags.contains(&tag)
+	}
+
+	/// Parse a task line such as `Draft :done, draft, 2025-01-06, 2w`.
+	fn parse(
+		line: usize,
+		text: &str,
+		section: Option<String>,
+		date_format: &DateFormat,
+	) -> Result<Self, MermaidParseError> {
+		let invalid = || MermaidParseError::InvalidLine { line, text: text.to_string() };
+		let (title, metadata) = text.split_once(':').ok_or_else(invalid)?;
+
+		let mut fields: Vec<&str> = metadata.split(',').map(str::trim).collect();
+		let tag_count = fields.iter().take_while(|field| Tag::from_name(field).is_some()).count();
+		let tags = fields.drain(..tag_count).filter_map(Tag::from_name).collect();
+
+		let (id, start, end) = match fields.as_slice() {
+			[end] => (None, None, *end),
+			[start, end] => (None, Some(*start), *end),
+			[id, start, end] => (Some((*id).to_string()), Some(*start), *end),
+			_ => return Err(invalid()),
+		};
+
+		let start = match start {
+			None => GanttStart::Previous,
+			Some(start) => match start.strip_prefix("after ") {
+				Some(ids) => {
+					GanttStart::After(ids.split_whitespace().map(str::to_string).collect())
+				}
+				None => GanttStart::Date(date_format.parse_date(start).ok_or_else(|| {
+					MermaidParseError::InvalidDate {
+						line,
+						date: start.to_string(),
+						format: date_format.to_string(),
+					}
+				})?),
+			},
+		};
+
+		let end = if let Some(ids) = end.strip_prefix("until ") {
+			GanttEnd::Until(ids.split_whitespace().map(str::to_string).collect())
+		} else if let Some(date) = date_format.parse_date(end) {
+			GanttEnd::Date(date)
+		} else {
+			GanttEnd::Duration(parse_duration(end).ok_or_else(|| {
+				MermaidParseError::InvalidDuration { line, duration: end.to_string() }
+			})?)
+		};
+
+		Ok(Self { line, section, title: unescape(title.trim()), tags, id, start, end })
+	}
+}
+
+/// A Mermaid gantt chart, as written.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GanttChart {
+	pub title: Option<String>,
+	/// The format dates are written in, `YYYY-MM-DD` unless declared otherwise.
+	pub date_format: DateFormat,
+	/// Keywords such as `axisFormat %d %b` or `excludes weekends`, with their values.
+	pub directives: Vec<(String, String)>,
+	/// The tasks of the chart, in the order they are declared.
+	pub items: Vec<GanttItem>,
+}
+
+impl GanttChart {
+	/// Parse a gantt chart, which starts with a `gantt` line.
+	pub fn parse(content: &str) -> Result<Self, MermaidParseError> {
+		Self::parse_from_line(content, 1)
+	}
+
+	/// Parse a gantt chart whose first line is a given line of an enclosing document.
+	pub fn parse_from_line(content: &str, first_line: usize) -> Result<Self, MermaidParseError> {
+		let mut lines = content
+			.lines()
+			.enumerate()
+			.map(|(index, text)| (first_line + index, text.trim()))
+			.filter(|(_, text)| !text.is_empty() && !text.starts_with("%%"));
+		let mut chart = Self::default();
+
+		// A front matter block may declare the title
+		let mut header = lines.next();
+		if matches!(header, Some((_, "---"))) {
+			for (_, text) in lines.by_ref() {
+				if text == "---" {
+					break;
+				}
+				if let Some(title) = text.strip_prefix("title:") {
+					chart.title = Some(title.trim().trim_matches(['"', '\'']).to_string());
+				}
+			}
+			header = lines.next();
+		}
+		if !matches!(header, Some((_, "gantt"))) {
+			return Err(MermaidParseError::NoGanttChart);
+		}
+
+		let mut section = None;
+		for (line, text) in lines {
+			let (keyword, value) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+			let value = value.trim();
+			match keyword.trim_end_matches(':') {
+				"title" => chart.title = Some(value.to_string()),
+				"dateFormat" => chart.date_format = DateFormat::parse(value)?,
+				"section" => section = Some(value.to_string()),
+				name if DIRECTIVES.contains(&name) => {
+					chart.directives.push((keyword.to_string(), value.to_string()));
+				}
+				_ => chart.items.push(GanttItem::parse(
+					line,
+					text,
+					section.clone(),
+					&chart.date_format,
+				)?),
+			}
+		}
+
+		Ok(chart)
+	}
+
+	/// The working calendar of the chart, if it `excludes` any days.
+	///
+	/// `excludes` takes weekday names, dates and `weekends`, which are Saturday and Sunday,
+	/// or Friday and Saturday after `weekend friday`. `includes` is not applied.
+	pub fn calendar(&self) -> Result<Option<Calendar>, MermaidParseError> {
+		let values = |name: &'static str| {
+			self.directives
+				.iter()
+				.filter(move |(keyword, _)| keyword.trim_end_matches(':') == name)
+				.map(|(_, value)| value.as_str())
+		};
+		let invalid = |keyword: &str, value: &str| MermaidParseError::InvalidDirective {
+			keyword: keyword.to_string(),
+			value: value.to_string(),
+		};
+		if values("excludes").next().is_none() {
+			return Ok(None);
+		}
+
+		let weekend = match values("weekend").next_back().map(str::trim) {
+			None | Some("saturday") => [Weekday::Sat, Weekday::Sun],
+			Some("friday") => [Weekday::Fri, Weekday::Sat],
+			Some(value) => return Err(invalid("weekend", value)),
+		};
+
+		let mut excluded = Vec::new();
+		let mut holidays = Vec::new();
+		for value in values("excludes") {
+			for token in value.split([',', ' ']).map(str::trim).filter(|token| !token.is_empty()) {
+				if token.eq_ignore_ascii_case("weekends") {
+					excluded.extend(weekend);
+				} else if let Ok(weekday) = token.parse::<Weekday>() {
+					excluded.push(weekday);
+				} else if let Some(date) = self
+					.date_format
+					.parse_date(token)
+					.or_else(|| DateFormat::date().parse_date(token))
+				{
+					holidays.push(date.date());
+				} else {
+					return Err(invalid("excludes", token));
+				}
+			}
+		}
+
+		let weekdays = std::iter::successors(Some(Weekday::Mon), |day| {
+			(*day != Weekday::Sun).then(|| day.succ())
+		})
+		.filter(|day| !excluded.contains(day));
+		Ok(Some(Calendar::new().with_weekdays(weekdays).with_holidays(holidays)))
+	}
+
+	/// The gantt charts in the ```` ```mermaid ```` blocks of a markdown document, with the line
+	/// each chart starts on.
+	pub fn blocks(markdown: &str) -> Vec<(usize, &str)> {
+		let mut blocks = Vec::new();
+		// The marker of the open fence, the line after it and the offset of that line
+		let mut fence: Option<(&str, usize, usize)> = None;
+		let mut offset = 0;
+
+		for (index, line) in markdown.split_inclusive('\n').enumerate() {
+			let text = line.trim();
+			match fence {
+				None => {
+					fence = ["```", "~~~"]
+						.into_iter()
+						.find(|marker| {
+							text.strip_prefix(marker).is_some_and(|info| info.trim() == "mermaid")
+						})
+						.map(|marker| (marker, index + 2, offset + line.len()));
+				}
+				Some((marker, first_line, start)) if text.starts_with(marker) => {
+					let block = &markdown[start..offset];
+					if Self::is_gantt(block) {
+						blocks.push((first_line, block));
+					}
+					fence = None;
+				}
+				Some(_) => {}
+			}
+			offset += line.len();
+		}
+
+		blocks
+	}
+
+	/// Whether a Mermaid diagram is a gantt chart.
+	fn is_gantt(diagram: &str) -> bool {
+		!matches!(Self::parse(diagram), Err(MermaidParseError::NoGanttChart))
+	}
+}
+
+impl fmt::Display for GanttChart {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(f, "gantt")?;
+		if let Some(title) = &self.title {
+			writeln!(f, "    title {title}")?;
+		}
+		writeln!(f, "    dateFormat {}", self.date_format)?;
+		for (keyword, value) in &self.directives {
+			writeln!(f, "    {keyword} {value}")?;
+		}
+
+		let width = self.items.iter().map(|item| escape(&item.title).chars().count()).max();
+		let mut section = None;
+		for item in &self.items {
+			if item.section.is_some() && item.section != section {
+				section = item.section.clone();
+				writeln!(f, "    section {}", section.as_deref().unwrap_or_default())?;
+			}
+
+			let mut fields: Vec<String> =
+				item.tags.iter().map(|tag| tag.name().to_string()).collect();
+			let start = match &item.start {
+				GanttStart::Previous => None,
+				GanttStart::Date(date) => Some(self.date_format.format(*date)),
+				GanttStart::After(ids) => Some(format!("after {}", ids.join(" "))),
+			};
+			if let Some(start) = start {
+				fields.extend(item.id.clone());
+				fields.push(start);
+			}
+			fields.push(match &item.end {
+				GanttEnd::Date(date) => self.date_format.format(*date),
+				GanttEnd::Duration(duration) => format_duration(*duration),
+				GanttEnd::Until(ids) => format!("until {}", ids.join(" ")),
+			});
+
+			let title = escape(&item.title);
+			let width = width.unwrap_or_default();
+			writeln!(f, "    {title:<width$} :{}", fields.join(", "))?;
+		}
+
+		Ok(())
+	}
+}
+
+/// Parse a duration such as `3d`, `2w` or `1.5h`.
+fn parse_duration(duration: &str) -> Option<TimeDelta> {
+	let split = duration.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+	let (value, unit) = duration.split_at(split);
+	let value: f64 = value.parse().ok()?;
+	let (_, millis) = UNITS.iter().find(|(name, _)| *name == unit)?;
+	Some(TimeDelta::milliseconds((value * *millis as f64).round() as i64))
+}
+
+/// Write a duration in the largest unit up to days which it is a whole number of.
+fn format_duration(duration: TimeDelta) -> String {
+	let millis = duration.num_milliseconds();
+	UNITS[..5]
+		.iter()
+		.rev()
+		.find(|(_, unit)| millis % unit == 0)
+		.map(|(name, unit)| format!("{}{name}", millis / unit))
+		.unwrap_or_else(|| format!("{millis}ms"))
+}
+
+/// Write the characters which end a title or a statement as entity codes, e.g. `#58;`.
+fn escape(title: &str) -> String {
+	title.chars().fold(String::with_capacity(title.len()), |mut escaped, c| {
+		match c {
+			'#' | ':' | ';' => escaped.push_str(&format!("#{};", u32::from(c))),
+			_ => escaped.push(c),
+		}
+		escaped
+	})
+}
+
+/// Replace numeric entity codes such as `#58;` with the characters they stand for.
+fn unescape(title: &str) -> String {
+	let mut unescaped = String::with_capacity(title.len());
+	let mut rest = title;
+	while let Some(index) = rest.find('#') {
+		unescaped.push_str(&rest[..index]);
+		rest = &rest[index..];
+		let code = rest[1..]
+			.split_once(';')
+			.and_then(|(code, _)| code.parse().ok())
+			.and_then(char::from_u32);
+		match code {
+			Some(c) => {
+				unescaped.push(c);
+				rest = &rest[rest.find(';').map_or(rest.len(), |end| end + 1)..];
+			}
+			None => {
+				unescaped.push('#');
+				rest = &rest[1..];
+			}
+		}
+	}
+	unescaped.push_str(rest);
+	unescaped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_parse_and_write() -> Result<(), anyhow::Error> {
+		let chart = GanttChart::parse(
+			"%% plan\ngantt\n    title Validation\n    dateFormat YYYY-MM-DD\n    excludes weekends\n    Kickoff :2025-01-06, 1d\n    section Drafting\n    Draft #35;1 #58; text :done, draft, 2025-01-07, 2w\n    Review :active, crit, after draft, 1.5d\n    Polish :until go\n",
+		)?;
+
+		assert_eq!(chart.title.as_deref(), Some("Validation"));
+		assert_eq!(chart.directives, vec![("excludes".to_string(), "weekends".to_string())]);
+		assert_eq!(chart.items.len(), 4);
+		assert_eq!(chart.items[0].section, None);
+
+		let draft = &chart.items[1];
+		assert_eq!(draft.line, 8);
+		assert_eq!(draft.title, "Draft #1 : text");
+		assert_eq!(draft.section.as_deref(), Some("Drafting"));
+		assert_eq!(draft.tags, vec![Tag::Done]);
+		assert_eq!(draft.id.as_deref(), Some("draft"));
+		assert_eq!(draft.end, GanttEnd::Duration(TimeDelta::weeks(2)));
+
+		let review = &chart.items[2];
+		assert_eq!(review.start, GanttStart::After(vec!["draft".to_string()]));
+		assert_eq!(review.end, GanttEnd::Duration(TimeDelta::hours(36)));
+		assert!(review.has_tag(Tag::Crit));
+		assert_eq!(chart.items[3].start, GanttStart::Previous);
+		assert_eq!(chart.items[3].end, GanttEnd::Until(vec!["go".to_string()]));
+
+		let written = chart.to_string();
+		assert!(written.contains("    Draft #35;1 #58; text :done, draft, 2025-01-07, 14d\n"));
+		assert!(written.contains(" :active, crit, after draft, 36h\n"));
+
+		// Only the lines of the tasks change when the chart is read back
+		let mut reread = GanttChart::parse(&written)?;
+		for (item, original) in reread.items.iter_mut().zip(&chart.items) {
+			item.line = original.line;
+		}
+		assert_eq!(reread, chart);
+
+		assert!(matches!(
+			GanttChart::parse("gantt\n    Draft :2025-01-07, soon\n"),
+			Err(MermaidParseError::InvalidDuration { line: 2, .. })
+		));
+		assert!(matches!(
+			GanttChart::parse("flowchart LR\n    A --> B\n"),
+			Err(MermaidParseError::NoGanttChart)
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_blocks() {
+		let markdown = "# Plan\n\n```mermaid\nflowchart LR\n  A --> B\n```\n\n```mermaid\ngantt\n  Draft :2025-01-06, 2w\n```\n";
+		let blocks = GanttChart::blocks(markdown);
+		assert_eq!(blocks, vec![(9, "gantt\n  Draft :2025-01-06, 2w\n")]);
+	}
+}
@@ -0,0 +1,528 @@
+//! Mermaid gantt parser and exporter for roadmaps.
+//!
+//! Many plans live in Mermaid `gantt` blocks inside READMEs. This crate reads such charts
+//! into tasks, and writes a roadline back as a gantt chart, so that a roadmap renders
+//! natively wherever Mermaid does, such as on GitHub.
+//!
+//! ```text
+//! gantt
+//!     title Validation
+//!     dateFormat YYYY-MM-DD
+//!     section Drafting
+//!     Draft  :done, draft, 2025-01-06, 2w
+//!     Review :active, review, after draft, 1w
+//!     Go     :milestone, crit, go, after review, 0d
+//! ```
+//!
+//! Tasks are numbered in the order they are declared, e.g. `T1`, `T2` and `M3` for the
+//! chart above, and are mapped as follows:
+//!
+//! - `after` makes a task depend on the tasks named, which may be declared before or after
+//!   it; it starts when the last of them ends.
+//! - Tasks with a date, or without a start, are roots which start as far from the start of
+//!   the chart as Mermaid draws them.
+//! - `milestone` makes a task a milestone, which lies halfway through its duration.
+//! - `done` records the task as started and finished on its dates, `active` as started.
+//! - `crit` labels the task [`CRITICAL_LABEL`], and its section is kept in the
+//!   [`SECTION_FIELD`] field.
+//!
+//! Dates are in UTC. Keywords such as `axisFormat` are kept in the front matter fields of the
+//! document. When a chart `excludes` days, durations of whole days count working days of
+//! [`GanttChart::calendar`], as Mermaid draws them, and [`MermaidParser::parse_and_build`]
+//! resolves them with that calendar.
+
+pub mod date_format;
+pub mod error;
+pub mod export;
+pub mod gantt;
+
+pub use date_format::DateFormat;
+pub use error::MermaidParseError;
+pub use export::MermaidExporter;
+pub use gantt::{GanttChart, GanttEnd, GanttItem, GanttStart, Tag};
+
+use chrono::{NaiveDateTime, NaiveTime, TimeDelta};
+use roadline_parser_markdown::{FrontMatter, RoadmapDocument};
+use roadline_representation_core::calendar::{Calendar, Calendars};
+use roadline_representation_core::range_algebra::Date;
+use roadline_representation_core::roadline::Roadline;
+use roadline_util::task::{
+	End, FieldValue, Id as TaskId, Kind, Progress, Range, Start, Summary, TargetDate, Task, Title,
+};
+use std::collections::{BTreeSet, HashMap};
+
+/// The custom field the section of a task is kept in.
+pub const SECTION_FIELD: &str = "Section";
+
+/// The label of tasks tagged `crit`.
+pub const CRITICAL_LABEL: &str = "crit";
+
+/// When a task of a chart starts and ends, and which tasks it starts after.
+#[derive(Debug, Clone)]
+struct Schedule {
+	start: NaiveDateTime,
+	end: NaiveDateTime,
+	/// The indices of the tasks named by `after`.
+	after: Vec<usize>,
+	/// The duration in working days, for charts which exclude days.
+	workdays: Option<u32>,
+}
+
+/// Parser for Mermaid gantt charts.
+///
+/// # Example
+///
+/// ````
+/// use roadline_parser_mermaid::MermaidParser;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let readme = r#"
+/// ```mermaid
+/// gantt
+///     Draft  :draft, 2025-01-06, 2w
+///     Review :after draft, 1w
+/// ```
+/// "#;
+///
+/// let document = MermaidParser::new().parse_markdown(readme)?;
+/// assert_eq!(document.tasks.len(), 2);
+/// # Ok(())
+/// # }
+/// ````
+#[derive(Debug, Clone, Default)]
+pub struct MermaidParser;
+
+impl MermaidParser {
+	/// Create a new Mermaid parser.
+	pub fn new() -> Self {
+		Self
+	}
+
+	/// Parse a gantt chart and return its tasks.
+	pub fn parse_tasks(&self, content: &str) -> Result<Vec<Task>, MermaidParseError> {
+		Ok(self.parse_document(content)?.tasks)
+	}
+
+	/// Parse a gantt chart, which starts with a `gantt` line.
+	pub fn parse_document(&self, content: &str) -> Result<RoadmapDocument, MermaidParseError> {
+		self.document_from_chart(&GanttChart::parse(content)?)
+	}
+
+	/// Parse the first gantt chart in the ```` ```mermaid ```` blocks of a markdown document.
+	///
+	/// Errors report lines of the markdown document.
+	pub fn parse_markdown(&self, markdown: &str) -> Result<RoadmapDocument, MermaidParseError> {
+		let (line, block) = GanttChart::blocks(markdown)
+			.into_iter()
+			.next()
+			.ok_or(MermaidParseError::NoGanttChart)?;
+		self.document_from_chart(&GanttChart::parse_from_line(block, line)?)
+	}
+
+	/// Parse a gantt chart and build a roadline representation, starting when the chart does.
+	///
+	/// Working days are resolved with the calendar of the chart.
+	pub fn parse_and_build(&self, content: &str) -> Result<Roadline, MermaidParseError> {
+		let chart = GanttChart::parse(content)?;
+		let builder = self.document_from_chart(&chart)?.into_builder()?;
+		let builder = match chart.calendar()? {
+			Some(calendar) => builder.with_calendars(Calendars::new(calendar)),
+			None => builder,
+		};
+		Ok(builder.build()?)
+	}
+
+	/// Convert a gantt chart into a document.
+	pub fn document_from_chart(
+		&self,
+		chart: &GanttChart,
+	) -> Result<RoadmapDocument, MermaidParseError> {
+		let max = usize::from(u8::MAX);
+		if chart.items.len() > max {
+			return Err(MermaidParseError::TooManyTasks { count: chart.items.len(), max });
+		}
+
+		let schedules = Self::resolve(chart, chart.calendar()?.as_ref())?;
+		let root = schedules
+			.iter()
+			.map(|schedule| schedule.start.date())
+			.min()
+			.ok_or(MermaidParseError::NoTasksFound)?;
+		let root_time = root.and_time(NaiveTime::MIN);
+
+		let tasks = chart
+			.items
+			.iter()
+			.enumerate()
+			.map(|(index, item)| Self::task(index, item, &schedules, root_time))
+			.collect();
+
+		let front_matter = FrontMatter {
+			start: Some(root),
+			timezone: None,
+			fields: chart.directives.iter().cloned().collect(),
+		};
+
+		Ok(RoadmapDocument {
+			title: chart.title.clone(),
+			authors: Vec::new(),
+			all_leads: Vec::new(),
+			start: Some(root),
+			front_matter,
+			tasks,
+			links: Vec::new(),
+		})
+	}
+
+	/// Work out when the tasks of a chart start and end, as Mermaid draws them.
+	///
+	/// With a calendar, durations of whole days count working days.
+	fn resolve(
+		chart: &GanttChart,
+		calendar: Option<&Calendar>,
+	) -> Result<Vec<Schedule>, MermaidParseError> {
+		// Tasks may refer to tasks declared after them, so all ids are collected first
+		let mut ids: HashMap<&str, usize> = HashMap::new();
+		for (index, item) in chart.items.iter().enumerate() {
+			if let Some(id) = &item.id {
+				if ids.insert(id, index).is_some() {
+					return Err(MermaidParseError::DuplicateTaskId {
+						line: item.line,
+						id: id.clone(),
+					});
+				}
+			}
+		}
+
+		// Resolve each task once the tasks it refers to are, until no task is left
+		let mut schedules: Vec<Option<Schedule>> = vec![None; chart.items.len()];
+		let mut pending: Vec<usize> = (0..chart.items.len()).collect();
+		while let Some(&first) = pending.first() {
+			let count = pending.len();
+			let mut waiting = Vec::new();
+			for index in pending {
+				match Self::schedule(chart, index, &ids, &schedules, calendar)? {
+					Some(schedule) => schedules[index] = Some(schedule),
+					None => waiting.push(index),
+				}
+			}
+			if waiting.len() == count {
+				let item = &chart.items[first];
+				return Err(MermaidParseError::CyclicReference {
+					line: item.line,
+					title: item.title.clone(),
+				});
+			}
+			pending = waiting;
+		}
+
+		Ok(schedules.into_iter().flatten().collect())
+	}
+
+	/// Work out when the task of a chart at an index starts and ends, or `None` while a task
+	/// it refers to is not resolved yet.
+	fn schedule(
+		chart: &GanttChart,
+		index: usize,
+		ids: &HashMap<&str, usize>,
+		schedules: &[Option<Schedule>],
+		calendar: Option<&Calendar>,
+	) -> Result<Option<Schedule>, MermaidParseError> {
+		let item = &chart.items[index];
+		let lookup = |references: &[String]| {
+			references
+				.iter()
+				.map(|id| {
+					ids.get(id.as_str()).copied().ok_or_else(|| MermaidParseError::UnknownTask {
+						line: item.line,
+						id: id.clone(),
+					})
+				})
+				.collect::<Result<Vec<usize>, _>>()
+		};
+		let resolved = |indices: &[usize]| {
+			indices
+				.iter()
+				.map(|&index| schedules[index].as_ref())
+				.collect::<Option<Vec<&Schedule>>>()
+		};
+		let missing_start =
+			|| MermaidParseError::MissingStart { line: item.line, title: item.title.clone() };
+
+		let after = match &item.start {
+			GanttStart::After(references) => lookup(references)?,
+			GanttStart::Previous | GanttStart::Date(_) => Vec::new(),
+		};
+		let start = match &item.start {
+			GanttStart::Previous => {
+				let previous = index.checked_sub(1).ok_or_else(missing_start)?;
+				match &schedules[previous] {
+					Some(previous) => previous.end,
+					None => return Ok(None),
+				}
+			}
+			GanttStart::Date(date) => *date,
+			GanttStart::After(_) => match resolved(&after) {
+				Some(dependencies) => dependencies
+					.iter()
+					.map(|dependency| dependency.end)
+					.max()
+					.ok_or_else(missing_start)?,
+				None => return Ok(None),
+			},
+		};
+
+		let mut workdays = None;
+		let end = match &item.end {
+			GanttEnd::Date(date) => *date,
+			GanttEnd::Duration(duration) => match calendar.zip(Self::whole_days(*duration)) {
+				Some((calendar, days)) => {
+					workdays = Some(days);
+					calendar.add_workdays(Date::new(start.and_utc()), days).inner().naive_utc()
+				}
+				None => start + *duration,
+			},
+			GanttEnd::Until(references) => match resolved(&lookup(references)?) {
+				Some(until) => until.iter().map(|until| until.start).min().ok_or_else(|| {
+					MermaidParseError::InvalidDuration {
+						line: item.line,
+						duration: "until".to_string(),
+					}
+				})?,
+				None => return Ok(None),
+			},
+		};
+		if end < start {
+			return Err(MermaidParseError::EndsBeforeStart {
+				line: item.line,
+				title: item.title.clone(),
+			});
+		}
+
+		// A milestone lies halfway through its duration, as Mermaid draws it
+		let (start, end, workdays) = if item.has_tag(Tag::Milestone) {
+			let point = start + (end - start) / 2;
+			(point, point, None)
+		} else {
+			(start, end, workdays)
+		};
+
+		Ok(Some(Schedule { start, end, after, workdays }))
+	}
+
+	/// The number of days in a duration of whole days.
+	fn whole_days(duration: TimeDelta) -> Option<u32> {
+		let whole = duration > TimeDelta::zero() && duration.num_seconds() % 86_400 == 0;
+		whole.then(|| u32::try_from(duration.num_days()).ok()).flatten()
+	}
+
+	/// Convert the task of a chart at an index.
+	fn task(index: usize, item: &GanttItem, schedules: &[Schedule], root: NaiveDateTime) -> Task {
+		let task_id = |index: usize| TaskId::new(u8::try_from(index + 1).unwrap_or(u8::MAX));
+		let id = task_id(index);
+		let schedule = &schedules[index];
+
+		// Tasks which follow others start relative to the last of them to end, which their
+		// start coincides with, and roots relative to the start of the chart
+		let (reference, reference_start) = schedule
+			.after
+			.iter()
+			.max_by_key(|&&after| schedules[after].end)
+			.map(|&after| (task_id(after), schedules[after].start))
+			.unwrap_or((id, root));
+		let offset = (schedule.start - reference_start).to_std().unwrap_or_default();
+		let duration = (schedule.end - schedule.start).to_std().unwrap_or_default();
+		let range = Range::new(
+			Start::from(TargetDate::new(reference.into(), offset.into())),
+			End::from(duration),
+		);
+		let range = match schedule.workdays {
+			Some(workdays) => range.with_workdays(workdays),
+			None => range,
+		};
+
+		let progress = if item.has_tag(Tag::Done) {
+			Progress::new()
+				.with_started(schedule.start.and_utc())
+				.with_finished(schedule.end.and_utc())
+		} else if item.has_tag(Tag::Active) {
+			Progress::new().with_started(schedule.start.and_utc())
+		} else {
+			Progress::new()
+		};
+
+		let kind = if item.has_tag(Tag::Milestone) { Kind::Milestone } else { Kind::Task };
+		let task = Task::new(
+			id,
+			Title::new(item.title.clone()),
+			schedule.after.iter().map(|&after| task_id(after)).collect(),
+			BTreeSet::new(),
+			Summary::new(String::new()),
+			range,
+		)
+		.with_kind(kind)
+		.with_progress(progress)
+		.with_labels(item.has_tag(Tag::Crit).then_some(CRITICAL_LABEL));
+
+		match &item.section {
+			Some(section) => task.with_field(SECTION_FIELD, FieldValue::Text(section.clone())),
+			None => task,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use roadline_util::task::ProgressStatus;
+
+	const README: &str = r#"# Validation
+
+The plan, as drawn on GitHub:
+
+```mermaid
+gantt
+    title Validation
+    dateFormat YYYY-MM-DD
+    excludes weekends
+    section Drafting
+    Draft     :done, draft, 2025-01-06, 2w
+    Figures   :active, figures, 2025-01-13, 5d
+    section Review
+    Review    :crit, review, after draft figures, 1w
+    Go        :milestone, go, after review, 0d
+    Follow-up :3d
+```
+"#;
+
+	fn day(date: &str) -> Result<NaiveDateTime, anyhow::Error> {
+		Ok(chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")?.and_time(NaiveTime::MIN))
+	}
+
+	#[test]
+	fn test_parse_markdown() -> Result<(), anyhow::Error> {
+		let document = MermaidParser::new().parse_markdown(README)?;
+		assert_eq!(document.title.as_deref(), Some("Validation"));
+		assert_eq!(document.start, chrono::NaiveDate::from_ymd_opt(2025, 1, 6));
+		assert_eq!(
+			document.front_matter.fields.get("excludes").map(String::as_str),
+			Some("weekends")
+		);
+
+		let tasks = &document.tasks;
+		assert_eq!(tasks.len(), 5);
+		assert_eq!(tasks[0].progress().status(), ProgressStatus::Complete);
+		assert_eq!(tasks[1].progress().status(), ProgressStatus::InProgress);
+		assert_eq!(tasks[0].field(SECTION_FIELD).and_then(FieldValue::as_text), Some("Drafting"));
+
+		let review = &tasks[2];
+		assert_eq!(review.depends_on(), &BTreeSet::from([TaskId::new(1), TaskId::new(2)]));
+		assert!(review.labels().iter().any(|label| label.as_str() == CRITICAL_LABEL));
+		assert_eq!(review.field(SECTION_FIELD).and_then(FieldValue::as_text), Some("Review"));
+
+		assert_eq!(tasks[3].kind(), Kind::Milestone);
+		assert!(tasks[4].is_root());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_and_build() -> Result<(), anyhow::Error> {
+		let block = GanttChart::blocks(README).first().map(|(_, block)| *block).unwrap_or_default();
+		let roadline = MermaidParser::new().parse_and_build(block)?;
+
+		let span = |id: u8| {
+			roadline.range_algebra().span(&TaskId::new(id)).map(|span| {
+				(span.start.inner().inner().naive_utc(), span.end.inner().inner().naive_utc())
+			})
+		};
+		// Weekends are excluded, so two weeks are fourteen working days
+		assert_eq!(span(1), Some((day("2025-01-06")?, day("2025-01-24")?)));
+		assert_eq!(span(2), Some((day("2025-01-13")?, day("2025-01-18")?)));
+		// Review starts when the later of Draft and Figures ends
+		assert_eq!(span(3), Some((day("2025-01-24")?, day("2025-02-04")?)));
+		assert_eq!(span(4), Some((day("2025-02-04")?, day("2025-02-04")?)));
+		assert_eq!(span(5), Some((day("2025-02-04")?, day("2025-02-07")?)));
+
+		// Without excludes, durations are calendar days
+		let roadline = MermaidParser::new().parse_and_build(&block.replace("excludes", "%%"))?;
+		let span = roadline.range_algebra().span(&TaskId::new(1)).map(|span| {
+			(span.start.inner().inner().naive_utc(), span.end.inner().inner().naive_utc())
+		});
+		assert_eq!(span, Some((day("2025-01-06")?, day("2025-01-20")?)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_excludes() -> Result<(), anyhow::Error> {
+		let chart = "gantt\n    weekend friday\n    excludes weekends, 2025-01-08\n    A :a, 2025-01-06, 3d\n    B :after a, 2d\n";
+		let roadline = MermaidParser::new().parse_and_build(chart)?;
+
+		// Friday, Saturday and the holiday are not worked
+		let span = |id: u8| {
+			roadline.range_algebra().span(&TaskId::new(id)).map(|span| {
+				(span.start.inner().inner().naive_utc(), span.end.inner().inner().naive_utc())
+			})
+		};
+		assert_eq!(span(1), Some((day("2025-01-06")?, day("2025-01-10")?)));
+		assert_eq!(span(2), Some((day("2025-01-10")?, day("2025-01-14")?)));
+
+		assert!(matches!(
+			GanttChart::parse("gantt\n    excludes lunchtime\n    A :2025-01-06, 1d\n")?.calendar(),
+			Err(MermaidParseError::InvalidDirective { ref value, .. }) if value == "lunchtime"
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forward_references() -> Result<(), anyhow::Error> {
+		let parser = MermaidParser::new();
+		let roadline = parser.parse_and_build(
+			"gantt\n    Review :review, after draft, 2d\n    Notes :notes, 2025-01-06, until review\n    Draft :draft, 2025-01-06, 1d\n",
+		)?;
+
+		let span = |id: u8| {
+			roadline.range_algebra().span(&TaskId::new(id)).map(|span| {
+				(span.start.inner().inner().naive_utc(), span.end.inner().inner().naive_utc())
+			})
+		};
+		assert_eq!(span(1), Some((day("2025-01-07")?, day("2025-01-09")?)));
+		assert_eq!(span(2), Some((day("2025-01-06")?, day("2025-01-07")?)));
+
+		assert!(matches!(
+			parser.parse_document("gantt\n    A :a, after b, 1d\n    B :b, after a, 1d\n"),
+			Err(MermaidParseError::CyclicReference { line: 2, .. })
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_errors_name_the_line() {
+		let parser = MermaidParser::new();
+
+		assert!(matches!(
+			parser.parse_markdown(README.replace("after draft figures", "after drafts").as_str()),
+			Err(MermaidParseError::UnknownTask { line: 14, ref id }) if id == "drafts"
+		));
+		assert!(matches!(
+			parser.parse_document("gantt\n    Draft :2w\n"),
+			Err(MermaidParseError::MissingStart { line: 2, .. })
+		));
+		assert!(matches!(
+			parser.parse_document("gantt\n    A :a, 2025-01-06, 1d\n    B :a, 2025-01-07, 1d\n"),
+			Err(MermaidParseError::DuplicateTaskId { line: 3, .. })
+		));
+		assert!(matches!(
+			parser.parse_document("gantt\n    A :2025-01-06, 2025-01-05\n"),
+			Err(MermaidParseError::EndsBeforeStart { line: 2, .. })
+		));
+		assert!(matches!(parser.parse_document("gantt\n"), Err(MermaidParseError::NoTasksFound)));
+		assert!(matches!(
+			parser.parse_markdown("# No charts\n"),
+			Err(MermaidParseError::NoGanttChart)
+		));
+	}
+}
@@ -0,0 +1,87 @@
+//! Error types for structured roadmap parsing.
+
+use roadline_parser_markdown::MarkdownParseError;
+use roadline_representation_core::roadline::RoadlineBuilderError;
+use thiserror::Error;
+
+/// Error type for structured parsing operations.
+#[derive(Debug, Error)]
+pub enum StructuredParseError {
+	/// The format of a file could not be told from its extension.
+	#[error("Unknown roadmap format for '{path}', expected a .yaml, .yml, .toml or .json file")]
+	UnknownFormat { path: String },
+
+	/// The roadmap is not valid YAML, or does not have the expected shape.
+	#[error("Invalid YAML roadmap: {source}")]
+	Yaml {
+		#[from]
+		source: serde_yaml::Error,
+	},
+
+	/// The roadmap is not valid TOML, or does not have the expected shape.
+	#[error("Invalid TOML roadmap: {source}")]
+	Toml {
+		#[from]
+		source: toml::de::Error,
+	},
+
+	/// The roadmap is not valid JSON, or does not have the expected shape.
+	#[error("Invalid JSON roadmap: {source}")]
+	Json {
+		#[from]
+		source: serde_json::Error,
+	},
+
+	/// The roadmap declares no tasks.
+	#[error("No tasks found in roadmap")]
+	NoTasksFound,
+
+	/// A task id is not of the form `T3`, or `M3` for a milestone.
+	#[error("Invalid task id '{id}', expected e.g. 'T3' or 'M3'")]
+	InvalidTaskId { id: String },
+
+	/// More than one task has the same id.
+	#[error("Task id '{id}' is used by more than one task")]
+	DuplicateTaskId { id: String },
+
+	/// More than one subtask of a task has the same id.
+	#[error("Subtask id '{id}' is used by more than one subtask")]
+	DuplicateSubtaskId { id: String },
+
+	/// A field of a task could not be parsed with the grammar of the markdown parser.
+	#[error("Task '{id}': {source}")]
+	Task {
+		id: String,
+		#[source]
+		source: MarkdownParseError,
+	},
+
+	/// A document-level field could not be parsed, or the tasks could not be scheduled.
+	#[error(transparent)]
+	Markdown {
+		#[from]
+		source: MarkdownParseError,
+	},
+
+	/// Error from the roadline builder.
+	#[error("Roadline builder error: {source}")]
+	RoadlineBuilder {
+		#[from]
+		source: RoadlineBuilderError,
+	},
+
+	/// IO error when reading files.
+	#[error("IO error: {source}")]
+	Io {
+		#[from]
+		source: std::io::Error,
+	},
+}
+
+impl StructuredParseError {
+	/// Attach the id of the task a grammar error occurred in.
+	pub fn in_task(id: impl Into<String>) -> impl FnOnce(MarkdownParseError) -> Self {
+		let id = id.into();
+		move |source| Self::Task { id, source }
+	}
+}
@@ -0,0 +1,64 @@
+//! The formats a structured roadmap can be written in.
+
+use super::error::StructuredParseError;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A data format for declarative roadmaps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+	Yaml,
+	Toml,
+	Json,
+}
+
+impl Format {
+	/// Tell the format of a file from its extension, e.g. `roadmap.yaml`.
+	pub fn from_path(path: impl AsRef<Path>) -> Result<Self, StructuredParseError> {
+		let path = path.as_ref();
+		path.extension()
+			.and_then(|extension| extension.to_str())
+			.and_then(|extension| extension.parse().ok())
+			.ok_or_else(|| StructuredParseError::UnknownFormat { path: path.display().to_string() })
+	}
+
+	/// Deserialize a roadmap written in this format.
+	///
+	/// Errors in the syntax or the shape of the roadmap come from the deserializer of the
+	/// format, and so report the line and column they occurred at.
+	pub fn deserialize<T: DeserializeOwned>(
+		&self,
+		content: &str,
+	) -> Result<T, StructuredParseError> {
+		Ok(match self {
+			Self::Yaml => serde_yaml::from_str(content)?,
+			Self::Toml => toml::from_str(content)?,
+			Self::Json => serde_json::from_str(content)?,
+		})
+	}
+}
+
+impl FromStr for Format {
+	type Err = StructuredParseError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		match name.trim().to_ascii_lowercase().as_str() {
+			"yaml" | "yml" => Ok(Self::Yaml),
+			"toml" => Ok(Self::Toml),
+			"json" => Ok(Self::Json),
+			_ => Err(StructuredParseError::UnknownFormat { path: name.to_string() }),
+		}
+	}
+}
+
+impl fmt::Display for Format {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(match self {
+			Self::Yaml => "yaml",
+			Self::Toml => "toml",
+			Self::Json => "json",
+		})
+	}
+}
@@ -0,0 +1,578 @@
+//! Structured parser for roadmap documents.
+//!
+//! This crate reads declarative roadmaps written in YAML, TOML or JSON, for teams which
+//! would rather not write OROAD markdown. Tasks declare the same fields as in markdown,
+//! such as `starts`, `ends` and `depends_on`, and their expressions are parsed with the
+//! grammar of the markdown parser, so `starts: T1 + 2 weeks` means the same in both.
+//!
+//! ```yaml
+//! start: 2025-01-06
+//! tasks:
+//!   - id: T1
+//!     title: Draft
+//!     starts: T0 + 0 days
+//!     ends: 2 weeks
+//!     leads: ["[Ada](mailto:ada@example.com)"]
+//!     subtasks:
+//!       - id: T1.1
+//!         title: Outline
+//!         ends: 1 week
+//!   - id: T2
+//!     title: Review
+//!     starts: T1 + 2 weeks
+//!     ends: 1 week
+//!     depends_on: [T1]
+//! ```
+
+pub mod error;
+pub mod format;
+pub mod spec;
+
+pub use error::StructuredParseError;
+pub use format::Format;
+pub use spec::{LeadSpec, RoadmapSpec, SubtaskSpec, SubtaskStatusSpec, TaskSpec};
+
+use roadline_parser_markdown::range::RangeParser;
+use roadline_parser_markdown::task::parse_task_reference;
+use roadline_parser_markdown::{
+	DocumentParser, EndDate, FrontMatter, LeadParser, MarkdownParseError, RichTextParser,
+	RoadmapDocument,
+};
+use roadline_representation_core::roadline::Roadline;
+use roadline_representation_core::timezone::Timezone;
+use roadline_util::task::subtask::{
+	Content, Id as SubtaskId, Lead, Position, Status, StatusDate, Subtask, Title as SubtaskTitle,
+};
+use roadline_util::task::{EmbeddedSubtask, Id as TaskId, SubtaskDependency, Summary, Task, Title};
+use std::collections::{BTreeSet, HashSet};
+use std::path::Path;
+
+/// Parser for declarative roadmaps in YAML, TOML or JSON.
+///
+/// # Example
+///
+/// ```no_run
+/// use roadline_parser_structured::{Format, StructuredParser};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let content = r#"
+/// tasks:
+///   - id: T1
+///     title: Push Towards Validation
+///     starts: T0 + 0 months
+///     ends: 1 month
+/// "#;
+///
+/// let roadline = StructuredParser::new().parse_and_build(content, Format::Yaml)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StructuredParser {
+	range_parser: RangeParser,
+	end_parser: EndDate,
+	lead_parser: LeadParser,
+	rich_text_parser: RichTextParser,
+}
+
+impl Default for StructuredParser {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl StructuredParser {
+	/// Create a new structured parser.
+	pub fn new() -> Self {
+		Self {
+			range_parser: RangeParser::new(),
+			end_parser: EndDate::new(),
+			lead_parser: LeadParser::new(),
+			rich_text_parser: RichTextParser::new(),
+		}
+	}
+
+	/// Parse a roadmap and return its tasks.
+	pub fn parse_tasks(
+		&self,
+		content: &str,
+		format: Format,
+	) -> Result<Vec<Task>, StructuredParseError> {
+		Ok(self.parse_document(content, format)?.tasks)
+	}
+
+	/// Parse a roadmap, including its title, authors, start date and timezone.
+	///
+	/// The result is the same document the markdown parser produces, so that both
+	/// kinds of roadmap are built the same way.
+	pub fn parse_document(
+		&self,
+		content: &str,
+		format: Format,
+	) -> Result<RoadmapDocument, StructuredParseError> {
+		self.document_from_spec(format.deserialize(content)?)
+	}
+
+	/// Parse a roadmap file, telling its format from its extension.
+	pub fn parse_file(
+		&self,
+		path: impl AsRef<Path>,
+	) -> Result<RoadmapDocument, StructuredParseError> {
+		let format = Format::from_path(&path)?;
+		self.parse_document(&std::fs::read_to_string(path)?, format)
+	}
+
+	/// Parse a roadmap and build a roadline representation.
+	///
+	/// The declared start date is used as the root date, and days are resolved in the
+	/// declared timezone, as for markdown roadmaps.
+	pub fn parse_and_build(
+		&self,
+		content: &str,
+		format: Format,
+	) -> Result<Roadline, StructuredParseError> {
+		Ok(self.parse_document(content, format)?.into_builder()?.build()?)
+	}
+
+	/// Convert a parsed roadmap into a document.
+	pub fn document_from_spec(
+		&self,
+		spec: RoadmapSpec,
+	) -> Result<RoadmapDocument, StructuredParseError> {
+		if spec.tasks.is_empty() {
+			return Err(StructuredParseError::NoTasksFound);
+		}
+
+		let mut front_matter = FrontMatter::default();
+		if let Some(start) = &spec.start {
+			front_matter.start = Some(DocumentParser::parse_date(start)?);
+		}
+		if let Some(timezone) = &spec.timezone {
+			let timezone = Timezone::parse(timezone)
+				.map_err(|e| MarkdownParseError::InvalidFrontMatter { message: e.to_string() })?;
+			front_matter.timezone = Some(timezone);
+		}
+
+		let timezone = front_matter.timezone.unwrap_or_default();
+		let mut ids = HashSet::new();
+		let mut tasks = Vec::with_capacity(spec.tasks.len());
+		for task in &spec.tasks {
+			let task = self.parse_task_in(task, timezone)?;
+			if !ids.insert(*task.id()) {
+				return Err(StructuredParseError::DuplicateTaskId {
+					id: format!("{}{}", task.kind().prefix(), task.id().value()),
+				});
+			}
+			tasks.push(task);
+		}
+
+		let authors = spec
+			.authors
+			.iter()
+			.map(|author| self.parse_lead(author))
+			.collect::<Result<Vec<Lead>, _>>()?;
+
+		Ok(RoadmapDocument {
+			title: spec.title,
+			authors,
+			all_leads: Vec::new(),
+			start: front_matter.start,
+			front_matter,
+			tasks,
+			links: Vec::new(),
+		})
+	}
+
+	/// Convert a declared task, parsing its fields with the markdown grammar.
+	///
+	/// Completion dates are days in UTC; see [`StructuredParser::document_from_spec`] for
+	/// days in the timezone of a roadmap.
+	pub fn parse_task(&self, spec: &TaskSpec) -> Result<Task, StructuredParseError> {
+		self.parse_task_in(spec, Timezone::utc())
+	}
+
+	/// Convert a declared task whose completion dates are days in `timezone`.
+	fn parse_task_in(
+		&self,
+		spec: &TaskSpec,
+		timezone: Timezone,
+	) -> Result<Task, StructuredParseError> {
+		let (id_kind, task_id) = parse_task_reference(&spec.id)
+			.ok_or_else(|| StructuredParseError::InvalidTaskId { id: spec.id.clone() })?;
+		let kind = spec.kind.unwrap_or(id_kind);
+		let in_task = || StructuredParseError::in_task(spec.id.as_str());
+
+		// Milestones need no end, and take no time if they declare one
+		let ends = spec.ends.as_deref().or(kind.is_milestone().then_some("0 days"));
+		let range = self
+			.range_parser
+			.parse(spec.starts.as_deref(), ends, &task_id)
+			.map_err(in_task())?;
+		if kind.is_milestone() && !range.end.duration().0.is_zero() {
+			return Err(in_task()(MarkdownParseError::MilestoneDuration {
+				id: spec.id.clone(),
+				ends: ends.unwrap_or_default().to_string(),
+			}));
+		}
+
+		let mut depends_on = BTreeSet::new();
+		let mut subtask_dependencies = BTreeSet::new();
+		for reference in &spec.depends_on {
+			match self.parse_dependency(reference) {
+				Some((dependency, None)) => {
+					depends_on.insert(dependency);
+				}
+				Some((dependency, Some(subtask))) => {
+					subtask_dependencies.insert(SubtaskDependency::new(dependency, subtask));
+				}
+				None => {
+					return Err(in_task()(MarkdownParseError::InvalidDependencyReference {
+						reference: reference.clone(),
+					}))
+				}
+			}
+		}
+
+		let leads = spec
+			.leads
+			.iter()
+			.map(|lead| self.parse_lead(lead))
+			.collect::<Result<Vec<Lead>, _>>()
+			.map_err(in_task())?;
+		let mut subtask_ids = HashSet::new();
+		let mut subtasks = BTreeSet::new();
+		for (index, subtask) in spec.subtasks.iter().enumerate() {
+			let subtask = self
+				.parse_subtask(subtask, task_id, index, leads.first(), timezone)
+				.map_err(in_task())?;
+			if !subtask_ids.insert(*subtask.subtask().id()) {
+				return Err(StructuredParseError::DuplicateSubtaskId {
+					id: format!("{}.{}", spec.id, subtask.subtask().id().value()),
+				});
+			}
+			subtasks.insert(subtask);
+		}
+
+		let summary = spec.summary.as_deref().unwrap_or_default().trim();
+		let title = Title::new(spec.title.clone())
+			.with_markdown(self.rich_text_parser.parse_inline(&spec.title));
+
+		Ok(Task::new(
+			task_id,
+			title,
+			depends_on,
+			subtasks,
+			Summary::new(summary.to_string()).with_markdown(self.rich_text_parser.parse(summary)),
+			range,
+		)
+		.with_subtask_dependencies(subtask_dependencies)
+		.with_kind(kind)
+		.with_leads(leads)
+		.with_labels(spec.labels.iter().map(String::as_str)))
+	}
+
+	/// Parse a dependency on a task, e.g. `T1`, or on one of its subtasks, e.g. `T1.3`.
+	fn parse_dependency(&self, reference: &str) -> Option<(TaskId, Option<SubtaskId>)> {
+		match reference.trim().split_once('.') {
+			Some((task, subtask)) => {
+				let (_, task_id) = parse_task_reference(task)?;
+				Some((task_id, Some(SubtaskId::new(subtask.parse().ok()?))))
+			}
+			None => parse_task_reference(reference).map(|(_, task_id)| (task_id, None)),
+		}
+	}
+
+	/// Convert a declared subtask of the task `task_id`; subtasks without a lead are led by
+	/// the first task lead.
+	fn parse_subtask(
+		&self,
+		spec: &SubtaskSpec,
+		task_id: TaskId,
+		index: usize,
+		task_lead: Option<&Lead>,
+		timezone: Timezone,
+	) -> Result<EmbeddedSubtask, MarkdownParseError> {
+		// The id of a subtask names its own task, e.g. `T3.1` for a subtask of `T3`
+		let id = match self.parse_dependency(&spec.id) {
+			Some((subtask_task, Some(id))) if subtask_task == task_id => id,
+			_ => return Err(MarkdownParseError::InvalidSubtaskId { id: spec.id.clone() }),
+		};
+
+		let status = match (&spec.completed, spec.status) {
+			(Some(completed), _) => {
+				let completed = DocumentParser::parse_date(completed)?;
+				Status::Complete(StatusDate::on(timezone.midnight(completed).inner()))
+			}
+			(None, SubtaskStatusSpec::Complete) => Status::Complete(StatusDate::undated()),
+			(None, SubtaskStatusSpec::InProgress) => Status::InProgress,
+			(None, SubtaskStatusSpec::Incomplete) => Status::Incomplete,
+		};
+
+		let lead = match &spec.lead {
+			Some(lead) => self.parse_lead(lead)?,
+			None => task_lead.cloned().unwrap_or_else(Lead::unknown),
+		};
+
+		let subtask = Subtask::new(
+			id,
+			Position::new(u8::try_from(index).unwrap_or(u8::MAX)),
+			SubtaskTitle { text: spec.title.clone() },
+			Content::new(spec.title.clone())
+				.with_markdown(self.rich_text_parser.parse(&spec.title)),
+			status,
+			lead,
+		);
+
+		Ok(EmbeddedSubtask::new(match &spec.ends {
+			Some(ends) => subtask.with_end(self.end_parser.parse(ends)?),
+			None => subtask,
+		}))
+	}
+
+	/// Parse a lead, which needs a name.
+	fn parse_lead(&self, spec: &LeadSpec) -> Result<Lead, MarkdownParseError> {
+		match spec {
+			LeadSpec::Text(text) => self
+				.lead_parser
+				.parse_lead(text)
+				.ok_or_else(|| MarkdownParseError::InvalidLead { lead: text.clone() }),
+			LeadSpec::Person { name, email } if name.trim().is_empty() => {
+				Err(MarkdownParseError::InvalidLead { lead: format!("{name} <{email}>") })
+			}
+			LeadSpec::Person { name, email } => Ok(Lead::new(name.clone(), email.clone())),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use roadline_util::task::Kind;
+
+	const YAML: &str = r#"
+title: Validation
+start: 2025-01-06
+timezone: Europe/Berlin
+tasks:
+  - id: T1
+    title: Draft
+    starts: T0 + 0 days
+    ends: 2 weeks
+    leads: ["[Ada](mailto:ada@example.com)"]
+    subtasks:
+      - id: T1.1
+        title: Outline
+        completed: 2025-01-08
+        ends: 3 days
+      - id: T1.2
+        title: Figures
+        status: in_progress
+        lead: { name: Grace, email: grace@example.com }
+  - id: T2
+    title: Review
+    starts: T1 + 2 weeks
+    ends: 2–3–6 days
+    depends_on: [T1]
+  - id: M3
+    title: Go
+    starts: T1 + 3 days
+    depends_on: [T1.1]
+"#;
+
+	const TOML: &str = r#"
+title = "Validation"
+start = 2025-01-06
+timezone = "Europe/Berlin"
+
+[[tasks]]
+id = "T1"
+title = "Draft"
+starts = "T0 + 0 days"
+ends = "2 weeks"
+leads = ["[Ada](mailto:ada@example.com)"]
+
+[[tasks.subtasks]]
+id = "T1.1"
+title = "Outline"
+completed = 2025-01-08
+ends = "3 days"
+
+[[tasks.subtasks]]
+id = "T1.2"
+title = "Figures"
+status = "in_progress"
+lead = { name = "Grace", email = "grace@example.com" }
+
+[[tasks]]
+id = "T2"
+title = "Review"
+starts = "T1 + 2 weeks"
+ends = "2–3–6 days"
+depends_on = ["T1"]
+
+[[tasks]]
+id = "M3"
+title = "Go"
+starts = "T1 + 3 days"
+depends_on = ["T1.1"]
+"#;
+
+	const JSON: &str = r#"{
+  "title": "Validation",
+  "start": "2025-01-06",
+  "timezone": "Europe/Berlin",
+  "tasks": [
+    {
+      "id": "T1",
+      "title": "Draft",
+      "starts": "T0 + 0 days",
+      "ends": "2 weeks",
+      "leads": ["[Ada](mailto:ada@example.com)"],
+      "subtasks": [
+        { "id": "T1.1", "title": "Outline", "completed": "2025-01-08", "ends": "3 days" },
+        {
+          "id": "T1.2",
+          "title": "Figures",
+          "status": "in_progress",
+          "lead": { "name": "Grace", "email": "grace@example.com" }
+        }
+      ]
+    },
+    {
+      "id": "T2",
+      "title": "Review",
+      "starts": "T1 + 2 weeks",
+      "ends": "2–3–6 days",
+      "depends_on": ["T1"]
+    },
+    { "id": "M3", "title": "Go", "starts": "T1 + 3 days", "depends_on": ["T1.1"] }
+  ]
+}"#;
+
+	#[test]
+	fn test_formats_agree() -> Result<(), anyhow::Error> {
+		let parser = StructuredParser::new();
+		let document = parser.parse_document(YAML, Format::Yaml)?;
+		assert_eq!(parser.parse_document(TOML, Format::Toml)?, document);
+		assert_eq!(parser.parse_document(JSON, Format::Json)?, document);
+
+		assert_eq!(document.title.as_deref(), Some("Validation"));
+		assert_eq!(document.timezone(), Some("Europe/Berlin"));
+		assert_eq!(document.tasks.len(), 3);
+
+		let t1 = &document.tasks[0];
+		assert_eq!(t1.leads()[0], Lead::new("Ada".to_string(), "ada@example.com".to_string()));
+		let subtasks = t1.subtasks();
+
+		// Completion dates are days in the timezone of the roadmap
+		let completed = match subtasks[0].status() {
+			Status::Complete(StatusDate { date }) => date.map(|date| date.to_rfc3339()),
+			_ => None,
+		};
+		assert_eq!(completed.as_deref(), Some("2025-01-07T23:00:00+00:00"));
+		assert!(subtasks[0].end().is_some());
+		assert_eq!(subtasks[0].lead().name(), "Ada");
+		assert_eq!(subtasks[1].status(), &Status::InProgress);
+		assert_eq!(subtasks[1].lead().name(), "Grace");
+
+		let t2 = &document.tasks[1];
+		assert!(t2.depends_on().contains(&TaskId::new(1)));
+		assert!(t2.range().estimate().is_some());
+
+		let m3 = &document.tasks[2];
+		assert_eq!(m3.kind(), Kind::Milestone);
+		assert!(m3
+			.subtask_dependencies()
+			.contains(&SubtaskDependency::new(TaskId::new(1), SubtaskId::new(1))));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_and_build() -> Result<(), anyhow::Error> {
+		let roadline = StructuredParser::new().parse_and_build(YAML, Format::Yaml)?;
+
+		// Days start at midnight in Berlin, an hour before midnight UTC
+		let t2 = roadline
+			.range_algebra()
+			.span(&TaskId::new(2))
+			.map(|span| span.start.inner().inner().to_rfc3339());
+		assert_eq!(t2.as_deref(), Some("2025-01-19T23:00:00+00:00"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_errors_name_the_task() -> Result<(), anyhow::Error> {
+		let parser = StructuredParser::new();
+
+		let error = parser
+			.parse_tasks(
+				"tasks:\n  - id: T1\n    title: Draft\n    starts: T0 + 0 days\n    ends: soon\n",
+				Format::Yaml,
+			)
+			.err();
+		assert!(matches!(error, Some(StructuredParseError::Task { ref id, .. }) if id == "T1"));
+
+		let duplicate = "[[tasks]]\nid = \"T1\"\ntitle = \"A\"\nstarts = \"T0 + 0 days\"\nends = \"1 day\"\n\n[[tasks]]\nid = \"M1\"\ntitle = \"B\"\nstarts = \"T1 + 1 day\"\n";
+		assert!(matches!(
+			parser.parse_tasks(duplicate, Format::Toml),
+			Err(StructuredParseError::DuplicateTaskId { .. })
+		));
+
+		assert!(matches!(
+			parser.parse_tasks(r#"{"tasks": [{"id": "X1", "title": "A"}]}"#, Format::Json),
+			Err(StructuredParseError::InvalidTaskId { .. })
+		));
+		// Subtasks are named after their own task, once each
+		let subtasks = |first: &str, second: &str| {
+			format!(
+				"tasks:\n  - id: T1\n    title: Draft\n    starts: T0 + 0 days\n    ends: 1 day\n    subtasks:\n      - {{ id: {first}, title: A }}\n      - {{ id: {second}, title: B }}\n"
+			)
+		};
+		assert!(parser.parse_tasks(&subtasks("T1.1", "T1.2"), Format::Yaml).is_ok());
+		assert!(matches!(
+			parser.parse_tasks(&subtasks("T1.1", "T2.2"), Format::Yaml),
+			Err(StructuredParseError::Task {
+				source: MarkdownParseError::InvalidSubtaskId { .. },
+				..
+			})
+		));
+		assert!(matches!(
+			parser.parse_tasks(&subtasks("T1.1", "T1.1"), Format::Yaml),
+			Err(StructuredParseError::DuplicateSubtaskId { ref id }) if id == "T1.1"
+		));
+
+		// Leads need a name
+		let leads = |leads: &str| {
+			format!("tasks:\n  - id: T1\n    title: Draft\n    starts: T0 + 0 days\n    ends: 1 day\n    leads: {leads}\n")
+		};
+		assert!(matches!(
+			parser.parse_tasks(&leads(r#"["Ada", "**"]"#), Format::Yaml),
+			Err(StructuredParseError::Task {
+				ref id,
+				source: MarkdownParseError::InvalidLead { ref lead },
+			}) if id == "T1" && lead == "**"
+		));
+		assert!(matches!(
+			parser.parse_tasks(&leads(r#"[{ name: "", email: ada@example.com }]"#), Format::Yaml),
+			Err(StructuredParseError::Task { source: MarkdownParseError::InvalidLead { .. }, .. })
+		));
+
+		// Errors in the shape of a roadmap point at where they occurred
+		let error = parser.parse_tasks("tasks:\n  - id: T1\n    titel: Draft\n", Format::Yaml);
+		assert!(matches!(error, Err(StructuredParseError::Yaml { .. })));
+		assert!(error.err().is_some_and(|error| error.to_string().contains("line 3 column 5")));
+		let error = parser.parse_tasks("[[tasks]]\nid = \"T1\"\ntitle = 3\n", Format::Toml);
+		assert!(matches!(error, Err(StructuredParseError::Toml { .. })));
+		assert!(error.err().is_some_and(|error| error.to_string().contains("line 3")));
+
+		assert!(matches!(
+			Format::from_path("roadmap.md"),
+			Err(StructuredParseError::UnknownFormat { .. })
+		));
+		assert_eq!(Format::from_path("roadmap.yml")?, Format::Yaml);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,107 @@
+//! The shape of a declarative roadmap.
+//!
+//! Fields hold the same expressions as the fields of a markdown roadmap, e.g.
+//! `starts: T1 + 2 weeks` or `ends: 2–3–6 weeks`, and are parsed with the same grammar.
+
+use roadline_util::task::Kind;
+use serde::{Deserialize, Deserializer};
+
+/// A declarative roadmap.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoadmapSpec {
+	/// The title of the roadmap.
+	pub title: Option<String>,
+	/// The date the roadmap starts, e.g. `2025-01-06`.
+	#[serde(default, deserialize_with = "date")]
+	pub start: Option<String>,
+	/// The IANA timezone the roadmap is planned in, e.g. `Europe/Berlin`.
+	pub timezone: Option<String>,
+	/// The authors of the roadmap.
+	#[serde(default)]
+	pub authors: Vec<LeadSpec>,
+	/// The tasks of the roadmap.
+	#[serde(default)]
+	pub tasks: Vec<TaskSpec>,
+}
+
+/// A task of a declarative roadmap.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskSpec {
+	/// The id of the task, e.g. `T3`, or `M3` for a milestone.
+	pub id: String,
+	pub title: String,
+	/// Overrides the kind given by the id.
+	pub kind: Option<Kind>,
+	/// When the task starts, e.g. `T1 + 2 weeks` or `T0 + 0 days` for a root task.
+	pub starts: Option<String>,
+	/// How long the task takes, e.g. `1 month`; milestones need none.
+	pub ends: Option<String>,
+	/// The tasks, e.g. `T1`, or subtasks, e.g. `T1.3`, the task depends on.
+	#[serde(default)]
+	pub depends_on: Vec<String>,
+	#[serde(default)]
+	pub leads: Vec<LeadSpec>,
+	pub summary: Option<String>,
+	#[serde(default)]
+	pub labels: Vec<String>,
+	#[serde(default)]
+	pub subtasks: Vec<SubtaskSpec>,
+}
+
+/// A subtask of a task.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubtaskSpec {
+	/// The id of the subtask, e.g. `T3.1`.
+	pub id: String,
+	pub title: String,
+	#[serde(default)]
+	pub status: SubtaskStatusSpec,
+	/// The date the subtask was completed, e.g. `2025-02-14`, which makes it complete.
+	#[serde(default, deserialize_with = "date")]
+	pub completed: Option<String>,
+	/// The lead of the subtask, the first lead of its task if not given.
+	pub lead: Option<LeadSpec>,
+	/// When the subtask ends, as an offset from the start of its task, e.g. `2 weeks`.
+	pub ends: Option<String>,
+}
+
+/// The status of a subtask.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtaskStatusSpec {
+	#[default]
+	Incomplete,
+	InProgress,
+	Complete,
+}
+
+/// A person, written as `[Name](mailto:email)` like in markdown, as a plain name, or as
+/// a name and an email.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum LeadSpec {
+	Text(String),
+	Person {
+		name: String,
+		#[serde(default)]
+		email: String,
+	},
+}
+
+/// Read a date written as a string, or as a TOML date such as `start = 2025-01-06`.
+fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+	#[derive(Deserialize)]
+	#[serde(untagged)]
+	enum Date {
+		Text(String),
+		Toml(toml::value::Datetime),
+	}
+
+	Ok(Option::<Date>::deserialize(deserializer)?.map(|date| match date {
+		Date::Text(text) => text,
+		Date::Toml(datetime) => datetime.to_string(),
+	}))
+}
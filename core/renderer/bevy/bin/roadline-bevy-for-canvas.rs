@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use roadline_bevy_renderer::{RoadlineRenderConfig, RoadlineRenderer};
+use roadline_bevy_renderer::{MilestoneShape, RoadlineRenderConfig, RoadlineRenderer};
 
 // Import test utilities for the canvas target
 use roadline_bevy_renderer::test_utils::*;
@@ -21,8 +21,11 @@ fn main() -> Result<(), anyhow::Error> {
 		milestone_color: Color::srgb(0.2, 0.8, 1.0), // Bright cyan milestones
 		edge_color: Color::srgb(0.9, 0.9, 0.9), // Light gray edges
 		milestone_radius: 12.0,   // Moderate milestone size
+		milestone_shape: MilestoneShape::Diamond, // Diamond milestones
+		baseline_color: Color::srgba(0.5, 0.5, 0.5, 0.3), // Faint baseline ghost bars
 		edge_thickness: 3.0,      // Moderate edge thickness
 		background_color: Color::srgb(1.0, 1.0, 1.0), // White background
+		shown_fields: vec!["Priority".to_string()], // Priorities after titles
 	};
 
 	let renderer = RoadlineRenderer::new().with_config(config);
@@ -1,5 +1,9 @@
+pub mod baseline;
 pub mod dependency;
+pub mod milestone;
 pub mod task;
 
+pub use baseline::{BaselineGhostMarker, BaselineGhostSpawner};
 pub use dependency::DependencyBundle;
+pub use milestone::{MilestoneShape, MilestoneSpawner};
 pub use task::TaskSpawner;
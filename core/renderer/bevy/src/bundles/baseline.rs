@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use bevy::render::mesh::Mesh2d;
+use bevy::render::view::RenderLayers;
+use bevy::sprite::{ColorMaterial, MeshMaterial2d};
+use roadline_util::task::Id as TaskId;
+
+/// Marks the ghost bar of a task, showing where the baseline had it
+#[derive(Debug, Component)]
+pub struct BaselineGhostMarker {
+	pub task_id: TaskId,
+}
+
+/// Helper struct for spawning a baseline ghost bar behind a task
+#[derive(Debug, Clone)]
+pub struct BaselineGhostSpawner {
+	pub task_id: TaskId,
+	pub position: Vec3,
+	pub size: Vec2,
+	pub color: Color,
+}
+
+impl BaselineGhostSpawner {
+	pub fn new(task_id: TaskId, position: Vec3, size: Vec2, color: Color) -> Self {
+		Self { task_id, position, size, color }
+	}
+
+	pub fn spawn(
+		self,
+		commands: &mut Commands,
+		meshes: &mut ResMut<Assets<Mesh>>,
+		materials: &mut ResMut<Assets<ColorMaterial>>,
+	) -> Entity {
+		let mesh = meshes.add(Rectangle::new(self.size.x, self.size.y));
+		let material = materials.add(ColorMaterial::from(self.color));
+
+		commands
+			.spawn((
+				BaselineGhostMarker { task_id: self.task_id },
+				Mesh2d(mesh),
+				MeshMaterial2d(material),
+				Transform::from_translation(self.position),
+				Visibility::Visible,
+				RenderLayers::layer(2),
+			))
+			.id()
+	}
+}
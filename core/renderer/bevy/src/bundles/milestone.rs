@@ -0,0 +1,66 @@
+use crate::components::{RenderState, Task};
+use bevy::prelude::*;
+use bevy::render::mesh::Mesh2d;
+use bevy::render::view::RenderLayers;
+use bevy::sprite::{ColorMaterial, MeshMaterial2d};
+use roadline_util::task::Id as TaskId;
+
+/// The shape milestones are drawn with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MilestoneShape {
+	#[default]
+	Diamond,
+	Circle,
+}
+
+#[derive(Debug, Component)]
+pub struct MilestoneMarker;
+
+/// Helper struct for spawning a milestone, which is drawn as a point rather than a box
+#[derive(Debug, Clone)]
+pub struct MilestoneSpawner {
+	pub task_id: TaskId,
+	pub position: Vec3,
+	pub radius: f32,
+	pub color: Color,
+	pub shape: MilestoneShape,
+}
+
+impl MilestoneSpawner {
+	pub fn new(task_id: TaskId, position: Vec3, radius: f32, color: Color) -> Self {
+		Self { task_id, position, radius, color, shape: MilestoneShape::default() }
+	}
+
+	pub fn with_shape(mut self, shape: MilestoneShape) -> Self {
+		self.shape = shape;
+		self
+	}
+
+	pub fn spawn(
+		self,
+		commands: &mut Commands,
+		meshes: &mut ResMut<Assets<Mesh>>,
+		materials: &mut ResMut<Assets<ColorMaterial>>,
+	) -> Entity {
+		let mesh = match self.shape {
+			MilestoneShape::Diamond => {
+				meshes.add(Rhombus::new(self.radius * 2.0, self.radius * 2.0))
+			}
+			MilestoneShape::Circle => meshes.add(Circle::new(self.radius)),
+		};
+		let material = materials.add(ColorMaterial::from(self.color));
+
+		commands
+			.spawn((
+				MilestoneMarker,
+				Task::new(self.task_id),
+				RenderState::new(),
+				Mesh2d(mesh),
+				MeshMaterial2d(material),
+				Transform::from_translation(self.position),
+				Visibility::Visible,
+				RenderLayers::layer(2),
+			))
+			.id()
+	}
+}
@@ -18,6 +18,7 @@ use bevy_ui_anchor::AnchorUiPlugin;
 
 use crate::resources::{Roadline, SelectionResource};
 
+pub use bundles::MilestoneShape;
 pub use roadline_renderer::RoadlineRenderer;
 
 /// Marker component for the UI camera
@@ -111,6 +112,7 @@ impl RoadlinePlugin {
 				Update,
 				(
 					systems::TaskSpawningSystem::default().build(),
+					systems::BaselineSpawningSystem::default().build(),
 					systems::DependencySpawningSystem::default().build(),
 					systems::DependencyHoverSystem::default().build(),
 					task_cursor_interaction_system.build(),
@@ -528,8 +530,14 @@ pub struct RoadlineRenderConfig {
 	pub edge_color: Color,
 	/// Milestone radius in pixels
 	pub milestone_radius: f32,
+	/// Shape milestones are drawn with
+	pub milestone_shape: MilestoneShape,
+	/// Color of the baseline ghost bars drawn behind tasks
+	pub baseline_color: Color,
 	/// Edge thickness in pixels
 	pub edge_thickness: f32,
+	/// Custom fields of tasks shown after their titles, e.g. `Priority`
+	pub shown_fields: Vec<String>,
 }
 
 impl Default for RoadlineRenderConfig {
@@ -540,7 +548,10 @@ impl Default for RoadlineRenderConfig {
 			milestone_color: Color::srgb(0.2, 0.7, 1.0),
 			edge_color: Color::srgb(0.8, 0.8, 0.8),
 			milestone_radius: 8.0,
+			milestone_shape: MilestoneShape::Diamond,
+			baseline_color: Color::srgba(0.5, 0.5, 0.5, 0.3),
 			edge_thickness: 20.423,
+			shown_fields: Vec::new(),
 		}
 	}
 }
@@ -1,3 +1,5 @@
+pub mod baseline;
+pub use baseline::*;
 pub mod selection;
 pub use selection::*;
 pub mod roadline;
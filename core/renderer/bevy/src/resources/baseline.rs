@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use roadline_representation_core::baseline::Baseline as CoreBaseline;
+
+/// Bevy Resource wrapper for a core Baseline, drawn as ghost bars behind the current tasks
+#[derive(Resource, Debug, Clone)]
+pub struct Baseline {
+	inner: CoreBaseline,
+}
+
+impl Baseline {
+	pub fn new(baseline: CoreBaseline) -> Self {
+		Self { inner: baseline }
+	}
+
+	/// Get a reference to the inner baseline
+	pub fn inner(&self) -> &CoreBaseline {
+		&self.inner
+	}
+
+	/// Consume the wrapper and return the inner baseline
+	pub fn into_inner(self) -> CoreBaseline {
+		self.inner
+	}
+}
+
+impl From<CoreBaseline> for Baseline {
+	fn from(baseline: CoreBaseline) -> Self {
+		Self::new(baseline)
+	}
+}
+
+impl std::ops::Deref for Baseline {
+	type Target = CoreBaseline;
+
+	fn deref(&self) -> &Self::Target {
+		&self.inner
+	}
+}
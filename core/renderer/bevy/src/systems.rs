@@ -1,7 +1,9 @@
+pub mod baseline;
 pub mod dependency;
 pub mod task;
 pub mod task_cursor_interaction;
 
+pub use baseline::BaselineSpawningSystem;
 pub use dependency::{DependencyHoverSystem, DependencySpawningSystem};
 pub use task::{cursor_interaction::TaskCursorInteractionSystem, TaskSpawningSystem};
 pub use task_cursor_interaction::task_cursor_interaction_system;
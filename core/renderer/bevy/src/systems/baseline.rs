@@ -0,0 +1,157 @@
+use crate::bundles::{BaselineGhostMarker, BaselineGhostSpawner};
+use crate::resources::{Baseline, RenderUpdateEvent, Roadline};
+use crate::RoadlineRenderConfig;
+use bevy::prelude::*;
+use bevy::render::mesh::Mesh;
+use bevy::sprite::ColorMaterial;
+
+/// Configuration for the system drawing baseline ghost bars
+///
+/// The pixel scale must match the [`TaskSpawningSystem`](crate::systems::TaskSpawningSystem),
+/// so that ghost bars line up with the tasks they are drawn behind.
+#[derive(Debug, Clone, Resource)]
+pub struct BaselineSpawningSystem {
+	pub pixels_per_x_unit: f32,
+	pub pixels_per_y_unit: f32,
+}
+
+impl Default for BaselineSpawningSystem {
+	fn default() -> Self {
+		Self { pixels_per_x_unit: 10.0, pixels_per_y_unit: 75.0 }
+	}
+}
+
+impl BaselineSpawningSystem {
+	/// Builds a system function for updating baseline ghost bars
+	pub fn build(
+		self,
+	) -> impl FnMut(
+		Commands,
+		EventReader<RenderUpdateEvent>,
+		Option<Res<Roadline>>,
+		Option<Res<Baseline>>,
+		Res<RoadlineRenderConfig>,
+		Query<Entity, With<BaselineGhostMarker>>,
+		ResMut<Assets<Mesh>>,
+		ResMut<Assets<ColorMaterial>>,
+	) {
+		move |mut commands: Commands,
+		      render_events: EventReader<RenderUpdateEvent>,
+		      roadline: Option<Res<Roadline>>,
+		      baseline: Option<Res<Baseline>>,
+		      config: Res<RoadlineRenderConfig>,
+		      existing_ghosts: Query<Entity, With<BaselineGhostMarker>>,
+		      mut meshes: ResMut<Assets<Mesh>>,
+		      mut materials: ResMut<Assets<ColorMaterial>>| {
+			if render_events.is_empty() {
+				return;
+			}
+
+			for entity in existing_ghosts.iter() {
+				commands.entity(entity).despawn();
+			}
+
+			let (Some(roadline), Some(baseline)) = (roadline, baseline) else {
+				return;
+			};
+			self.spawn_ghosts(
+				&mut commands,
+				&roadline,
+				&baseline,
+				&config,
+				&mut meshes,
+				&mut materials,
+			);
+		}
+	}
+
+	/// Spawns a ghost bar behind each task which is also in the baseline.
+	///
+	/// The bar of a task maps its planned span onto pixels, and the ghost bar maps the
+	/// baseline span of the task with the same scale.
+	pub fn spawn_ghosts(
+		&self,
+		commands: &mut Commands,
+		roadline: &Roadline,
+		baseline: &Baseline,
+		config: &RoadlineRenderConfig,
+		meshes: &mut ResMut<Assets<Mesh>>,
+		materials: &mut ResMut<Assets<ColorMaterial>>,
+	) {
+		for (task_id, start_x, start_y, end_x, end_y) in roadline.task_rectangles() {
+			let (Some(span), Some(baseline_span)) =
+				(roadline.range_algebra().span(task_id), baseline.span(task_id))
+			else {
+				continue;
+			};
+
+			// Milestones have no duration to scale by
+			let seconds = (span.end.inner().inner() - span.start.inner().inner()).num_seconds();
+			if seconds <= 0 {
+				continue;
+			}
+
+			let pixel_x = start_x as f32 * self.pixels_per_x_unit;
+			let pixel_y = start_y as f32 * self.pixels_per_y_unit;
+			let width = (end_x - start_x) as f32 * self.pixels_per_x_unit;
+			let height = (end_y - start_y) as f32 * self.pixels_per_y_unit;
+			let pixels_per_second = width / seconds as f32;
+
+			let offset = (baseline_span.start.inner().inner() - span.start.inner().inner())
+				.num_seconds() as f32;
+			let length = (baseline_span.end.inner().inner() - baseline_span.start.inner().inner())
+				.num_seconds() as f32;
+			let ghost_x = pixel_x + offset * pixels_per_second;
+			let ghost_width = length * pixels_per_second;
+
+			// Positioned by center, like tasks, and drawn behind them
+			BaselineGhostSpawner::new(
+				*task_id,
+				Vec3::new(ghost_x + ghost_width / 2.0, pixel_y, -1.0),
+				Vec2::new(ghost_width, height),
+				config.baseline_color,
+			)
+			.spawn(commands, meshes, materials);
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::bundles::task::tests::utils::setup_task_test_app;
+	use crate::test_utils::create_test_roadline;
+	use roadline_representation_core::baseline::Baseline as CoreBaseline;
+
+	fn send_render_update_event(app: &mut App) {
+		let mut events = app.world_mut().resource_mut::<Events<RenderUpdateEvent>>();
+		events.send(RenderUpdateEvent);
+	}
+
+	#[test]
+	fn test_baseline_ghost_bars() -> Result<(), Box<dyn std::error::Error>> {
+		let mut app = setup_task_test_app();
+		let roadline = create_test_roadline()?;
+		let baseline = CoreBaseline::capture("v1", &roadline);
+		let task_count = roadline.task_count();
+
+		app.insert_resource(Roadline::from(roadline));
+		app.insert_resource(RoadlineRenderConfig::default());
+		app.add_event::<RenderUpdateEvent>();
+		app.add_systems(Update, BaselineSpawningSystem::default().build());
+
+		// Without a baseline nothing is drawn
+		send_render_update_event(&mut app);
+		app.update();
+		let mut ghost_query = app.world_mut().query::<&BaselineGhostMarker>();
+		assert_eq!(ghost_query.iter(app.world()).count(), 0);
+
+		// An unchanged roadline gets a ghost bar behind every task
+		app.insert_resource(Baseline::from(baseline));
+		send_render_update_event(&mut app);
+		app.update();
+		assert_eq!(ghost_query.iter(app.world()).count(), task_count);
+
+		Ok(())
+	}
+}
@@ -1,10 +1,13 @@
 use crate::bundles::task::TaskSpawner;
+use crate::bundles::MilestoneSpawner;
 use crate::components::Task;
 use crate::resources::{RenderUpdateEvent, Roadline};
 use crate::RoadlineRenderConfig;
 use bevy::prelude::*;
 use bevy::render::mesh::Mesh;
 use bevy::sprite::ColorMaterial;
+use roadline_util::task::subtask::Status;
+use roadline_util::task::ProgressStatus;
 
 /// Configuration for task spawning systems
 #[derive(Debug, Clone, Resource)]
@@ -35,7 +38,7 @@ impl TaskSpawningSystem {
 		move |mut commands: Commands,
 		      render_events: EventReader<RenderUpdateEvent>,
 		      reified_opt: Option<Res<Roadline>>,
-		      _config: Res<RoadlineRenderConfig>,
+		      config: Res<RoadlineRenderConfig>,
 		      existing_tasks: Query<Entity, With<Task>>,
 		      mut meshes: ResMut<Assets<Mesh>>,
 		      mut materials: ResMut<Assets<ColorMaterial>>| {
@@ -43,6 +46,7 @@ impl TaskSpawningSystem {
 				&mut commands,
 				&render_events,
 				&reified_opt,
+				&config,
 				&existing_tasks,
 				&mut meshes,
 				&mut materials,
@@ -56,6 +60,7 @@ impl TaskSpawningSystem {
 		commands: &mut Commands,
 		render_events: &EventReader<RenderUpdateEvent>,
 		reified_opt: &Option<Res<Roadline>>,
+		config: &RoadlineRenderConfig,
 		existing_tasks: &Query<Entity, With<Task>>,
 		meshes: &mut ResMut<Assets<Mesh>>,
 		materials: &mut ResMut<Assets<ColorMaterial>>,
@@ -113,17 +118,38 @@ impl TaskSpawningSystem {
 				continue;
 			}
 			let task = task.unwrap();
-			let title = task.title();
+
+			// Milestones are points, drawn where they fall rather than as boxes
+			if task.is_milestone() {
+				MilestoneSpawner::new(
+					*task_id,
+					Vec3::new(pixel_x, pixel_y, 0.0),
+					config.milestone_radius,
+					config.milestone_color,
+				)
+				.with_shape(config.milestone_shape)
+				.spawn(commands, meshes, materials);
+				continue;
+			}
+
+			let title = task_label(task, &config.shown_fields);
+
+			// A task is still due until its forecast end has passed
+			let in_future = reified
+				.range_algebra()
+				.forecast(task_id)
+				.is_none_or(|span| span.end.inner().inner() > chrono::Utc::now());
+			let (completed, total) = task_completion(task);
 
 			// Use TaskSpawner to spawn all task entities
 			let task_spawner = TaskSpawner::new(
 				*task_id,
 				Vec3::new(left_justified_x, pixel_y, 0.0),
 				Vec2::new(sprite_width, sprite_height),
-				title.text.clone(),
-				false,
-				3,
-				3,
+				title,
+				in_future,
+				completed,
+				total,
 			);
 
 			task_spawner.spawn(commands, meshes, materials);
@@ -131,6 +157,41 @@ impl TaskSpawningSystem {
 	}
 }
 
+/// The text shown for a task: its title, followed by the custom fields the config shows.
+fn task_label(task: &roadline_util::task::Task, shown_fields: &[String]) -> String {
+	let title = task.title().text.clone();
+	let fields: Vec<String> = shown_fields
+		.iter()
+		.filter_map(|name| task.field(name).map(|value| format!("{name}: {value}")))
+		.collect();
+
+	if fields.is_empty() {
+		title
+	} else {
+		format!("{title} · {}", fields.join(" · "))
+	}
+}
+
+/// The completed and total counts shown in the status of a task.
+///
+/// A task with subtasks counts its completed subtasks, and any other task its percent complete.
+/// A finished task is complete either way.
+fn task_completion(task: &roadline_util::task::Task) -> (u32, u32) {
+	let subtasks = task.subtasks();
+	if subtasks.is_empty() {
+		return (u32::from(task.progress().percent_complete()), 100);
+	}
+
+	let total = subtasks.len() as u32;
+	if task.progress().status() == ProgressStatus::Complete {
+		return (total, total);
+	}
+
+	let completed =
+		subtasks.iter().filter(|subtask| matches!(subtask.status, Status::Complete(_))).count();
+	(completed as u32, total)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -498,4 +559,71 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_spawning_system_draws_milestones() -> Result<(), Box<dyn std::error::Error>> {
+		use crate::bundles::milestone::MilestoneMarker;
+		use roadline_representation_core::roadline::RoadlineBuilder;
+		use roadline_util::task::{Kind, Task as CoreTask};
+		use std::time::Duration as StdDuration;
+
+		// A task followed by a milestone
+		let task1 = CoreTask::test_from_id(1)?
+			.for_standard_duration(StdDuration::from_secs(30 * 24 * 60 * 60));
+		let milestone2 = CoreTask::test_from_id(2)?
+			.after(&task1)
+			.with_kind(Kind::Milestone)
+			.with_dependencies([1]);
+		let roadline = RoadlineBuilder::new().task(task1)?.task(milestone2)?.build()?;
+
+		let mut app = setup_spawning_test_app();
+		app.insert_resource(Roadline::from(roadline));
+		app.add_systems(Update, TaskSpawningSystem::default().build());
+		send_render_update_event(&mut app);
+		app.update();
+
+		// Both are tasks, but only the milestone is drawn as a marker without a box
+		let mut task_query = app.world_mut().query::<&Task>();
+		assert_eq!(task_query.iter(app.world()).count(), 2);
+
+		let mut milestone_query = app.world_mut().query_filtered::<&Task, With<MilestoneMarker>>();
+		let milestones: Vec<_> =
+			milestone_query.iter(app.world()).map(|task| task.task_id).collect();
+		assert_eq!(milestones, vec![TaskId::new(2)]);
+
+		let mut size_query = app.world_mut().query::<&TaskSize>();
+		assert_eq!(size_query.iter(app.world()).count(), 1);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_task_label() -> Result<(), Box<dyn std::error::Error>> {
+		use roadline_util::task::FieldValue;
+
+		let task = roadline_util::task::Task::test_from_id(1)?
+			.with_field("Priority", FieldValue::Text("P1".to_string()));
+		let title = task.title().text.clone();
+		assert_eq!(task_label(&task, &[]), title);
+		assert_eq!(task_label(&task, &["Owner".to_string()]), title);
+		assert_eq!(task_label(&task, &["Priority".to_string()]), format!("{title} · Priority: P1"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_task_completion() -> Result<(), Box<dyn std::error::Error>> {
+		use roadline_util::task::Progress;
+
+		let task = roadline_util::task::Task::test_from_id(1)?;
+		assert_eq!(task_completion(&task), (0, 100));
+
+		let task = task.with_progress(Progress::new().with_percent_complete(40));
+		assert_eq!(task_completion(&task), (40, 100));
+
+		let task = task.with_progress(Progress::new().with_finished(chrono::Utc::now()));
+		assert_eq!(task_completion(&task), (100, 100));
+
+		Ok(())
+	}
 }
@@ -0,0 +1,222 @@
+//! Baselines: frozen snapshots of a roadline to measure later versions against.
+//!
+//! A [`Baseline`] keeps the planned span of every task and the set of dependencies. It can be
+//! saved to and loaded from JSON, and compared against a current [`Roadline`] to find how far
+//! each task has drifted and which scope was added or removed since.
+
+use crate::range_algebra::{Span, Variance};
+use crate::roadline::Roadline;
+use roadline_util::task::Id as TaskId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use thiserror::Error;
+
+/// Error types for Baseline operations
+#[derive(Error, Debug)]
+pub enum BaselineError {
+	#[error("Failed to read or write baseline: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("Invalid baseline JSON: {0}")]
+	Json(#[from] serde_json::Error),
+}
+
+/// A dependency of one task on another, as `(task, dependency)`.
+pub type DependencyPair = (TaskId, TaskId);
+
+/// A named snapshot of the planned spans and dependencies of a roadline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+	/// The name of the baseline, e.g. "Q3 plan".
+	name: String,
+	/// The planned span of each task.
+	spans: BTreeMap<TaskId, Span>,
+	/// The dependencies between tasks.
+	dependencies: BTreeSet<DependencyPair>,
+}
+
+impl Baseline {
+	/// Captures the planned spans and dependencies of a roadline.
+	pub fn capture(name: impl Into<String>, roadline: &Roadline) -> Self {
+		let spans = roadline
+			.range_algebra()
+			.spans()
+			.iter()
+			.map(|(task_id, span)| (*task_id, *span))
+			.collect();
+
+		Self { name: name.into(), spans, dependencies: Self::dependencies_of(roadline) }
+	}
+
+	pub fn name(&self) -> &str {
+		&self.name
+	}
+
+	/// Get the planned spans of all tasks in the baseline.
+	pub fn spans(&self) -> &BTreeMap<TaskId, Span> {
+		&self.spans
+	}
+
+	/// Get the planned span of a specific task.
+	pub fn span(&self, task_id: &TaskId) -> Option<&Span> {
+		self.spans.get(task_id)
+	}
+
+	/// Get the dependencies in the baseline.
+	pub fn dependencies(&self) -> &BTreeSet<DependencyPair> {
+		&self.dependencies
+	}
+
+	/// Serializes the baseline to pretty-printed JSON.
+	pub fn to_json(&self) -> Result<String, BaselineError> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+
+	/// Deserializes a baseline from JSON.
+	pub fn from_json(json: &str) -> Result<Self, BaselineError> {
+		Ok(serde_json::from_str(json)?)
+	}
+
+	/// Saves the baseline as JSON to a file.
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<(), BaselineError> {
+		Ok(std::fs::write(path, self.to_json()?)?)
+	}
+
+	/// Loads a baseline from a JSON file.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, BaselineError> {
+		Self::from_json(&std::fs::read_to_string(path)?)
+	}
+
+	/// Compares a current roadline against the baseline.
+	///
+	/// Tasks are compared by their forecast spans, so that actual progress shows up as drift.
+	pub fn compare(&self, roadline: &Roadline) -> BaselineComparison {
+		let range_algebra = roadline.range_algebra();
+		let current: BTreeSet<TaskId> = range_algebra.task_ids().copied().collect();
+		let baseline: BTreeSet<TaskId> = self.spans.keys().copied().collect();
+
+		let variances = self
+			.spans
+			.iter()
+			.filter_map(|(task_id, planned)| {
+				let span = range_algebra.forecast(task_id).or(range_algebra.span(task_id))?;
+				Some((*task_id, Variance::between(planned, span)))
+			})
+			.collect();
+
+		let dependencies = Self::dependencies_of(roadline);
+
+		BaselineComparison {
+			variances,
+			added_tasks: current.difference(&baseline).copied().collect(),
+			removed_tasks: baseline.difference(&current).copied().collect(),
+			added_dependencies: dependencies.difference(&self.dependencies).copied().collect(),
+			removed_dependencies: self.dependencies.difference(&dependencies).copied().collect(),
+		}
+	}
+
+	/// The dependencies between the tasks of a roadline.
+	fn dependencies_of(roadline: &Roadline) -> BTreeSet<DependencyPair> {
+		let graph = roadline.graph();
+		graph
+			.task_ids()
+			.filter_map(|task_id| graph.task(task_id))
+			.flat_map(|task| task.depends_on().iter().map(|dependency| (*task.id(), *dependency)))
+			.collect()
+	}
+}
+
+/// How a current roadline differs from a baseline.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BaselineComparison {
+	/// The variance of each task in both the baseline and the roadline.
+	pub variances: BTreeMap<TaskId, Variance>,
+	/// Tasks which are new since the baseline.
+	pub added_tasks: BTreeSet<TaskId>,
+	/// Tasks of the baseline which are gone.
+	pub removed_tasks: BTreeSet<TaskId>,
+	/// Dependencies which are new since the baseline.
+	pub added_dependencies: BTreeSet<DependencyPair>,
+	/// Dependencies of the baseline which are gone.
+	pub removed_dependencies: BTreeSet<DependencyPair>,
+}
+
+impl BaselineComparison {
+	/// Get the variance of a specific task.
+	pub fn variance(&self, task_id: &TaskId) -> Option<&Variance> {
+		self.variances.get(task_id)
+	}
+
+	/// Whether the scope changed, i.e. tasks or dependencies were added or removed.
+	pub fn has_scope_change(&self) -> bool {
+		!(self.added_tasks.is_empty()
+			&& self.removed_tasks.is_empty()
+			&& self.added_dependencies.is_empty()
+			&& self.removed_dependencies.is_empty())
+	}
+
+	/// Tasks which finish later than in the baseline.
+	pub fn late_tasks(&self) -> impl Iterator<Item = &TaskId> {
+		self.variances
+			.iter()
+			.filter(|(_, variance)| variance.is_late())
+			.map(|(task_id, _)| task_id)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::roadline::RoadlineBuilder;
+	use roadline_util::task::Task;
+	use std::time::Duration as StdDuration;
+
+	fn days(n: u64) -> StdDuration {
+		StdDuration::from_secs(n * 24 * 60 * 60)
+	}
+
+	#[test]
+	fn test_baseline_round_trip_and_comparison() -> Result<(), anyhow::Error> {
+		let task1 = Task::test_from_id(1)?.for_standard_duration(days(10));
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(days(10))
+			.with_dependencies([1]);
+		let roadline =
+			RoadlineBuilder::start_of_epoch()?.task(task1.clone())?.task(task2)?.build()?;
+
+		let baseline = Baseline::from_json(&Baseline::capture("v1", &roadline).to_json()?)?;
+		assert_eq!(baseline.name(), "v1");
+		assert_eq!(baseline.spans().len(), 2);
+		assert!(baseline.dependencies().contains(&(TaskId::new(2), TaskId::new(1))));
+
+		// T1 now takes twice as long, T2 is gone and T3 follows T1
+		let task1 = Task::test_from_id(1)?.for_standard_duration(days(20));
+		let task3 = Task::test_from_id(3)?
+			.after(&task1)
+			.for_standard_duration(days(10))
+			.with_dependencies([1]);
+		let roadline = RoadlineBuilder::start_of_epoch()?.task(task1)?.task(task3)?.build()?;
+
+		let comparison = baseline.compare(&roadline);
+		let variance = comparison.variance(&TaskId::new(1)).ok_or(anyhow::anyhow!("no T1"))?;
+		assert_eq!(variance.start, chrono::Duration::zero());
+		assert_eq!(variance.finish, chrono::Duration::days(10));
+		assert_eq!(comparison.late_tasks().collect::<Vec<_>>(), vec![&TaskId::new(1)]);
+
+		assert!(comparison.has_scope_change());
+		assert_eq!(comparison.added_tasks, BTreeSet::from([TaskId::new(3)]));
+		assert_eq!(comparison.removed_tasks, BTreeSet::from([TaskId::new(2)]));
+		assert_eq!(
+			comparison.added_dependencies,
+			BTreeSet::from([(TaskId::new(3), TaskId::new(1))])
+		);
+		assert_eq!(
+			comparison.removed_dependencies,
+			BTreeSet::from([(TaskId::new(2), TaskId::new(1))])
+		);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,367 @@
+//! Working calendars, which resolve durations declared in working days.
+//!
+//! A [`Calendar`] knows which weekdays are worked and which dates are holidays. [`Calendars`]
+//! hold a default calendar and optional calendars per lead, and can be loaded from a simple
+//! file:
+//!
+//! ```text
+//! # The company calendar
+//! weekdays: Mon-Fri
+//! holidays: 2025-12-25, 2025-12-26..2026-01-01
+//!
+//! [Ada Lovelace]
+//! weekdays: Mon-Thu
+//! holidays: 2025-08-04..2025-08-15
+//! ```
+//!
+//! A lead's section starts from the default calendar above it, so it only lists what differs;
+//! its holidays add to the default ones.
+
+use crate::range_algebra::Date;
+use crate::timezone::Timezone;
+use chrono::{Datelike, Days, NaiveDate, Utc, Weekday};
+use roadline_util::task::Task;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use thiserror::Error;
+
+/// Error types for Calendar operations
+#[derive(Error, Debug)]
+pub enum CalendarError {
+	#[error("Failed to read calendar: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("Invalid calendar line {line}: {content}")]
+	InvalidLine { line: usize, content: String },
+	#[error("Invalid weekday: {name}")]
+	InvalidWeekday { name: String },
+	#[error("Invalid holiday date: {date}")]
+	InvalidDate { date: String },
+	#[error("Invalid calendar line {line}: holidays {range} end before they start")]
+	InvalidHolidayRange { line: usize, range: String },
+}
+
+/// The working weekdays and holidays of a team or person.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Calendar {
+	/// Whether each weekday is worked, from Monday to Sunday.
+	weekdays: [bool; 7],
+	/// Dates which are not worked, whatever their weekday.
+	holidays: BTreeSet<NaiveDate>,
+}
+
+impl Default for Calendar {
+	/// Monday to Friday, without holidays.
+	fn default() -> Self {
+		Self { weekdays: [true, true, true, true, true, false, false], holidays: BTreeSet::new() }
+	}
+}
+
+impl Calendar {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Constructs with the specified working weekdays.
+	pub fn with_weekdays(self, weekdays: impl IntoIterator<Item = Weekday>) -> Self {
+		let mut worked = [false; 7];
+		for weekday in weekdays {
+			worked[weekday.num_days_from_monday() as usize] = true;
+		}
+		Self { weekdays: worked, ..self }
+	}
+
+	/// Constructs with additional holidays.
+	pub fn with_holidays(mut self, holidays: impl IntoIterator<Item = NaiveDate>) -> Self {
+		self.holidays.extend(holidays);
+		self
+	}
+
+	/// Get the working weekdays, from Monday to Sunday.
+	pub fn weekdays(&self) -> impl Iterator<Item = Weekday> + '_ {
+		(0..7u8)
+			.filter(|index| self.weekdays[*index as usize])
+			.filter_map(|index| Weekday::try_from(index).ok())
+	}
+
+	pub fn holidays(&self) -> &BTreeSet<NaiveDate> {
+		&self.holidays
+	}
+
+	/// Whether a date is worked.
+	pub fn is_working_day(&self, date: NaiveDate) -> bool {
+		self.weekdays[date.weekday().num_days_from_monday() as usize]
+			&& !self.holidays.contains(&date)
+	}
+
+	/// Returns the date after a number of working days, counted from `start`.
+	///
+	/// The day of `start` counts if it is worked, and the result lies at the same time of day
+	/// after the last working day. A calendar without working weekdays counts every day.
+	pub fn add_workdays(&self, start: Date, workdays: u32) -> Date {
+		self.add_workdays_in(start, workdays, Timezone::utc())
+	}
+
+	/// Returns the date after a number of working days, counting the days of `timezone`.
+	///
+	/// See [`Calendar::add_workdays`]; the time of day is kept in local time across clock
+	/// changes.
+	pub fn add_workdays_in(&self, start: Date, workdays: u32, timezone: Timezone) -> Date {
+		let mut date = timezone.local(start);
+		let mut remaining = workdays;
+		let counts_every_day = !self.weekdays.contains(&true);
+
+		while remaining > 0 {
+			if counts_every_day || self.is_working_day(date.date_naive()) {
+				remaining -= 1;
+			}
+			date = match date.checked_add_days(Days::new(1)) {
+				Some(next) => next,
+				None => break,
+			};
+		}
+
+		Date::new(date.with_timezone(&Utc))
+	}
+
+	/// Returns the date a number of working days before `start`, counting the days of
+	/// `timezone`.
+	///
+	/// The inverse of [`Calendar::add_workdays_in`]: the day of `start` does not count, and
+	/// every worked day from the result up to `start` does.
+	pub fn subtract_workdays_in(&self, start: Date, workdays: u32, timezone: Timezone) -> Date {
+		let mut date = timezone.local(start);
+		let mut remaining = workdays;
+		let counts_every_day = !self.weekdays.contains(&true);
+
+		while remaining > 0 {
+			date = match date.checked_sub_days(Days::new(1)) {
+				Some(previous) => previous,
+				None => break,
+			};
+			if counts_every_day || self.is_working_day(date.date_naive()) {
+				remaining -= 1;
+			}
+		}
+
+		Date::new(date.with_timezone(&Utc))
+	}
+
+	/// Applies a `weekdays:` or `holidays:` line of a calendar file.
+	fn apply(&mut self, line: usize, key: &str, value: &str) -> Result<bool, CalendarError> {
+		match key.to_ascii_lowercase().as_str() {
+			"weekdays" | "workdays" => {
+				let weekdays =
+					value.split(',').map(Self::parse_weekdays).collect::<Result<Vec<_>, _>>()?;
+				*self = std::mem::take(self).with_weekdays(weekdays.into_iter().flatten());
+			}
+			"holidays" | "holiday" => {
+				for holidays in value.split(',').filter(|part| !part.trim().is_empty()) {
+					self.holidays.extend(Self::parse_holidays(line, holidays)?);
+				}
+			}
+			_ => return Ok(false),
+		}
+
+		Ok(true)
+	}
+
+	/// Parses a weekday or a range of weekdays, e.g. "Mon" or "Mon-Fri".
+	fn parse_weekdays(part: &str) -> Result<Vec<Weekday>, CalendarError> {
+		let parse = |name: &str| {
+			name.trim()
+				.parse::<Weekday>()
+				.map_err(|_| CalendarError::InvalidWeekday { name: name.trim().to_string() })
+		};
+
+		let Some((first, last)) = part.split_once('-') else {
+			return Ok(vec![parse(part)?]);
+		};
+		let (first, last) = (parse(first)?, parse(last)?);
+
+		let mut weekdays = vec![first];
+		let mut weekday = first;
+		while weekday != last {
+			weekday = weekday.succ();
+			weekdays.push(weekday);
+		}
+
+		Ok(weekdays)
+	}
+
+	/// Parses a holiday or an inclusive range of holidays, e.g. "2025-12-24..2025-12-26".
+	fn parse_holidays(line: usize, part: &str) -> Result<Vec<NaiveDate>, CalendarError> {
+		let parse = |date: &str| {
+			NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+				.map_err(|_| CalendarError::InvalidDate { date: date.trim().to_string() })
+		};
+
+		let Some((first, last)) = part.split_once("..") else {
+			return Ok(vec![parse(part)?]);
+		};
+		let (first, last) = (parse(first)?, parse(last)?);
+		if last < first {
+			return Err(CalendarError::InvalidHolidayRange {
+				line,
+				range: part.trim().to_string(),
+			});
+		}
+
+		Ok(first.iter_days().take_while(|date| *date <= last).collect())
+	}
+}
+
+/// A default working calendar and the calendars of individual leads.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Calendars {
+	/// The calendar of tasks whose lead has none of their own.
+	default: Calendar,
+	/// Calendars by lead name.
+	leads: BTreeMap<String, Calendar>,
+}
+
+impl Calendars {
+	pub fn new(default: Calendar) -> Self {
+		Self { default, leads: BTreeMap::new() }
+	}
+
+	/// Constructs with the calendar of a lead.
+	pub fn with_lead(mut self, name: impl Into<String>, calendar: Calendar) -> Self {
+		self.leads.insert(name.into(), calendar);
+		self
+	}
+
+	/// Get the default calendar.
+	pub fn default_calendar(&self) -> &Calendar {
+		&self.default
+	}
+
+	/// Get the calendar of a lead, or the default calendar if the lead has none.
+	pub fn for_lead(&self, name: &str) -> &Calendar {
+		self.leads.get(name).unwrap_or(&self.default)
+	}
+
+	/// Get the calendar a task is scheduled with, which is that of its first lead.
+	pub fn for_task(&self, task: &Task) -> &Calendar {
+		match task.leads().first() {
+			Some(lead) => self.for_lead(lead.name()),
+			None => &self.default,
+		}
+	}
+
+	/// Parses calendars from the contents of a calendar file.
+	pub fn parse(contents: &str) -> Result<Self, CalendarError> {
+		let mut calendars = Self::default();
+		let mut lead: Option<String> = None;
+
+		for (index, line) in contents.lines().enumerate() {
+			let line = line.trim();
+			if line.is_empty() || line.starts_with('#') {
+				continue;
+			}
+
+			let invalid =
+				|| CalendarError::InvalidLine { line: index + 1, content: line.to_string() };
+
+			if let Some(name) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+				let name = name.trim().to_string();
+				calendars.leads.entry(name.clone()).or_insert_with(|| calendars.default.clone());
+				lead = Some(name);
+				continue;
+			}
+
+			let (key, value) = line.split_once(':').ok_or_else(invalid)?;
+			let calendar = match &lead {
+				Some(name) => calendars.leads.entry(name.clone()).or_default(),
+				None => &mut calendars.default,
+			};
+			if !calendar.apply(index + 1, key.trim(), value)? {
+				return Err(invalid());
+			}
+		}
+
+		Ok(calendars)
+	}
+
+	/// Loads calendars from a calendar file.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, CalendarError> {
+		Self::parse(&std::fs::read_to_string(path)?)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn date(year: i32, month: u32, day: u32) -> Result<NaiveDate, anyhow::Error> {
+		NaiveDate::from_ymd_opt(year, month, day).ok_or(anyhow::anyhow!("invalid date"))
+	}
+
+	fn midnight(year: i32, month: u32, day: u32) -> Result<Date, anyhow::Error> {
+		let datetime = date(year, month, day)?.and_hms_opt(0, 0, 0);
+		Ok(Date::new(datetime.ok_or(anyhow::anyhow!("invalid time"))?.and_utc()))
+	}
+
+	#[test]
+	fn test_add_workdays() -> Result<(), anyhow::Error> {
+		let calendar = Calendar::new().with_holidays([date(2025, 12, 25)?, date(2025, 12, 26)?]);
+
+		// Friday and Monday, ending on Tuesday
+		assert_eq!(calendar.add_workdays(midnight(2025, 12, 5)?, 2), midnight(2025, 12, 9)?);
+
+		// Ten working days across Christmas and two weekends
+		assert_eq!(calendar.add_workdays(midnight(2025, 12, 22)?, 10), midnight(2026, 1, 7)?);
+
+		// Starting on a weekend counts from Monday
+		assert_eq!(calendar.add_workdays(midnight(2025, 12, 6)?, 1), midnight(2025, 12, 9)?);
+		assert_eq!(calendar.add_workdays(midnight(2025, 12, 6)?, 0), midnight(2025, 12, 6)?);
+
+		// Subtracting undoes adding across Christmas and two weekends
+		let utc = Timezone::utc();
+		assert_eq!(
+			calendar.subtract_workdays_in(midnight(2026, 1, 7)?, 10, utc),
+			midnight(2025, 12, 22)?
+		);
+		assert_eq!(
+			calendar.subtract_workdays_in(midnight(2025, 12, 9)?, 2, utc),
+			midnight(2025, 12, 5)?
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_parse_calendars() -> Result<(), anyhow::Error> {
+		let calendars = Calendars::parse(
+			"# The company calendar
+			weekdays: Mon-Fri
+			holidays: 2025-12-25, 2025-12-31..2026-01-01
+
+			[Ada Lovelace]
+			weekdays: Mon-Thu
+			holidays: 2025-08-04",
+		)?;
+
+		let default = calendars.default_calendar();
+		assert_eq!(default.weekdays().count(), 5);
+		assert_eq!(default.holidays().len(), 3);
+
+		let ada = calendars.for_lead("Ada Lovelace");
+		assert_eq!(ada.weekdays().last(), Some(Weekday::Thu));
+		assert!(!ada.is_working_day(date(2025, 12, 25)?));
+		assert!(!ada.is_working_day(date(2025, 8, 4)?));
+		assert_eq!(calendars.for_lead("Grace Hopper"), default);
+
+		assert!(matches!(
+			Calendars::parse("weekdays: Mon-Funday"),
+			Err(CalendarError::InvalidWeekday { .. })
+		));
+		assert!(matches!(Calendars::parse("vacation"), Err(CalendarError::InvalidLine { .. })));
+		assert!(matches!(
+			Calendars::parse("weekdays: Mon-Fri\nholidays: 2026-01-01..2025-12-31"),
+			Err(CalendarError::InvalidHolidayRange { line: 2, .. })
+		));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,335 @@
+//! Graphviz DOT export of the dependency graph.
+//!
+//! The export shows the dependency structure of a roadline without its time axis, e.g. for
+//! architecture reviews. Tasks are drawn as boxes labeled with their titles, milestones as
+//! diamonds, and each dependency as an arrow from the task depended on to its dependent:
+//!
+//! - Dependencies on the critical path are drawn bold and red, and the tasks on it bold.
+//! - Redundant dependencies, which a longer path already implies, are drawn dashed and gray.
+//!
+//! The output can be rendered with e.g. `dot -Tsvg roadline.dot -o roadline.svg`.
+
+use crate::graph::Graph;
+use crate::grid_algebra::GridAlgebra;
+use crate::roadline::Roadline;
+use roadline_util::task::Id as TaskId;
+use std::collections::{BTreeMap, HashSet};
+
+/// How tasks are grouped into clusters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Clusters {
+	/// Tasks are not grouped.
+	#[default]
+	None,
+	/// A cluster per swimlane of the grid layout.
+	Lanes,
+	/// A cluster per label; a task with several labels is grouped by the first of them.
+	Labels,
+}
+
+/// Exporter which writes the dependency graph of a roadline as a DOT digraph.
+///
+/// # Example
+///
+/// ```
+/// use roadline_representation_core::dot::{Clusters, DotExporter};
+/// use roadline_representation_core::roadline::RoadlineBuilder;
+/// use roadline_util::task::Task;
+/// use std::time::Duration;
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let draft = Task::test_from_id(1)?.for_standard_duration(Duration::from_secs(86400));
+/// let review = Task::test_from_id(2)?.after(&draft).with_dependencies([1]);
+/// let roadline = RoadlineBuilder::new().task(draft)?.task(review)?.build()?;
+///
+/// let dot = DotExporter::new()
+///     .with_clusters(Clusters::Lanes)
+///     .with_timeline_ranks(true)
+///     .export(&roadline);
+/// assert!(dot.contains("t1 -> t2 [color=red, penwidth=2];"));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DotExporter {
+	clusters: Clusters,
+	timeline_ranks: bool,
+}
+
+impl DotExporter {
+	/// Create a new DOT exporter, which does not cluster tasks.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Constructs with a way to group tasks into clusters.
+	pub fn with_clusters(self, clusters: Clusters) -> Self {
+		Self { clusters, ..self }
+	}
+
+	/// Constructs to emit rank constraints derived from the grid layout, so that the graph
+	/// preserves the order of the timeline.
+	///
+	/// The tasks of each lane are chained by invisible edges in the order they start, and
+	/// tasks starting in the same column of the grid share a rank. Graphviz cannot rank
+	/// tasks of different clusters together, so the latter is left out with clusters.
+	pub fn with_timeline_ranks(self, timeline_ranks: bool) -> Self {
+		Self { timeline_ranks, ..self }
+	}
+
+	/// Write the dependency graph of a roadline.
+	pub fn export(&self, roadline: &Roadline) -> String {
+		self.write(roadline.graph(), Some(roadline.grid_algebra()))
+	}
+
+	/// Write a dependency graph.
+	///
+	/// A graph has no schedule, so there is no critical path, lanes or timeline; tasks can
+	/// only be clustered by label and no rank constraints are emitted.
+	pub fn export_graph(&self, graph: &Graph) -> String {
+		self.write(graph, None)
+	}
+
+	fn write(&self, graph: &Graph, grid: Option<&GridAlgebra>) -> String {
+		let mut task_ids: Vec<TaskId> = graph.arena().tasks().keys().copied().collect();
+		task_ids.sort();
+
+		let critical_path =
+			grid.map(|grid| grid.range_algebra().critical_path()).unwrap_or_default();
+		let critical: HashSet<(TaskId, TaskId)> =
+			critical_path.windows(2).map(|pair| (pair[0], pair[1])).collect();
+		let redundant: HashSet<(TaskId, TaskId)> =
+			graph.redundant_dependencies().into_iter().collect();
+
+		let mut lines = vec![
+			"digraph roadline {".to_string(),
+			"    rankdir=LR;".to_string(),
+			"    node [shape=box, style=rounded];".to_string(),
+		];
+
+		// Tasks outside of clusters first, then each cluster
+		let mut clusters: BTreeMap<(Option<u8>, Option<String>), Vec<TaskId>> = BTreeMap::new();
+		for task_id in &task_ids {
+			clusters.entry(self.cluster(graph, grid, task_id)).or_default().push(*task_id);
+		}
+		for (index, ((lane, label), members)) in clusters.iter().enumerate() {
+			let title = match (lane, label) {
+				(Some(lane), _) => Some(format!("Lane {lane}")),
+				(None, label) => label.clone(),
+			};
+			let indent = match &title {
+				Some(title) => {
+					lines.push(format!("    subgraph cluster_{index} {{"));
+					lines.push(format!("        label=\"{}\";", escape(title)));
+					"        "
+				}
+				None => "    ",
+			};
+			for task_id in members {
+				lines.push(format!("{indent}{}", Self::node(graph, task_id, critical_path)));
+			}
+			if title.is_some() {
+				lines.push("    }".to_string());
+			}
+		}
+
+		for from_task in &task_ids {
+			let mut dependents = graph.get_dependents(from_task);
+			dependents.sort();
+			dependents.dedup();
+			for to_task in dependents {
+				let edge = (*from_task, to_task);
+				let style = if critical.contains(&edge) {
+					" [color=red, penwidth=2]"
+				} else if redundant.contains(&edge) {
+					" [style=dashed, color=gray]"
+				} else {
+					""
+				};
+				lines.push(format!(
+					"    {} -> {}{style};",
+					node_id(graph, from_task),
+					node_id(graph, &to_task)
+				));
+			}
+		}
+
+		if let Some(grid) = grid.filter(|_| self.timeline_ranks) {
+			lines.extend(self.ranks(graph, grid));
+		}
+
+		lines.push("}".to_string());
+		lines.join("\n") + "\n"
+	}
+
+	/// The lane or label a task is clustered by, if any.
+	fn cluster(
+		&self,
+		graph: &Graph,
+		grid: Option<&GridAlgebra>,
+		task_id: &TaskId,
+	) -> (Option<u8>, Option<String>) {
+		match self.clusters {
+			Clusters::None => (None, None),
+			Clusters::Lanes => {
+				(grid.and_then(|grid| grid.task_cell(task_id)).map(|cell| cell.lane_id()), None)
+			}
+			Clusters::Labels => (
+				None,
+				graph
+					.task(task_id)
+					.and_then(|task| task.labels().iter().next())
+					.map(|label| label.to_string()),
+			),
+		}
+	}
+
+	/// The statement declaring a task.
+	fn node(graph: &Graph, task_id: &TaskId, critical_path: &[TaskId]) -> String {
+		let mut attributes = vec![format!("label=\"{}\"", escape(&node_label(graph, task_id)))];
+		if graph.task(task_id).is_some_and(|task| task.kind().is_milestone()) {
+			attributes.push("shape=diamond".to_string());
+		}
+		if critical_path.contains(task_id) {
+			attributes.push("penwidth=2".to_string());
+		}
+		format!("{} [{}];", node_id(graph, task_id), attributes.join(", "))
+	}
+
+	/// The rank constraints which keep the graph in the order of the timeline.
+	fn ranks(&self, graph: &Graph, grid: &GridAlgebra) -> Vec<String> {
+		let mut lanes: BTreeMap<u8, Vec<(u8, TaskId)>> = BTreeMap::new();
+		let mut columns: BTreeMap<u8, Vec<TaskId>> = BTreeMap::new();
+		for (task_id, cell) in grid.tasks() {
+			lanes
+				.entry(cell.lane_id())
+				.or_default()
+				.push((cell.stretch().start(), *task_id));
+			columns.entry(cell.stretch().start()).or_default().push(*task_id);
+		}
+
+		let mut lines = Vec::new();
+		for tasks in lanes.values_mut() {
+			tasks.sort();
+			for pair in tasks.windows(2) {
+				lines.push(format!(
+					"    {} -> {} [style=invis];",
+					node_id(graph, &pair[0].1),
+					node_id(graph, &pair[1].1)
+				));
+			}
+		}
+
+		if self.clusters == Clusters::None {
+			for tasks in columns.values_mut().filter(|tasks| tasks.len() > 1) {
+				tasks.sort();
+				let ids: Vec<String> =
+					tasks.iter().map(|task_id| node_id(graph, task_id)).collect();
+				lines.push(format!("    {{ rank=same; {}; }}", ids.join("; ")));
+			}
+		}
+
+		lines
+	}
+}
+
+/// The id of a task, e.g. `T3` or `M3` for a milestone.
+fn node_label_id(graph: &Graph, task_id: &TaskId) -> String {
+	let prefix = graph.task(task_id).map_or('T', |task| task.kind().prefix());
+	format!("{prefix}{}", task_id.value())
+}
+
+/// The DOT identifier of a task, e.g. `t3`.
+fn node_id(graph: &Graph, task_id: &TaskId) -> String {
+	node_label_id(graph, task_id).to_lowercase()
+}
+
+/// The label of a task, e.g. `T3: Review`.
+fn node_label(graph: &Graph, task_id: &TaskId) -> String {
+	let id = node_label_id(graph, task_id);
+	match graph.task(task_id) {
+		Some(task) => format!("{id}: {}", task.title().plain_text()),
+		None => id,
+	}
+}
+
+/// Escape text for a quoted DOT string.
+fn escape(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::roadline::RoadlineBuilder;
+	use roadline_util::task::Task;
+	use std::time::Duration as StdDuration;
+
+	fn days(days: u64) -> StdDuration {
+		StdDuration::from_secs(days * 24 * 60 * 60)
+	}
+
+	/// T1 -> T2 -> T3 with a redundant T1 -> T3, and T4 on its own.
+	fn create_roadline() -> Result<Roadline, anyhow::Error> {
+		let task1 = Task::test_from_id(1)?.for_standard_duration(days(10)).with_labels(["core"]);
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(days(5))
+			.with_dependencies([1])
+			.with_labels(["core"]);
+		let task3 = Task::test_from_id(3)?
+			.after(&task2)
+			.for_standard_duration(days(5))
+			.with_dependencies([1, 2]);
+		let task4 = Task::test_from_id(4)?.for_standard_duration(days(3)).with_labels(["infra"]);
+
+		Ok(RoadlineBuilder::start_of_epoch()?
+			.tasks([task1, task2, task3, task4])?
+			.build()?)
+	}
+
+	#[test]
+	fn test_export_styles_dependencies() -> Result<(), anyhow::Error> {
+		let roadline = create_roadline()?;
+		let dot = DotExporter::new().export(&roadline);
+
+		assert!(dot.starts_with("digraph roadline {\n    rankdir=LR;\n"));
+		assert!(dot.contains("    t3 [label=\"T3: "));
+		assert!(dot.contains(", penwidth=2];"));
+		assert!(dot.contains("    t1 -> t2 [color=red, penwidth=2];\n"));
+		assert!(dot.contains("    t2 -> t3 [color=red, penwidth=2];\n"));
+		assert!(dot.contains("    t1 -> t3 [style=dashed, color=gray];\n"));
+		assert!(!dot.contains("subgraph"));
+		assert!(!dot.contains("rank=same"));
+
+		// Without a schedule there is no critical path
+		let dot = DotExporter::new().export_graph(roadline.graph());
+		assert!(dot.contains("    t1 -> t2;\n"));
+		assert!(dot.contains("    t1 -> t3 [style=dashed, color=gray];\n"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_export_clusters_and_ranks() -> Result<(), anyhow::Error> {
+		let roadline = create_roadline()?;
+
+		let dot = DotExporter::new().with_clusters(Clusters::Labels).export(&roadline);
+		assert!(dot.contains("    subgraph cluster_1 {\n        label=\"core\";\n        t1 ["));
+		assert!(dot.contains("        label=\"infra\";\n        t4 ["));
+
+		// T1 and T4 start together, and T2 follows T1 in its lane
+		let dot = DotExporter::new().with_timeline_ranks(true).export(&roadline);
+		assert!(dot.contains("    { rank=same; t1; t4; }\n"));
+		assert!(dot.contains("    t1 -> t2 [style=invis];\n"));
+
+		let dot = DotExporter::new()
+			.with_clusters(Clusters::Lanes)
+			.with_timeline_ranks(true)
+			.export(&roadline);
+		assert!(dot.contains("label=\"Lane 0\";"));
+		assert!(!dot.contains("rank=same"));
+
+		Ok(())
+	}
+}
@@ -5,6 +5,7 @@
 //! - `query`: Lookups, inspections, and basic graph queries  
 //! - `traversal`: DFS, BFS, and path-finding algorithms
 //! - `analysis`: Cycle detection, topological sorting, and structural analysis
+//! - `metrics`: Depth, fan-in/out, centrality and articulation tasks of a graph
 
 pub mod operations;
 pub mod predicate;
@@ -2,6 +2,7 @@ pub mod mutation;
 pub mod query;
 pub mod traversal;
 pub mod analysis;
+pub mod metrics;
 
 #[cfg(test)]
 pub mod tests;
@@ -1,6 +1,6 @@
 use crate::graph::{Graph, GraphError};
 use roadline_util::task::Id as TaskId;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 impl Graph {
     /// Detects if the graph contains cycles.
@@ -197,6 +197,44 @@ impl Graph {
         
         Ok(cycles)
     }
+
+    /// Finds the dependencies which a longer path already implies, e.g. task1 -> task4
+    /// when task1 -> task2 -> task4, i.e. those a transitive reduction would drop.
+    ///
+    /// Returns `(dependency, dependent)` pairs in order.
+    pub fn redundant_dependencies(&self) -> Vec<(TaskId, TaskId)> {
+        let mut redundant = Vec::new();
+
+        for (from_task, predicates) in &self.facts {
+            for predicate in predicates {
+                let to_task = predicate.task_id;
+
+                // Look for another way from the dependency to the dependent
+                let mut visited = HashSet::new();
+                let mut stack: Vec<TaskId> = predicates
+                    .iter()
+                    .map(|p| p.task_id)
+                    .filter(|task_id| *task_id != to_task)
+                    .collect();
+
+                while let Some(task_id) = stack.pop() {
+                    if task_id == to_task {
+                        redundant.push((*from_task, to_task));
+                        break;
+                    }
+                    if visited.insert(task_id) {
+                        if let Some(next) = self.facts.get(&task_id) {
+                            stack.extend(next.iter().map(|p| p.task_id));
+                        }
+                    }
+                }
+            }
+        }
+
+        redundant.sort();
+        redundant.dedup();
+        redundant
+    }
 }
 
 #[cfg(test)]
@@ -310,6 +348,18 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_redundant_dependencies() -> Result<(), anyhow::Error> {
+        let mut graph = create_acyclic_graph()?;
+        assert!(graph.redundant_dependencies().is_empty());
+
+        // task1 -> task4 is implied by task1 -> task2 -> task4
+        graph.add_dependency(TaskId::new(1), DependencyId::from_u8(1, 4), TaskId::new(4))?;
+        assert_eq!(graph.redundant_dependencies(), vec![(TaskId::new(1), TaskId::new(4))]);
+
+        Ok(())
+    }
+
     #[test]
     fn test_self_loop_cycle() -> Result<(), anyhow::Error> {
         let mut graph = Graph::new();
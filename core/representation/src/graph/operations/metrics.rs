@@ -0,0 +1,250 @@
+use crate::graph::{Graph, GraphError};
+use roadline_util::task::Id as TaskId;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+/// Structural metrics of a single task.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TaskMetrics {
+	/// The length of the longest dependency chain from a root task, which is at depth 0.
+	pub depth: usize,
+	/// The number of tasks this task depends on.
+	pub fan_in: usize,
+	/// The number of tasks depending on this task.
+	pub fan_out: usize,
+	/// The number of tasks which transitively depend on this task, and so slip with it.
+	pub descendants: usize,
+	/// The number of shortest dependency paths between other tasks which pass through this
+	/// task, each pair of tasks weighing one in total.
+	pub betweenness: f64,
+}
+
+/// A report of the structural metrics of a graph.
+///
+/// Risky bottleneck tasks are the articulation tasks, whose removal disconnects the graph,
+/// with many descendants, and tasks with a high betweenness centrality.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphMetrics {
+	tasks: BTreeMap<TaskId, TaskMetrics>,
+	articulation_tasks: Vec<TaskId>,
+}
+
+impl GraphMetrics {
+	/// The metrics of every task.
+	pub fn tasks(&self) -> &BTreeMap<TaskId, TaskMetrics> {
+		&self.tasks
+	}
+
+	/// The metrics of a task.
+	pub fn task(&self, task_id: &TaskId) -> Option<&TaskMetrics> {
+		self.tasks.get(task_id)
+	}
+
+	/// The tasks connecting otherwise separate parts of the graph, most descendants first.
+	///
+	/// Dependencies are treated as undirected, so a slip of one of these tasks is the only
+	/// way for one part of the roadline to affect another.
+	pub fn articulation_tasks(&self) -> &[TaskId] {
+		&self.articulation_tasks
+	}
+
+	/// The length of the longest dependency chain.
+	pub fn max_depth(&self) -> usize {
+		self.tasks.values().map(|metrics| metrics.depth).max().unwrap_or(0)
+	}
+
+	/// The tasks with the highest betweenness centrality, most central first.
+	///
+	/// Tasks no shortest path passes through are left out.
+	pub fn most_central(&self, count: usize) -> Vec<TaskId> {
+		let mut central: Vec<(&TaskId, &TaskMetrics)> =
+			self.tasks.iter().filter(|(_, metrics)| metrics.betweenness > 0.0).collect();
+		central.sort_by(|(a_id, a), (b_id, b)| {
+			b.betweenness.total_cmp(&a.betweenness).then_with(|| a_id.cmp(b_id))
+		});
+		central.into_iter().take(count).map(|(task_id, _)| *task_id).collect()
+	}
+}
+
+impl Graph {
+	/// Computes depth, fan-in, fan-out, descendants and betweenness centrality of every task,
+	/// and the articulation tasks of the graph.
+	///
+	/// Fails if the graph has cycles, as depth is undefined then.
+	pub fn metrics(&self) -> Result<GraphMetrics, GraphError> {
+		let order = self.topological_sort()?;
+		let dependents = self.dependents_by_task();
+
+		let mut depth: HashMap<TaskId, usize> = HashMap::new();
+		let mut fan_in: HashMap<TaskId, usize> = HashMap::new();
+		for task_id in &order {
+			let task_depth = depth.get(task_id).copied().unwrap_or(0);
+			for dependent in &dependents[task_id] {
+				let dependent_depth = depth.entry(*dependent).or_insert(0);
+				*dependent_depth = (*dependent_depth).max(task_depth + 1);
+				*fan_in.entry(*dependent).or_insert(0) += 1;
+			}
+		}
+
+		// The descendants of a task are its dependents and theirs, collected in reverse order
+		let mut descendants: HashMap<TaskId, BTreeSet<TaskId>> = HashMap::new();
+		for task_id in order.iter().rev() {
+			let mut reachable = BTreeSet::new();
+			for dependent in &dependents[task_id] {
+				reachable.insert(*dependent);
+				reachable.extend(descendants[dependent].iter().copied());
+			}
+			descendants.insert(*task_id, reachable);
+		}
+
+		let betweenness = Self::betweenness(&dependents);
+		let tasks: BTreeMap<TaskId, TaskMetrics> = order
+			.iter()
+			.map(|task_id| {
+				let metrics = TaskMetrics {
+					depth: depth.get(task_id).copied().unwrap_or(0),
+					fan_in: fan_in.get(task_id).copied().unwrap_or(0),
+					fan_out: dependents[task_id].len(),
+					descendants: descendants[task_id].len(),
+					betweenness: betweenness.get(task_id).copied().unwrap_or(0.0),
+				};
+				(*task_id, metrics)
+			})
+			.collect();
+
+		let mut articulation_tasks: Vec<TaskId> =
+			Self::articulation_points(&dependents).into_iter().collect();
+		articulation_tasks.sort_by_key(|task_id| {
+			(
+				std::cmp::Reverse(tasks.get(task_id).map_or(0, |metrics| metrics.descendants)),
+				*task_id,
+			)
+		});
+
+		Ok(GraphMetrics { tasks, articulation_tasks })
+	}
+
+	/// The distinct dependents of every task, in order of task id.
+	fn dependents_by_task(&self) -> BTreeMap<TaskId, Vec<TaskId>> {
+		let mut dependents: BTreeMap<TaskId, Vec<TaskId>> =
+			self.task_ids().map(|task_id| (*task_id, Vec::new())).collect();
+		for (task_id, predicates) in &self.facts {
+			let unique: BTreeSet<TaskId> =
+				predicates.iter().map(|predicate| predicate.task_id).collect();
+			dependents.entry(*task_id).or_default().extend(unique.iter().copied());
+			for dependent in unique {
+				dependents.entry(dependent).or_default();
+			}
+		}
+		dependents
+	}
+
+	/// Brandes' algorithm for betweenness centrality along dependency edges.
+	fn betweenness(dependents: &BTreeMap<TaskId, Vec<TaskId>>) -> HashMap<TaskId, f64> {
+		let mut centrality: HashMap<TaskId, f64> =
+			dependents.keys().map(|task_id| (*task_id, 0.0)).collect();
+
+		for source in dependents.keys() {
+			let mut stack = Vec::new();
+			let mut predecessors: HashMap<TaskId, Vec<TaskId>> = HashMap::new();
+			let mut paths: HashMap<TaskId, f64> = HashMap::from([(*source, 1.0)]);
+			let mut distance: HashMap<TaskId, usize> = HashMap::from([(*source, 0)]);
+			let mut queue = VecDeque::from([*source]);
+
+			while let Some(current) = queue.pop_front() {
+				stack.push(current);
+				let current_distance = distance[&current];
+				for next in &dependents[&current] {
+					if !distance.contains_key(next) {
+						distance.insert(*next, current_distance + 1);
+						queue.push_back(*next);
+					}
+					if distance[next] == current_distance + 1 {
+						*paths.entry(*next).or_insert(0.0) += paths[&current];
+						predecessors.entry(*next).or_default().push(current);
+					}
+				}
+			}
+
+			let mut dependency: HashMap<TaskId, f64> = HashMap::new();
+			while let Some(task_id) = stack.pop() {
+				let task_dependency = dependency.get(&task_id).copied().unwrap_or(0.0);
+				for predecessor in predecessors.get(&task_id).into_iter().flatten() {
+					let share = paths[predecessor] / paths[&task_id] * (1.0 + task_dependency);
+					*dependency.entry(*predecessor).or_insert(0.0) += share;
+				}
+				if task_id != *source {
+					*centrality.entry(task_id).or_insert(0.0) += task_dependency;
+				}
+			}
+		}
+
+		centrality
+	}
+
+	/// Tarjan's algorithm for the articulation points of the undirected dependency graph.
+	fn articulation_points(dependents: &BTreeMap<TaskId, Vec<TaskId>>) -> HashSet<TaskId> {
+		let mut neighbors: BTreeMap<TaskId, BTreeSet<TaskId>> = BTreeMap::new();
+		for (task_id, task_dependents) in dependents {
+			neighbors.entry(*task_id).or_default();
+			for dependent in task_dependents {
+				neighbors.entry(*task_id).or_default().insert(*dependent);
+				neighbors.entry(*dependent).or_default().insert(*task_id);
+			}
+		}
+
+		struct Search<'a> {
+			neighbors: &'a BTreeMap<TaskId, BTreeSet<TaskId>>,
+			discovery: HashMap<TaskId, usize>,
+			low: HashMap<TaskId, usize>,
+			points: HashSet<TaskId>,
+		}
+
+		impl Search<'_> {
+			fn visit(&mut self, task_id: TaskId, parent: Option<TaskId>) {
+				let time = self.discovery.len();
+				self.discovery.insert(task_id, time);
+				self.low.insert(task_id, time);
+				let mut children = 0;
+
+				let neighbors = self.neighbors;
+				for next in &neighbors[&task_id] {
+					if Some(*next) == parent {
+						continue;
+					}
+					if let Some(next_discovery) = self.discovery.get(next).copied() {
+						let low = self.low[&task_id].min(next_discovery);
+						self.low.insert(task_id, low);
+						continue;
+					}
+
+					children += 1;
+					self.visit(*next, Some(task_id));
+					let low = self.low[&task_id].min(self.low[next]);
+					self.low.insert(task_id, low);
+					if parent.is_some() && self.low[next] >= self.discovery[&task_id] {
+						self.points.insert(task_id);
+					}
+				}
+
+				if parent.is_none() && children > 1 {
+					self.points.insert(task_id);
+				}
+			}
+		}
+
+		let mut search = Search {
+			neighbors: &neighbors,
+			discovery: HashMap::new(),
+			low: HashMap::new(),
+			points: HashSet::new(),
+		};
+		for task_id in neighbors.keys() {
+			if !search.discovery.contains_key(task_id) {
+				search.visit(*task_id, None);
+			}
+		}
+
+		search.points
+	}
+}
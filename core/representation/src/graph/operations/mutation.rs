@@ -3,6 +3,7 @@ use roadline_util::dependency::Dependency;
 use roadline_util::task::Id as TaskId;
 use roadline_util::dependency::id::Id as DependencyId;
 use roadline_util::task::Task;
+use std::collections::BTreeSet;
 
 impl Graph {
     /// Adds a task to the graph with no dependencies.
@@ -24,6 +25,19 @@ impl Graph {
             self.arena.add_dependency(dep);
         }
 
+        // subtask dependencies are edges from the task owning the subtask
+        let subtask_parents: BTreeSet<TaskId> = task
+            .subtask_dependencies()
+            .iter()
+            .map(|dependency| dependency.task())
+            .filter(|parent| !task.dependencies().contains(parent))
+            .collect();
+        for from_task_id in subtask_parents {
+            let dep = Dependency::new(from_task_id, task_id);
+            self.add_dependency(from_task_id, *dep.id(), task_id)?;
+            self.arena.add_dependency(dep);
+        }
+
         self.arena.add_task(task);
         
         Ok(())
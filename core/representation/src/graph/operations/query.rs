@@ -1,6 +1,7 @@
 use crate::graph::{Graph, Predicate};
 use roadline_util::task::id::Id as TaskId;
-use std::collections::HashSet;
+use roadline_util::task::{FieldValue, Label};
+use std::collections::{BTreeMap, HashSet};
 
 impl Graph {
     /// Gets all predicates for a given task.
@@ -87,6 +88,43 @@ impl Graph {
             .copied()
             .collect()
     }
+
+    /// Finds all tasks carrying the given label, in id order.
+    pub fn tasks_with_label(&self, label: &str) -> Vec<TaskId> {
+        let mut task_ids: Vec<TaskId> = self.arena.tasks()
+            .iter()
+            .filter(|(_, task)| task.has_label(label))
+            .map(|(task_id, _)| *task_id)
+            .collect();
+        task_ids.sort();
+        task_ids
+    }
+
+    /// Groups tasks by label. A task with several labels is in several groups.
+    pub fn group_by_label(&self) -> BTreeMap<Label, Vec<TaskId>> {
+        let mut groups: BTreeMap<Label, Vec<TaskId>> = BTreeMap::new();
+        for (task_id, task) in self.arena.tasks() {
+            for label in task.labels() {
+                groups.entry(label.clone()).or_default().push(*task_id);
+            }
+        }
+        groups.values_mut().for_each(|task_ids| task_ids.sort());
+        groups
+    }
+
+    /// Groups tasks by the value of a custom field, e.g. `Priority`.
+    ///
+    /// Tasks without the field are left out.
+    pub fn group_by_field(&self, field: &str) -> BTreeMap<FieldValue, Vec<TaskId>> {
+        let mut groups: BTreeMap<FieldValue, Vec<TaskId>> = BTreeMap::new();
+        for (task_id, task) in self.arena.tasks() {
+            if let Some(value) = task.field(field) {
+                groups.entry(value.clone()).or_default().push(*task_id);
+            }
+        }
+        groups.values_mut().for_each(|task_ids| task_ids.sort());
+        groups
+    }
 }
 
 #[cfg(test)]
@@ -218,6 +256,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_labels_and_fields() -> Result<(), anyhow::Error> {
+        use roadline_util::task::Task;
+
+        let mut graph = Graph::new();
+        graph.add(Task::test_from_id(1)?
+            .with_labels(["infra", "security"])
+            .with_field("Priority", FieldValue::parse("P1")))?;
+        graph.add(Task::test_from_id(2)?
+            .with_labels(["infra"])
+            .with_field("Priority", FieldValue::parse("P2")))?;
+        graph.add(Task::test_from_id(3)?.with_field("Priority", FieldValue::parse("P1")))?;
+
+        assert_eq!(graph.tasks_with_label("infra"), vec![TaskId::new(1), TaskId::new(2)]);
+        assert_eq!(graph.tasks_with_label("security"), vec![TaskId::new(1)]);
+        assert!(graph.tasks_with_label("design").is_empty());
+
+        let labels = graph.group_by_label();
+        assert_eq!(labels.get(&Label::from("infra")).map(Vec::len), Some(2));
+
+        let priorities = graph.group_by_field("Priority");
+        assert_eq!(
+            priorities.get(&FieldValue::Text("P1".to_string())),
+            Some(&vec![TaskId::new(1), TaskId::new(3)])
+        );
+        assert_eq!(priorities.len(), 2);
+        assert!(graph.group_by_field("Epic").is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn test_leaf_tasks() -> Result<(), anyhow::Error> {
         let graph = create_test_graph()?;
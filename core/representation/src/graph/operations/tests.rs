@@ -327,4 +327,39 @@ mod integration_tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_graph_metrics() -> Result<(), anyhow::Error> {
+        // task1 -> task2 -> task3 -> task4
+        let metrics = create_linear_graph()?.metrics()?;
+        let task2 = metrics.task(&TaskId::new(2)).ok_or(anyhow::anyhow!("missing task2"))?;
+
+        assert_eq!(task2.depth, 1);
+        assert_eq!((task2.fan_in, task2.fan_out), (1, 1));
+        assert_eq!(task2.descendants, 2);
+        // task2 lies on the paths from task1 to task3 and task4
+        assert_eq!(task2.betweenness, 2.0);
+        assert_eq!(metrics.max_depth(), 3);
+        assert_eq!(metrics.articulation_tasks(), [TaskId::new(2), TaskId::new(3)]);
+        assert_eq!(metrics.most_central(1), vec![TaskId::new(2)]);
+
+        // task1 -> [task2, task3] -> task4, task1 -> task5
+        let metrics = create_branched_graph()?.metrics()?;
+        let task1 = metrics.task(&TaskId::new(1)).ok_or(anyhow::anyhow!("missing task1"))?;
+        let task4 = metrics.task(&TaskId::new(4)).ok_or(anyhow::anyhow!("missing task4"))?;
+
+        assert_eq!((task1.fan_out, task1.descendants), (3, 4));
+        assert_eq!((task4.depth, task4.fan_in), (2, 2));
+        // Only task1 connects task5 to the rest
+        assert_eq!(metrics.articulation_tasks(), [TaskId::new(1)]);
+
+        // The report survives serialization
+        let json = serde_json::to_string(&metrics)?;
+        let parsed: crate::graph::operations::metrics::GraphMetrics = serde_json::from_str(&json)?;
+        assert_eq!(parsed, metrics);
+
+        assert!(create_cyclic_graph()?.metrics().is_err());
+
+        Ok(())
+    }
 }
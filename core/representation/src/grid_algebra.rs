@@ -101,17 +101,18 @@ impl PreGridAlgebra {
 			return Err(GridAlgebraError::NoTasks);
 		}
 
-		// Calculate average task duration in seconds
-		let total_duration_seconds: u64 = spans
-			.values()
-			.map(|span| {
+		// Calculate average task duration in seconds, leaving out milestones which have none
+		let durations: Vec<u64> = spans
+			.iter()
+			.filter(|(task_id, _)| !self.is_milestone(task_id))
+			.map(|(_, span)| {
 				let start = span.start.inner().inner().timestamp();
 				let end = span.end.inner().inner().timestamp();
 				(end - start) as u64
 			})
-			.sum();
+			.collect();
 
-		let average_duration = total_duration_seconds / spans.len() as u64;
+		let average_duration = durations.iter().sum::<u64>() / durations.len().max(1) as u64;
 		Ok(StretchUnit::canonical_from_average_seconds(average_duration))
 	}
 
@@ -123,23 +124,39 @@ impl PreGridAlgebra {
 		let spans = self.range_algebra.spans();
 
 		// Find the earliest start time to use as reference point
-		let reference_time = spans
+		let earliest = spans
 			.values()
-			.map(|span| span.start.inner().inner().timestamp())
+			.map(|span| span.start.inner())
 			.min()
 			.ok_or(GridAlgebraError::NoTasks)?;
 
+		// Units of a day or longer count calendar days in the roadmap timezone from the day
+		// of the earliest start, as local days last 23 or 25 hours across clock changes
+		let timezone = self.range_algebra.timezone();
+		let reference_day = timezone.local_date(earliest);
 		let unit_seconds = time_unit.seconds() as i64;
+		let unit_of = |date: Date| {
+			let seconds = if time_unit >= StretchUnit::Days {
+				(timezone.local_date(date) - reference_day).num_days() * 24 * 60 * 60
+			} else {
+				date.inner().timestamp() - earliest.inner().timestamp()
+			};
+			(seconds / unit_seconds) as u8 // Floor division for simplicity
+		};
+
 		let mut task_stretches = HashMap::new();
 
 		for (&task_id, span) in spans {
-			let start_timestamp = span.start.inner().inner().timestamp();
-			let end_timestamp = span.end.inner().inner().timestamp();
-			// Convert to grid units relative to reference time
-			let start_unit = ((start_timestamp - reference_time) / unit_seconds) as u8;
-			let end_unit = ((end_timestamp - reference_time) / unit_seconds) as u8; // Floor division for simplicity
+			// Convert to grid units relative to the reference
+			let start_unit = unit_of(span.start.inner());
+			let end_unit = unit_of(span.end.inner());
 
-			let stretch_range = StretchRange::new(start_unit, end_unit.max(start_unit + 1)); // Ensure minimum 1 unit duration
+			// Milestones are points; other tasks take at least one unit
+			let stretch_range = if self.is_milestone(&task_id) {
+				StretchRange::point(start_unit)
+			} else {
+				StretchRange::new(start_unit, end_unit.max(start_unit + 1))
+			};
 			let stretch = Stretch::new(stretch_range, time_unit);
 
 			task_stretches.insert(task_id, stretch);
@@ -148,6 +165,11 @@ impl PreGridAlgebra {
 		Ok(task_stretches)
 	}
 
+	/// Whether a task is a milestone.
+	fn is_milestone(&self, task_id: &TaskId) -> bool {
+		self.range_algebra.task(task_id).is_some_and(Task::is_milestone)
+	}
+
 	/// Assigns lanes using DFS with dependency locality and temporal overlap prevention.
 	fn assign_lanes_dfs(
 		&self,
@@ -682,6 +704,43 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_milestone_layout() -> Result<(), anyhow::Error> {
+		use roadline_util::task::Kind;
+
+		let mut graph = create_simple_test_graph()?;
+
+		// Two milestones at the end of T1, which must not share a lane
+		let task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(30 * 24 * 60 * 60));
+		for id in [4, 5] {
+			let milestone = Task::test_from_id(id)?
+				.after(&task1)
+				.with_kind(Kind::Milestone)
+				.with_dependencies([1]);
+			graph.add(milestone)?;
+		}
+
+		let range_algebra =
+			PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z"))?;
+		let grid_algebra = PreGridAlgebra::new(range_algebra).compute()?;
+
+		// Milestones do not shorten the time unit of the other tasks
+		assert_eq!(grid_algebra.time_unit(), StretchUnit::Weeks);
+
+		let milestone4 =
+			grid_algebra.task_cell(&TaskId::new(4)).map(|cell| *cell.stretch().range());
+		let milestone5 =
+			grid_algebra.task_cell(&TaskId::new(5)).map(|cell| *cell.stretch().range());
+		assert_eq!(milestone4, Some(StretchRange::point(4)));
+		assert_eq!(milestone5, Some(StretchRange::point(4)));
+
+		let lane = |id| grid_algebra.task_cell(&TaskId::new(id)).map(Cell::lane_id);
+		assert_ne!(lane(4), lane(5));
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_grid_algebra_api_safety() -> Result<(), anyhow::Error> {
 		let graph = create_simple_test_graph()?;
@@ -976,4 +1035,67 @@ mod tests {
 		);
 		Ok(())
 	}
+
+	#[test]
+	fn test_days_start_at_local_midnight() -> Result<(), anyhow::Error> {
+		use crate::timezone::Timezone;
+
+		// T1 starts just after midnight on Monday in Berlin, which is still Sunday in UTC
+		let mut graph = Graph::new();
+		let task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(180 * 3600));
+		graph.add(task1.clone())?;
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(240 * 3600))
+			.with_dependencies([1]);
+		graph.add(task2)?;
+		let root_date = test_date("2025-01-05T23:30:00Z");
+
+		let berlin = Timezone::parse("Europe/Berlin")?;
+		let local = PreGridAlgebra::new(
+			PreRangeAlgebra::new(graph.clone()).with_timezone(berlin).compute(root_date)?,
+		)
+		.compute()?;
+		let utc = PreGridAlgebra::new(PreRangeAlgebra::new(graph).compute(root_date)?).compute()?;
+		assert_eq!(local.time_unit(), StretchUnit::Days);
+
+		// T2 starts at noon on the next Monday in Berlin, the eighth local day but the ninth UTC day
+		let start =
+			|grid: &GridAlgebra| grid.task_cell(&TaskId::new(2)).map(|cell| cell.stretch().start());
+		assert_eq!(start(&local), Some(7));
+		assert_eq!(start(&utc), Some(8));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_days_count_across_clock_changes() -> Result<(), anyhow::Error> {
+		use crate::timezone::Timezone;
+
+		// T1 starts at midnight on Friday 28 March in Berlin and ends at midnight on Tuesday
+		// 1 April, 95 hours later as the clocks go forward on Sunday
+		let mut graph = Graph::new();
+		let task1 = Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(95 * 3600));
+		graph.add(task1.clone())?;
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(325 * 3600))
+			.with_dependencies([1]);
+		graph.add(task2)?;
+		let root_date = test_date("2025-03-27T23:00:00Z");
+
+		let berlin = Timezone::parse("Europe/Berlin")?;
+		let grid = PreGridAlgebra::new(
+			PreRangeAlgebra::new(graph).with_timezone(berlin).compute(root_date)?,
+		)
+		.compute()?;
+		assert_eq!(grid.time_unit(), StretchUnit::Days);
+
+		// T2 starts on the fourth local day, although fewer than 96 hours have passed
+		let cell = grid.task_cell(&TaskId::new(2)).ok_or(anyhow::anyhow!("no T2 cell"))?;
+		assert_eq!(cell.stretch().start(), 4);
+
+		Ok(())
+	}
 }
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 /// The start and end are the indices of the time units that the stretch spans.
 ///
 /// The start is inclusive and the end is exclusive.
+/// A range whose start equals its end is a point, as used for milestones.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct StretchRange {
 	start: u8,
@@ -19,6 +20,15 @@ impl StretchRange {
 		Self { start, end }
 	}
 
+	/// Creates a point at the given index.
+	pub fn point(index: u8) -> Self {
+		Self { start: index, end: index }
+	}
+
+	pub fn is_point(&self) -> bool {
+		self.start == self.end
+	}
+
 	pub fn start(&self) -> u8 {
 		self.start
 	}
@@ -35,8 +45,16 @@ impl StretchRange {
 		index >= self.start && index < self.end
 	}
 
+	/// Whether the ranges share a time unit.
+	///
+	/// A point overlaps a range containing it and a point at the same index.
 	pub fn overlaps(&self, other: &StretchRange) -> bool {
-		self.start < other.end && other.start < self.end
+		match (self.is_point(), other.is_point()) {
+			(true, true) => self.start == other.start,
+			(true, false) => other.contains(self.start),
+			(false, true) => self.contains(other.start),
+			(false, false) => self.start < other.end && other.start < self.end,
+		}
 	}
 
 	pub fn seconds(&self, unit: StretchUnit) -> (u64, u64) {
@@ -197,6 +215,10 @@ impl Stretch {
 		self.range.duration()
 	}
 
+	pub fn is_point(&self) -> bool {
+		self.range.is_point()
+	}
+
 	pub fn overlaps(&self, other: &Stretch) -> bool {
 		self.unit == other.unit && self.range.overlaps(&other.range)
 	}
@@ -0,0 +1,782 @@
+//! The `roadline.json` interchange format.
+//!
+//! [`Roadline`] and its layers derive `Serialize`, but their output follows the internal
+//! data structures: ids are `ShortId` tuples, maps are emitted in hash order and nothing
+//! marks the shape of the data. A [`RoadlineDocument`] is the stable, versioned form of a
+//! roadline for tools which are not written in Rust. It holds the tasks with their
+//! dependencies, computed spans and layout, ordered by task id, and is described by the
+//! JSON Schema returned by [`schema`].
+//!
+//! Documents are read with [`RoadlineDocument::from_json`], which migrates older versions
+//! of the format and validates the result.
+
+pub mod schema;
+
+pub use schema::schema;
+
+use crate::reified::{ConnectionPoint, DownCell};
+use crate::roadline::Roadline;
+use crate::timezone::Timezone;
+use chrono::{DateTime, Utc};
+use roadline_util::dependency::Id as DependencyId;
+use roadline_util::task::subtask::{Id as SubtaskId, Status as SubtaskStatus};
+use roadline_util::task::{Id as TaskId, Kind, Lead, ProgressStatus, Task};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use thiserror::Error;
+
+/// The version of the format written by this crate.
+pub const VERSION: u32 = 1;
+
+/// Upgrades a document from one version of the format to the next.
+#[derive(Debug, Clone, Copy)]
+struct Migration {
+	/// The version the migration upgrades from.
+	from: u32,
+	/// Rewrites the document in place; the version field is updated afterwards.
+	migrate: fn(&mut Value) -> Result<(), InterchangeError>,
+}
+
+/// The migrations from every earlier version, applied in order by [`RoadlineDocument::from_json`].
+///
+/// A change to the format which older readers would misread bumps [`VERSION`] and adds a
+/// migration from the previous version here.
+const MIGRATIONS: &[Migration] = &[];
+
+#[derive(Error, Debug)]
+pub enum InterchangeError {
+	#[error("Failed to read or write roadline.json: {0}")]
+	Io(#[from] std::io::Error),
+	#[error("Invalid roadline.json: {0}")]
+	Json(#[from] serde_json::Error),
+	#[error("roadline.json has no version")]
+	MissingVersion,
+	#[error("roadline.json version {version} is not supported, expected at most {VERSION}")]
+	UnsupportedVersion { version: u64 },
+	#[error("invalid task id '{id}', expected e.g. 'T3' or 'M3'")]
+	InvalidTaskId { id: String },
+	#[error("invalid subtask id '{id}' of task '{task}', expected e.g. '{task}.1'")]
+	InvalidSubtaskId { id: String, task: String },
+	#[error("task '{id}' appears more than once")]
+	DuplicateTask { id: String },
+	#[error("task '{task}' and milestone '{milestone}' share their id")]
+	SharedTaskId { task: String, milestone: String },
+	#[error("dependency {from} → {to} refers to a task which is not in the document")]
+	UnknownTask { from: String, to: String },
+	#[error("task '{id}' ends before it starts")]
+	InvalidSpan { id: String },
+}
+
+/// A roadline in the `roadline.json` interchange format.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoadlineDocument {
+	/// The version of the format, see [`VERSION`].
+	pub version: u32,
+	/// The IANA timezone the roadmap is planned in.
+	pub timezone: Timezone,
+	/// The size of the laid out roadline, in layout units.
+	pub bounds: Bounds,
+	/// The tasks, ordered by id.
+	pub tasks: Vec<TaskRecord>,
+	/// The dependencies between tasks, ordered by the task depended on and then the dependent.
+	pub dependencies: Vec<DependencyRecord>,
+}
+
+/// The width and height of a laid out roadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Bounds {
+	pub width: u16,
+	pub height: u16,
+}
+
+/// A task with its computed span and layout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskRecord {
+	/// The id of the task, e.g. `T3`, or `M3` for a milestone.
+	pub id: String,
+	pub kind: Kind,
+	pub title: String,
+	#[serde(default, skip_serializing_if = "String::is_empty")]
+	pub summary: String,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub leads: Vec<Lead>,
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub labels: Vec<String>,
+	/// The subtasks, ordered by id.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub subtasks: Vec<SubtaskRecord>,
+	pub progress: ProgressRecord,
+	/// When the task is planned to start and end.
+	pub span: SpanRecord,
+	/// Where the task is drawn, absent if the roadline was not laid out.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub layout: Option<Rect>,
+}
+
+/// A subtask of a task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SubtaskRecord {
+	/// The id of the subtask, e.g. `T3.1`.
+	pub id: String,
+	pub title: String,
+	pub status: ProgressStatus,
+	/// When the subtask was completed, if it is.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub completed: Option<DateTime<Utc>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub lead: Option<Lead>,
+	/// Further leads sharing the subtask with its lead.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub co_leads: Vec<Lead>,
+	/// When the subtask is planned to end.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub end: Option<DateTime<Utc>>,
+}
+
+/// What actually happened to a task.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProgressRecord {
+	pub status: ProgressStatus,
+	pub percent_complete: u8,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub started: Option<DateTime<Utc>>,
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub finished: Option<DateTime<Utc>>,
+}
+
+/// The computed start and end of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpanRecord {
+	pub start: DateTime<Utc>,
+	pub end: DateTime<Utc>,
+}
+
+/// The rectangle a task is drawn in, in layout units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Rect {
+	pub x: u16,
+	pub y: u16,
+	pub width: u16,
+	pub height: u16,
+	/// The lane the task is placed in, counting from 0 at the top.
+	pub lane: u8,
+}
+
+/// A dependency of one task on another, or on some of its subtasks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DependencyRecord {
+	/// The task depended on.
+	pub from: String,
+	/// The dependent task.
+	pub to: String,
+	/// The subtasks of `from` which `to` depends on, empty if it depends on the whole task.
+	#[serde(default, skip_serializing_if = "Vec::is_empty")]
+	pub subtasks: Vec<String>,
+	/// The curve the dependency is drawn as, absent if the roadline was not laid out.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub layout: Option<Curve>,
+}
+
+/// A cubic Bézier curve, in layout units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Curve {
+	pub start: Point,
+	pub control1: Point,
+	pub control2: Point,
+	pub end: Point,
+}
+
+/// A point, in layout units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Point {
+	pub x: u16,
+	pub y: u16,
+}
+
+impl RoadlineDocument {
+	/// Converts a roadline into the current version of the interchange format.
+	pub fn from_roadline(roadline: &Roadline) -> Self {
+		let range_algebra = roadline.range_algebra();
+		let graph = roadline.graph();
+		let (width, height) = roadline.visual_bounds();
+
+		let ids: BTreeSet<TaskId> = range_algebra.task_ids().copied().collect();
+		let tasks = ids
+			.iter()
+			.filter_map(|task_id| {
+				let task = graph.task(task_id)?;
+				let span = range_algebra.span(task_id)?;
+				let subtasks = Self::subtask_records(task, |subtask_id| {
+					range_algebra.subtask_end(task_id, subtask_id).map(|date| date.inner())
+				});
+
+				Some(TaskRecord {
+					id: task_label(task),
+					kind: task.kind(),
+					title: task.title().text.clone(),
+					summary: task.summary().text.clone(),
+					leads: task.leads().to_vec(),
+					labels: task.labels.iter().map(|label| label.as_str().to_string()).collect(),
+					subtasks,
+					progress: ProgressRecord {
+						status: task.progress.status(),
+						percent_complete: task.progress.percent_complete(),
+						started: task.progress.started(),
+						finished: task.progress.finished(),
+					},
+					span: SpanRecord {
+						start: span.start.inner().inner(),
+						end: span.end.inner().inner(),
+					},
+					layout: roadline.get_task_bounds(task_id).map(Rect::from_down_cell),
+				})
+			})
+			.collect();
+
+		// Dependencies are keyed by the task depended on and the dependent, as in the graph
+		let mut edges: BTreeMap<DependencyId, BTreeSet<SubtaskId>> = BTreeMap::new();
+		for task_id in &ids {
+			let Some(task) = graph.task(task_id) else { continue };
+			for dependency in task.depends_on() {
+				edges.entry(DependencyId::new(*dependency, *task_id)).or_default();
+			}
+			for dependency in task.subtask_dependencies() {
+				edges
+					.entry(DependencyId::new(dependency.task(), *task_id))
+					.or_default()
+					.insert(dependency.subtask());
+			}
+		}
+
+		let dependencies = edges
+			.into_iter()
+			.map(|(dependency_id, subtasks)| {
+				let from = graph
+					.task(&dependency_id.from())
+					.map_or_else(|| format!("T{}", dependency_id.from().value()), task_label);
+				let to = graph
+					.task(&dependency_id.to())
+					.map_or_else(|| format!("T{}", dependency_id.to().value()), task_label);
+				DependencyRecord {
+					subtasks: subtasks
+						.iter()
+						.map(|subtask_id| format!("{from}.{}", subtask_id.value()))
+						.collect(),
+					from,
+					to,
+					layout: roadline.get_connection(&dependency_id).map(|joint| {
+						let bezier = joint.bezier_connection();
+						Curve {
+							start: Point::from(&bezier.start),
+							control1: Point::from(&bezier.control1),
+							control2: Point::from(&bezier.control2),
+							end: Point::from(&bezier.end),
+						}
+					}),
+				}
+			})
+			.collect();
+
+		Self {
+			version: VERSION,
+			timezone: roadline.timezone(),
+			bounds: Bounds { width: width.value(), height: height.value() },
+			tasks,
+			dependencies,
+		}
+	}
+
+	/// The subtasks of a task, ordered by id.
+	fn subtask_records(
+		task: &Task,
+		end: impl Fn(&SubtaskId) -> Option<DateTime<Utc>>,
+	) -> Vec<SubtaskRecord> {
+		let mut subtasks = task.subtasks();
+		subtasks.sort_by_key(|subtask| *subtask.id());
+		subtasks
+			.into_iter()
+			.map(|subtask| {
+				let (status, completed) = match subtask.status() {
+					SubtaskStatus::Incomplete => (ProgressStatus::NotStarted, None),
+					SubtaskStatus::InProgress => (ProgressStatus::InProgress, None),
+					SubtaskStatus::Complete(date) => (ProgressStatus::Complete, date.date),
+				};
+				SubtaskRecord {
+					id: format!("{}.{}", task_label(task), subtask.id().value()),
+					title: subtask.title().text.clone(),
+					status,
+					completed,
+					lead: Some(subtask.lead().clone()).filter(|lead| !lead.is_unknown()),
+					co_leads: subtask.co_leads().to_vec(),
+					end: end(subtask.id()),
+				}
+			})
+			.collect()
+	}
+
+	/// Reads a document of any supported version, migrating it to the current version.
+	pub fn from_json(json: &str) -> Result<Self, InterchangeError> {
+		Self::from_value(serde_json::from_str(json)?)
+	}
+
+	/// Reads an already parsed document, see [`RoadlineDocument::from_json`].
+	pub fn from_value(value: Value) -> Result<Self, InterchangeError> {
+		Self::migrate(value, MIGRATIONS)
+	}
+
+	/// Migrates a document to the current version one version at a time, then validates it.
+	fn migrate(mut value: Value, migrations: &[Migration]) -> Result<Self, InterchangeError> {
+		let mut version = value
+			.get("version")
+			.and_then(Value::as_u64)
+			.ok_or(InterchangeError::MissingVersion)?;
+		if version > u64::from(VERSION) {
+			return Err(InterchangeError::UnsupportedVersion { version });
+		}
+
+		while version < u64::from(VERSION) {
+			let migration = migrations
+				.iter()
+				.find(|migration| u64::from(migration.from) == version)
+				.ok_or(InterchangeError::UnsupportedVersion { version })?;
+			log::info!("Migrating roadline.json from version {version}");
+			(migration.migrate)(&mut value)?;
+			version += 1;
+			value["version"] = Value::from(version);
+		}
+
+		let document: Self = serde_json::from_value(value)?;
+		document.validate()?;
+		Ok(document)
+	}
+
+	/// Checks what the schema cannot express: that ids are unique and well formed,
+	/// dependencies refer to tasks of the document and no task ends before it starts.
+	///
+	/// Tasks and milestones share their numbering, so `T3` and `M3` may not both appear.
+	pub fn validate(&self) -> Result<(), InterchangeError> {
+		let mut ids = BTreeSet::new();
+		let mut numbers = BTreeMap::new();
+		for task in &self.tasks {
+			if !ids.insert(task.id.as_str()) {
+				return Err(InterchangeError::DuplicateTask { id: task.id.clone() });
+			}
+			if let Some(other) = numbers.insert(parse_task_id(&task.id)?, task.id.as_str()) {
+				let (task, milestone) = match task.id.starts_with('M') {
+					true => (other.to_string(), task.id.clone()),
+					false => (task.id.clone(), other.to_string()),
+				};
+				return Err(InterchangeError::SharedTaskId { task, milestone });
+			}
+			if task.span.end < task.span.start {
+				return Err(InterchangeError::InvalidSpan { id: task.id.clone() });
+			}
+			for subtask in &task.subtasks {
+				parse_subtask_id(&task.id, &subtask.id)?;
+			}
+		}
+
+		for dependency in &self.dependencies {
+			if !ids.contains(dependency.from.as_str()) || !ids.contains(dependency.to.as_str()) {
+				return Err(InterchangeError::UnknownTask {
+					from: dependency.from.clone(),
+					to: dependency.to.clone(),
+				});
+			}
+			for subtask in &dependency.subtasks {
+				parse_subtask_id(&dependency.from, subtask)?;
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Writes the document as pretty-printed JSON.
+	pub fn to_json(&self) -> Result<String, InterchangeError> {
+		Ok(serde_json::to_string_pretty(self)?)
+	}
+
+	/// Saves the document as JSON to a file.
+	pub fn save(&self, path: impl AsRef<Path>) -> Result<(), InterchangeError> {
+		Ok(std::fs::write(path, self.to_json()?)?)
+	}
+
+	/// Loads a document of any supported version from a JSON file.
+	pub fn load(path: impl AsRef<Path>) -> Result<Self, InterchangeError> {
+		Self::from_json(&std::fs::read_to_string(path)?)
+	}
+
+	/// The task with the given id, e.g. `T3`.
+	pub fn task(&self, id: &str) -> Option<&TaskRecord> {
+		self.tasks.iter().find(|task| task.id == id)
+	}
+}
+
+impl Rect {
+	fn from_down_cell(cell: &DownCell) -> Self {
+		let stretch = cell.down_stretch().range();
+		let lane = cell.down_lane().range();
+		Self {
+			x: stretch.start().value(),
+			y: lane.start().value(),
+			// Trimmed stretches of short tasks may end before they start
+			width: stretch.end().value().saturating_sub(stretch.start().value()),
+			height: lane.end().value().saturating_sub(lane.start().value()),
+			lane: cell.down_lane().lane_id().value(),
+		}
+	}
+}
+
+impl From<&ConnectionPoint> for Point {
+	fn from(point: &ConnectionPoint) -> Self {
+		Self { x: point.x.value(), y: point.y.value() }
+	}
+}
+
+/// A task as `T3`, or `M3` for a milestone.
+fn task_label(task: &Task) -> String {
+	format!("{}{}", task.kind().prefix(), task.id().value())
+}
+
+/// Parses a task id such as `T3` or `M3`.
+pub fn parse_task_id(id: &str) -> Result<TaskId, InterchangeError> {
+	id.strip_prefix(['T', 'M'])
+		.and_then(parse_number)
+		.map(TaskId::new)
+		.ok_or_else(|| InterchangeError::InvalidTaskId { id: id.to_string() })
+}
+
+/// Parses the id of a subtask of the given task, such as `T3.1`.
+pub fn parse_subtask_id(task: &str, id: &str) -> Result<SubtaskId, InterchangeError> {
+	id.strip_prefix(task)
+		.and_then(|rest| rest.strip_prefix('.'))
+		.and_then(parse_number)
+		.map(SubtaskId::new)
+		.ok_or_else(|| InterchangeError::InvalidSubtaskId {
+			id: id.to_string(),
+			task: task.to_string(),
+		})
+}
+
+/// Parses the number of an id, from 0 to 255, written without a sign or leading zeros.
+fn parse_number(number: &str) -> Option<u8> {
+	number.parse::<u8>().ok().filter(|parsed| parsed.to_string() == number)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::roadline::RoadlineBuilder;
+	use roadline_util::task::subtask::{Content, Position, StatusDate, Subtask, Title};
+	use roadline_util::task::{EmbeddedSubtask, Progress, SubtaskDependency};
+	use std::time::Duration as StdDuration;
+
+	const DAY: u64 = 24 * 60 * 60;
+
+	fn test_roadline() -> Result<Roadline, anyhow::Error> {
+		let mut builder = RoadlineBuilder::start_of_epoch()?;
+
+		let subtask = Subtask::new(
+			SubtaskId::new(1),
+			Position::new(0),
+			Title { text: "Draft".to_string() },
+			Content::new("Draft".to_string()),
+			SubtaskStatus::Complete(StatusDate::undated()),
+			Lead::unknown(),
+		);
+		let mut task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(10 * DAY));
+		task1.subtasks_mut().insert(EmbeddedSubtask::new(subtask));
+
+		// T10 sorts after T2 by id, but before it by name
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(5 * DAY))
+			.with_dependencies([1]);
+		let task10 = Task::test_from_id(10)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(3 * DAY))
+			.with_subtask_dependencies([SubtaskDependency::new(TaskId::new(1), SubtaskId::new(1))]);
+
+		builder.add_task(task10)?;
+		builder.add_task(task2)?;
+		builder.add_task(task1)?;
+
+		Ok(builder.build()?)
+	}
+
+	#[test]
+	fn test_document_round_trip() -> Result<(), anyhow::Error> {
+		let roadline = test_roadline()?;
+		let document = RoadlineDocument::from_roadline(&roadline);
+
+		// Tasks are ordered by id rather than by name or hash
+		let ids: Vec<&str> = document.tasks.iter().map(|task| task.id.as_str()).collect();
+		assert_eq!(ids, ["T1", "T2", "T10"]);
+		let edges: Vec<(&str, &str)> = document
+			.dependencies
+			.iter()
+			.map(|dependency| (dependency.from.as_str(), dependency.to.as_str()))
+			.collect();
+		assert_eq!(edges, [("T1", "T2"), ("T1", "T10")]);
+		assert_eq!(document.dependencies[1].subtasks.len(), 1);
+		assert!(document.dependencies.iter().all(|dependency| dependency.layout.is_some()));
+
+		// A subtask checked off without a date is complete on no particular date
+		let t1 = document.task("T1").ok_or(anyhow::anyhow!("T1 should be in the document"))?;
+		assert_eq!(t1.subtasks[0].status, ProgressStatus::Complete);
+		assert_eq!(t1.subtasks[0].completed, None);
+
+		let t2 = document.task("T2").ok_or(anyhow::anyhow!("T2 should be in the document"))?;
+		assert_eq!(t2.span.start.to_rfc3339(), "1970-01-11T00:00:00+00:00");
+		assert!(t2.layout.is_some());
+
+		// The output is deterministic and reads back to the same document
+		let json = document.to_json()?;
+		assert_eq!(RoadlineDocument::from_roadline(&roadline).to_json()?, json);
+		assert!(!json.contains("ShortId"));
+		assert_eq!(RoadlineDocument::from_json(&json)?, document);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_load_migrates_and_validates() -> Result<(), anyhow::Error> {
+		let document = RoadlineDocument::from_roadline(&test_roadline()?);
+
+		// A document one version behind is migrated, here from a format which called spans dates
+		fn rename_dates(value: &mut Value) -> Result<(), InterchangeError> {
+			for task in value["tasks"].as_array_mut().into_iter().flatten() {
+				if let Some(dates) = task.as_object_mut().and_then(|task| task.remove("dates")) {
+					task["span"] = dates;
+				}
+			}
+			Ok(())
+		}
+		let mut older = serde_json::to_value(&document)?;
+		older["version"] = Value::from(VERSION - 1);
+		for task in older["tasks"].as_array_mut().into_iter().flatten() {
+			if let Some(span) = task.as_object_mut().and_then(|task| task.remove("span")) {
+				task["dates"] = span;
+			}
+		}
+		let migrations = [Migration { from: VERSION - 1, migrate: rename_dates }];
+		assert_eq!(RoadlineDocument::migrate(older.clone(), &migrations)?, document);
+		assert!(matches!(
+			RoadlineDocument::from_value(older),
+			Err(InterchangeError::UnsupportedVersion { .. })
+		));
+
+		let mut newer = serde_json::to_value(&document)?;
+		newer["version"] = Value::from(VERSION + 1);
+		assert!(matches!(
+			RoadlineDocument::from_value(newer),
+			Err(InterchangeError::UnsupportedVersion { .. })
+		));
+
+		let mut unknown_field = serde_json::to_value(&document)?;
+		unknown_field["tasks"][0]["colour"] = Value::from("red");
+		assert!(matches!(
+			RoadlineDocument::from_value(unknown_field),
+			Err(InterchangeError::Json(_))
+		));
+
+		let mut dangling = document.clone();
+		dangling.dependencies[0].to = "T7".to_string();
+		assert!(matches!(dangling.validate(), Err(InterchangeError::UnknownTask { .. })));
+
+		let mut duplicate = document.clone();
+		duplicate.tasks[1].id = "T1".to_string();
+		assert!(matches!(duplicate.validate(), Err(InterchangeError::DuplicateTask { .. })));
+
+		// Tasks and milestones share their numbering
+		let mut shared = document.clone();
+		shared.tasks[2].id = "M2".to_string();
+		assert!(matches!(
+			shared.validate(),
+			Err(InterchangeError::SharedTaskId { task, milestone }) if task == "T2" && milestone == "M2"
+		));
+
+		Ok(())
+	}
+
+	/// Checks `value` against a node of the schema, supporting the keywords the schema uses.
+	fn check(value: &Value, node: &Value, root: &Value, path: &str) -> Result<(), String> {
+		let Some(node) = node.as_object() else {
+			return Err(format!("{path}: schema node is not an object"));
+		};
+		let fail = |reason: &str| Err(format!("{path}: {reason} in {value}"));
+
+		for (keyword, expected) in node {
+			match keyword.as_str() {
+				"$schema" | "title" | "description" | "$defs" => {}
+				"$ref" => {
+					let name = expected.as_str().and_then(|r| r.strip_prefix("#/$defs/"));
+					let target = name.and_then(|name| root["$defs"].get(name));
+					let target = target.ok_or(format!("{path}: unknown reference {expected}"))?;
+					check(value, target, root, path)?;
+				}
+				"type" => {
+					let matches = match expected.as_str() {
+						Some("object") => value.is_object(),
+						Some("array") => value.is_array(),
+						Some("string") => value.is_string(),
+						Some("integer") => value.is_u64() || value.is_i64(),
+						_ => return Err(format!("{path}: unsupported type {expected}")),
+					};
+					if !matches {
+						return fail(&format!("expected {expected}"));
+					}
+				}
+				"const" => {
+					if value != expected {
+						return fail(&format!("expected {expected}"));
+					}
+				}
+				"enum" => {
+					if !expected.as_array().is_some_and(|values| values.contains(value)) {
+						return fail(&format!("expected one of {expected}"));
+					}
+				}
+				"minimum" | "maximum" => {
+					let (Some(number), Some(bound)) = (value.as_f64(), expected.as_f64()) else {
+						return fail("expected a number");
+					};
+					if (keyword == "minimum" && number < bound)
+						|| (keyword == "maximum" && number > bound)
+					{
+						return fail(&format!("out of the {keyword} {bound}"));
+					}
+				}
+				"pattern" => {
+					let pattern = regex::Regex::new(expected.as_str().unwrap_or_default())
+						.map_err(|error| format!("{path}: {error}"))?;
+					if !value.as_str().is_some_and(|text| pattern.is_match(text)) {
+						return fail(&format!("does not match {expected}"));
+					}
+				}
+				"format" if expected == "date-time" => {
+					if value
+						.as_str()
+						.and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+						.is_none()
+					{
+						return fail("expected an RFC 3339 date");
+					}
+				}
+				"required" => {
+					for field in expected.as_array().into_iter().flatten().filter_map(Value::as_str)
+					{
+						if value.get(field).is_none() {
+							return fail(&format!("missing {field}"));
+						}
+					}
+				}
+				"properties" => {
+					let properties =
+						expected.as_object().ok_or(format!("{path}: bad properties"))?;
+					for (field, field_value) in value.as_object().into_iter().flatten() {
+						match properties.get(field) {
+							Some(field_node) => {
+								check(field_value, field_node, root, &format!("{path}.{field}"))?;
+							}
+							None if node.get("additionalProperties")
+								== Some(&Value::Bool(false)) =>
+							{
+								return fail(&format!("unexpected field {field}"));
+							}
+							None => {}
+						}
+					}
+				}
+				"additionalProperties" if expected == &Value::Bool(false) => {}
+				"items" => {
+					for (index, item) in value.as_array().into_iter().flatten().enumerate() {
+						check(item, expected, root, &format!("{path}[{index}]"))?;
+					}
+				}
+				_ => return Err(format!("{path}: unsupported keyword {keyword}: {expected}")),
+			}
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_documents_match_schema() -> Result<(), anyhow::Error> {
+		let mut builder = RoadlineBuilder::start_of_epoch()?;
+		let ada = Lead::new("Ada".to_string(), "ada@example.com".to_string());
+		let grace = Lead::new("Grace".to_string(), "grace@example.com".to_string());
+
+		// Every optional field is set, so that none of them escapes the schema
+		let subtask = Subtask::new(
+			SubtaskId::new(1),
+			Position::new(0),
+			Title { text: "Draft".to_string() },
+			Content::new("Draft".to_string()),
+			SubtaskStatus::Complete(StatusDate::on(DateTime::UNIX_EPOCH)),
+			ada.clone(),
+		)
+		.with_co_leads(vec![grace]);
+		let mut task1 = Task::test_from_id(1)?
+			.for_standard_duration(StdDuration::from_secs(10 * DAY))
+			.with_leads([ada])
+			.with_labels(["infra"])
+			.with_progress(
+				Progress::new().with_started(DateTime::UNIX_EPOCH).with_percent_complete(40),
+			);
+		task1.summary_mut().text = "The first task".to_string();
+		task1.subtasks_mut().insert(EmbeddedSubtask::new(subtask));
+		let milestone = Task::test_from_id(255)?
+			.with_kind(Kind::Milestone)
+			.after(&task1)
+			.for_standard_duration(StdDuration::ZERO)
+			.with_subtask_dependencies([SubtaskDependency::new(TaskId::new(1), SubtaskId::new(1))]);
+		builder.add_task(task1)?;
+		builder.add_task(milestone)?;
+
+		let schema = schema();
+		for roadline in [builder.build()?, test_roadline()?] {
+			let document = serde_json::to_value(RoadlineDocument::from_roadline(&roadline))?;
+			check(&document, &schema, &schema, "$").map_err(anyhow::Error::msg)?;
+		}
+
+		// Ids beyond the range of task numbers are rejected by the schema and the reader
+		let mut document =
+			serde_json::to_value(RoadlineDocument::from_roadline(&test_roadline()?))?;
+		document["tasks"][0]["id"] = Value::from("T256");
+		assert!(check(&document, &schema, &schema, "$").is_err());
+		assert!(matches!(
+			RoadlineDocument::from_value(document),
+			Err(InterchangeError::InvalidTaskId { .. })
+		));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_schema_is_up_to_date() -> Result<(), anyhow::Error> {
+		// Regenerate with `UPDATE_SCHEMA=1 cargo test -p roadline-representation-core schema`
+		let path = concat!(env!("CARGO_MANIFEST_DIR"), "/schema/roadline.schema.json");
+		if std::env::var_os("UPDATE_SCHEMA").is_some() {
+			std::fs::write(path, format!("{}\n", serde_json::to_string_pretty(&schema())?))?;
+		}
+
+		let checked_in: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+		assert_eq!(checked_in, schema());
+
+		Ok(())
+	}
+}
@@ -0,0 +1,178 @@
+//! The JSON Schema of the `roadline.json` interchange format.
+//!
+//! The schema is checked in at `core/representation/schema/roadline.schema.json`, and a test
+//! keeps that copy in line with [`schema`].
+
+use super::VERSION;
+use serde_json::{json, Value};
+
+/// A number from 0 to 255 without leading zeros, the range of task and subtask numbers.
+const NUMBER: &str = "(25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])";
+
+/// A task id such as `T3` or `M3`.
+fn task_id() -> String {
+	format!("^[TM]{NUMBER}$")
+}
+
+/// A subtask id such as `T3.1`.
+fn subtask_id() -> String {
+	format!("^[TM]{NUMBER}\\.{NUMBER}$")
+}
+
+/// The JSON Schema (draft 2020-12) of the current version of `roadline.json`.
+pub fn schema() -> Value {
+	json!({
+		"$schema": "https://json-schema.org/draft/2020-12/schema",
+		"title": "roadline.json",
+		"description": "A roadline with its tasks, dependencies, computed spans and layout. Tasks are ordered by id and dependencies by the task depended on, then the dependent. Dates are RFC 3339 instants in UTC; display them in `timezone`. Layout is in abstract units, x growing with time and y downwards.",
+		"type": "object",
+		"required": ["version", "timezone", "bounds", "tasks", "dependencies"],
+		"additionalProperties": false,
+		"properties": {
+			"version": { "const": VERSION },
+			"timezone": {
+				"description": "The IANA timezone the roadmap is planned in, e.g. `Europe/Berlin`.",
+				"type": "string"
+			},
+			"bounds": {
+				"type": "object",
+				"required": ["width", "height"],
+				"additionalProperties": false,
+				"properties": {
+					"width": { "$ref": "#/$defs/unit" },
+					"height": { "$ref": "#/$defs/unit" }
+				}
+			},
+			"tasks": { "type": "array", "items": { "$ref": "#/$defs/task" } },
+			"dependencies": { "type": "array", "items": { "$ref": "#/$defs/dependency" } }
+		},
+		"$defs": definitions()
+	})
+}
+
+/// The definitions shared by the schema, split up to stay within the macro recursion limit.
+fn definitions() -> Value {
+	let mut definitions = json!({
+		"unit": { "type": "integer", "minimum": 0, "maximum": 65535 },
+		"date": { "type": "string", "format": "date-time" },
+		"task_id": { "type": "string", "pattern": task_id() },
+		"subtask_id": { "type": "string", "pattern": subtask_id() },
+		"status": { "enum": ["not_started", "in_progress", "complete"] },
+		"lead": {
+			"type": "object",
+			"required": ["name", "email"],
+			"additionalProperties": false,
+			"properties": {
+				"name": { "type": "string" },
+				"email": { "type": "string" }
+			}
+		},
+		"point": {
+			"type": "object",
+			"required": ["x", "y"],
+			"additionalProperties": false,
+			"properties": {
+				"x": { "$ref": "#/$defs/unit" },
+				"y": { "$ref": "#/$defs/unit" }
+			}
+		}
+	});
+	definitions["task"] = task();
+	definitions["subtask"] = subtask();
+	definitions["dependency"] = dependency();
+	definitions
+}
+
+fn task() -> Value {
+	json!({
+		"type": "object",
+		"required": ["id", "kind", "title", "progress", "span"],
+		"additionalProperties": false,
+		"properties": {
+			"id": { "$ref": "#/$defs/task_id" },
+			"kind": { "enum": ["task", "milestone"] },
+			"title": { "type": "string" },
+			"summary": { "type": "string" },
+			"leads": { "type": "array", "items": { "$ref": "#/$defs/lead" } },
+			"labels": { "type": "array", "items": { "type": "string" } },
+			"subtasks": { "type": "array", "items": { "$ref": "#/$defs/subtask" } },
+			"progress": {
+				"type": "object",
+				"required": ["status", "percent_complete"],
+				"additionalProperties": false,
+				"properties": {
+					"status": { "$ref": "#/$defs/status" },
+					"percent_complete": { "type": "integer", "minimum": 0, "maximum": 100 },
+					"started": { "$ref": "#/$defs/date" },
+					"finished": { "$ref": "#/$defs/date" }
+				}
+			},
+			"span": {
+				"description": "When the task is planned to start and end.",
+				"type": "object",
+				"required": ["start", "end"],
+				"additionalProperties": false,
+				"properties": {
+					"start": { "$ref": "#/$defs/date" },
+					"end": { "$ref": "#/$defs/date" }
+				}
+			},
+			"layout": {
+				"description": "The rectangle the task is drawn in.",
+				"type": "object",
+				"required": ["x", "y", "width", "height", "lane"],
+				"additionalProperties": false,
+				"properties": {
+					"x": { "$ref": "#/$defs/unit" },
+					"y": { "$ref": "#/$defs/unit" },
+					"width": { "$ref": "#/$defs/unit" },
+					"height": { "$ref": "#/$defs/unit" },
+					"lane": { "type": "integer", "minimum": 0, "maximum": 255 }
+				}
+			}
+		}
+	})
+}
+
+fn subtask() -> Value {
+	json!({
+		"type": "object",
+		"required": ["id", "title", "status"],
+		"additionalProperties": false,
+		"properties": {
+			"id": { "$ref": "#/$defs/subtask_id" },
+			"title": { "type": "string" },
+			"status": { "$ref": "#/$defs/status" },
+			"completed": { "$ref": "#/$defs/date" },
+			"lead": { "$ref": "#/$defs/lead" },
+			"co_leads": { "type": "array", "items": { "$ref": "#/$defs/lead" } },
+			"end": { "$ref": "#/$defs/date" }
+		}
+	})
+}
+
+fn dependency() -> Value {
+	json!({
+		"description": "`to` depends on `from`, or on the listed subtasks of it.",
+		"type": "object",
+		"required": ["from", "to"],
+		"additionalProperties": false,
+		"properties": {
+			"from": { "$ref": "#/$defs/task_id" },
+			"to": { "$ref": "#/$defs/task_id" },
+			"subtasks": { "type": "array", "items": { "$ref": "#/$defs/subtask_id" } },
+			"layout": {
+				"description": "The cubic Bézier curve the dependency is drawn as.",
+				"type": "object",
+				"required": ["start", "control1", "control2", "end"],
+				"additionalProperties": false,
+				"properties": {
+					"start": { "$ref": "#/$defs/point" },
+					"control1": { "$ref": "#/$defs/point" },
+					"control2": { "$ref": "#/$defs/point" },
+					"end": { "$ref": "#/$defs/point" }
+				}
+			}
+		}
+	})
+}
@@ -2,11 +2,18 @@
 #![warn(clippy::expect_used)]
 
 pub mod arena;
+pub mod baseline;
+pub mod calendar;
+pub mod dot;
 pub mod graph;
 pub mod grid_algebra;
+pub mod interchange;
 pub mod range_algebra;
 pub mod reified;
 pub mod roadline;
+pub mod simulation;
+pub mod timezone;
+pub mod workload;
 
 pub use roadline::*;
 pub use roadline_util::*;
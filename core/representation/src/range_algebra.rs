@@ -1,10 +1,17 @@
+pub mod explain;
 pub mod span;
+pub mod variance;
 
 pub use span::Date;
 pub use span::Span;
+pub use variance::Variance;
 
+use crate::calendar::Calendars;
 use crate::graph::Graph;
+use crate::timezone::Timezone;
 use roadline_util::dependency::{Dependency, Id as DependencyId};
+use roadline_util::task::range::Direction;
+use roadline_util::task::subtask::{Id as SubtaskId, Status, StatusDate};
 use roadline_util::task::{id::Id as TaskId, Task};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,7 +31,22 @@ pub enum RangeAlgebraError {
 	#[error("Root task {task_id:?} must reference itself with +0 offset")]
 	InvalidRootRange { task_id: TaskId },
 	#[error("Task {task_id:?} dependency not satisfied: dependency {dependency_id:?} must end before task starts")]
-	TooEarlyForDependency { task_id: TaskId, dependency_id: TaskId },
+	TooEarlyForDependency {
+		task_id: TaskId,
+		dependency_id: TaskId,
+		task_start: Date,
+		dependency_end: Date,
+	},
+	#[error("Task {task_id:?} depends on subtask {subtask_id:?} of {dependency_id:?}, which does not exist")]
+	SubtaskNotFound { task_id: TaskId, dependency_id: TaskId, subtask_id: SubtaskId },
+	#[error("Task {task_id:?} subtask dependency not satisfied: subtask {subtask_id:?} of {dependency_id:?} must end before task starts")]
+	TooEarlyForSubtask {
+		task_id: TaskId,
+		dependency_id: TaskId,
+		subtask_id: SubtaskId,
+		task_start: Date,
+		subtask_end: Date,
+	},
 	#[error("No root tasks found in graph")]
 	NoRootTasks,
 	#[error("Root task {task_id:?} has invalid offset: {offset:?}. Only root tasks can self-reference their start date")]
@@ -56,12 +78,191 @@ fn format_cycles(cycles: &[Vec<TaskId>]) -> String {
 }
 
 /// Adds a duration to a date, returning a new date.
-fn add_duration_to_date(date: Date, duration: std::time::Duration) -> Date {
+pub(crate) fn add_duration_to_date(date: Date, duration: std::time::Duration) -> Date {
 	let datetime = date.inner();
 	let duration_chrono = chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::zero()); // Fallback for invalid duration
 	Date::new(datetime + duration_chrono)
 }
 
+/// Helper function to move a date forwards or backwards by a duration.
+pub(crate) fn offset_date(date: Date, duration: std::time::Duration, direction: Direction) -> Date {
+	match direction {
+		Direction::After => add_duration_to_date(date, duration),
+		Direction::Before => {
+			let duration_chrono =
+				chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::zero()); // Fallback for invalid duration
+			Date::new(date.inner() - duration_chrono)
+		}
+	}
+}
+
+/// Computes when a subtask ends, given the span of its task.
+///
+/// A subtask completed on a known date ends on that date. Otherwise it ends at its declared
+/// offset from the start of the task, or else together with the task.
+pub(crate) fn subtask_end_date(task: &Task, span: &Span, subtask_id: &SubtaskId) -> Option<Date> {
+	let subtask = task.subtask(subtask_id)?;
+
+	match (subtask.status(), subtask.end()) {
+		(Status::Complete(StatusDate { date: Some(completed) }), _) => Some(Date::new(*completed)),
+		(_, Some(end)) => {
+			let duration: roadline_util::duration::Duration = end.clone().into();
+			Some(add_duration_to_date(span.start.inner(), duration.into()))
+		}
+		_ => Some(span.end.inner()),
+	}
+}
+
+/// The spans of tasks pushed through the graph by [`forward_pass`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ForwardPass {
+	/// The span of each task.
+	pub spans: HashMap<TaskId, Span>,
+	/// The task which drove the start of each task, if any.
+	pub drivers: HashMap<TaskId, Option<TaskId>>,
+}
+
+impl ForwardPass {
+	/// The tasks which drove the start of the task which ends last, from the first of them to
+	/// that task.
+	pub fn critical_path(&self) -> Vec<TaskId> {
+		let last = self.spans.iter().max_by_key(|(task_id, span)| (span.end.inner(), *task_id));
+
+		let mut path = Vec::new();
+		let mut current = last.map(|(task_id, _)| *task_id);
+		while let Some(task_id) = current {
+			path.push(task_id);
+			current = self.drivers.get(&task_id).copied().flatten();
+		}
+
+		path.reverse();
+		path
+	}
+}
+
+/// Pushes tasks through the graph in topological order, ending each task at the date `end`
+/// gives for its start.
+///
+/// Unlike [`PreRangeAlgebra::compute`], a dependency which ends after a task was meant to
+/// start does not fail; the task waits for it instead. A task which actually started or
+/// finished keeps those dates, whatever its plan and dependencies, and a task which finished
+/// before its forecast start is taken to have started by the time it finished.
+pub(crate) fn forward_pass(
+	graph: &Graph,
+	order: &[TaskId],
+	root_date: Date,
+	calendars: &Calendars,
+	timezone: Timezone,
+	mut end: impl FnMut(&Task, Date) -> Date,
+) -> Result<ForwardPass, RangeAlgebraError> {
+	let mut pass = ForwardPass {
+		spans: HashMap::with_capacity(order.len()),
+		drivers: HashMap::with_capacity(order.len()),
+	};
+
+	for task_id in order {
+		let task = graph
+			.task(task_id)
+			.ok_or(RangeAlgebraError::TaskNotFound { task_id: *task_id })?;
+
+		// Start relative to the reference, as planned
+		let (mut start_date, mut driver) = if task.is_root() {
+			(planned_start(calendars, timezone, task, root_date), None)
+		} else {
+			let reference_id: TaskId = task.range.start.point_of_reference().clone().into();
+			let reference = pass
+				.spans
+				.get(&reference_id)
+				.ok_or(RangeAlgebraError::InvalidReference { task_id: *task_id, reference_id })?;
+			let start_date = planned_start(calendars, timezone, task, reference.start.inner());
+			(start_date, Some(reference_id))
+		};
+
+		// Wait for dependencies which end later than planned
+		for dependency_id in task.depends_on() {
+			if let Some(dependency) = pass.spans.get(dependency_id) {
+				if dependency.end.inner() > start_date {
+					start_date = dependency.end.inner();
+					driver = Some(*dependency_id);
+				}
+			}
+		}
+		for dependency in task.subtask_dependencies() {
+			let dependency_id = dependency.task();
+			let end = pass.spans.get(&dependency_id).and_then(|span| {
+				subtask_end_date(graph.task(&dependency_id)?, span, &dependency.subtask())
+			});
+			if let Some(end) = end.filter(|end| *end > start_date) {
+				start_date = end;
+				driver = Some(dependency_id);
+			}
+		}
+
+		// Actual dates override the forecast
+		let progress = task.progress();
+		if let Some(started) = progress.started() {
+			start_date = Date::new(started);
+			driver = None;
+		}
+		let end_date = match progress.finished() {
+			Some(finished) => {
+				let finished = Date::new(finished);
+				start_date = start_date.min(finished);
+				finished
+			}
+			None => end(task, start_date),
+		};
+
+		pass.spans
+			.insert(*task_id, Span::new(span::Start::new(start_date), span::End::new(end_date)));
+		pass.drivers.insert(*task_id, driver);
+	}
+
+	Ok(pass)
+}
+
+/// The planned start of a task whose point of reference starts at `reference`.
+///
+/// An offset of working days is resolved through the calendar of the task, and any other
+/// offset is wall-clock time, as in [`planned_end`].
+pub(crate) fn planned_start(
+	calendars: &Calendars,
+	timezone: Timezone,
+	task: &Task,
+	reference: Date,
+) -> Date {
+	let start = task.range.start();
+	match start.workdays() {
+		Some(workdays) => {
+			let calendar = calendars.for_task(task);
+			match start.direction() {
+				Direction::After => calendar.add_workdays_in(reference, workdays, timezone),
+				Direction::Before => calendar.subtract_workdays_in(reference, workdays, timezone),
+			}
+		}
+		None => offset_date(reference, (*start.duration()).into(), start.direction()),
+	}
+}
+
+/// The planned end of a task starting at `start`.
+///
+/// A duration of working days is resolved through the calendar of the task, and any other
+/// duration is wall-clock time. Working days are the days of the roadmap timezone.
+pub(crate) fn planned_end(
+	calendars: &Calendars,
+	timezone: Timezone,
+	task: &Task,
+	start: Date,
+) -> Date {
+	match task.range.workdays() {
+		Some(workdays) => calendars.for_task(task).add_workdays_in(start, workdays, timezone),
+		None => {
+			let end: roadline_util::duration::Duration = task.range.end.clone().into();
+			add_duration_to_date(start, end.into())
+		}
+	}
+}
+
 /// A mutable structure used to compute the range algebra of a graph.
 /// Does not provide access to computed spans to prevent modification.
 /// Must be consumed to create a `RangeAlgebra` for safe access to spans.
@@ -71,15 +272,38 @@ pub struct PreRangeAlgebra {
 	graph: Graph,
 	/// Internal spans storage - not accessible externally.
 	spans: HashMap<TaskId, Span>,
+	/// The working calendars durations in working days are resolved with.
+	calendars: Calendars,
+	/// The timezone calendar days are resolved in.
+	timezone: Timezone,
 }
 
 impl PreRangeAlgebra {
 	pub fn new(graph: Graph) -> Self {
-		Self { graph, spans: HashMap::new() }
+		Self::with_capacity(graph, 0)
 	}
 
 	pub fn with_capacity(graph: Graph, capacity: usize) -> Self {
-		Self { graph, spans: HashMap::with_capacity(capacity) }
+		Self {
+			graph,
+			spans: HashMap::with_capacity(capacity),
+			calendars: Calendars::default(),
+			timezone: Timezone::default(),
+		}
+	}
+
+	/// Sets the working calendars durations in working days are resolved with.
+	///
+	/// Defaults to Monday to Friday without holidays.
+	pub fn with_calendars(self, calendars: Calendars) -> Self {
+		Self { calendars, ..self }
+	}
+
+	/// Sets the timezone calendar days, such as working days, are resolved in.
+	///
+	/// Defaults to UTC.
+	pub fn with_timezone(self, timezone: Timezone) -> Self {
+		Self { timezone, ..self }
 	}
 
 	pub fn graph(&self) -> &Graph {
@@ -102,7 +326,8 @@ impl PreRangeAlgebra {
 	///    b. Compute start date based on reference task
 	///    c. Compute end date by adding duration to start date
 	///    d. Validate that all dependencies end before this task starts
-	/// 4. Store computed spans and return immutable RangeAlgebra
+	/// 4. Forecast spans from the actual progress of tasks, see [`RangeAlgebra::forecast`]
+	/// 5. Store computed spans and return immutable RangeAlgebra
 	pub fn compute(mut self, root_date: Date) -> Result<RangeAlgebra, RangeAlgebraError> {
 		// Clear existing spans
 		self.spans.clear();
@@ -118,7 +343,7 @@ impl PreRangeAlgebra {
 
 		// Process tasks in topological order, collecting all errors
 		let mut errors = Vec::new();
-		for task_id in topo_order {
+		for task_id in topo_order.iter().copied() {
 			if let Err(e) = self.compute_task_span(task_id, root_date) {
 				errors.push(e);
 			}
@@ -129,8 +354,27 @@ impl PreRangeAlgebra {
 			return Err(RangeAlgebraError::Multiple { errors });
 		}
 
+		// Forecast from the actual progress of tasks
+		let (calendars, timezone) = (&self.calendars, self.timezone);
+		let forecasts = forward_pass(
+			&self.graph,
+			&topo_order,
+			root_date,
+			calendars,
+			timezone,
+			|task, start| planned_end(calendars, timezone, task, start),
+		)?;
+		let critical_path = forecasts.critical_path();
+
 		// Consume self and return immutable RangeAlgebra
-		Ok(RangeAlgebra { graph: self.graph, spans: self.spans })
+		Ok(RangeAlgebra {
+			graph: self.graph,
+			spans: self.spans,
+			forecasts: forecasts.spans,
+			critical_path,
+			calendars: self.calendars,
+			timezone: self.timezone,
+		})
 	}
 
 	/// Computes the span for a single task based on its range specification.
@@ -146,28 +390,18 @@ impl PreRangeAlgebra {
 			.get(&task_id)
 			.ok_or(RangeAlgebraError::TaskNotFound { task_id })?;
 
-		// Extract range components
-		// Start duration
-		let start_target_date = task.range.start.clone().into(); // Convert Start to TargetDate
-		let start_duration: roadline_util::duration::Duration =
-			task.range.start.duration().clone().into(); // Convert Start to Duration
-
-		// End duration
-		let end_duration: roadline_util::duration::Duration = task.range.end.clone().into(); // Convert End to Duration
-		let end_duration: std::time::Duration = end_duration.into(); // Convert to std Duration
-
 		// Compute start date
 		let start_date = if task.is_root() {
 			// Root tasks will ignore the reference and simply offset from the root date
 			// This has the side-effect of allowing self-reference, which some users may prefer.
-			add_duration_to_date(root_date, start_duration.into())
+			planned_start(&self.calendars, self.timezone, task, root_date)
 		} else {
 			// For non-root tasks, use the reference and offset
-			self.compute_non_root_start_date(&start_target_date, &task_id)?
+			self.compute_non_root_start_date(task)?
 		};
 
-		// Compute end date by adding duration to start date
-		let end_date = add_duration_to_date(start_date, end_duration);
+		// Compute end date by adding duration, or working days, to start date
+		let end_date = planned_end(&self.calendars, self.timezone, task, start_date);
 
 		// Validate dependencies are satisfied
 		self.validate_dependencies(task, start_date)?;
@@ -180,11 +414,9 @@ impl PreRangeAlgebra {
 	}
 
 	/// Computes the start date for a task based on its TargetDate specification.
-	fn compute_non_root_start_date(
-		&self,
-		target_date: &roadline_util::task::range::TargetDate,
-		task_id: &TaskId,
-	) -> Result<Date, RangeAlgebraError> {
+	fn compute_non_root_start_date(&self, task: &Task) -> Result<Date, RangeAlgebraError> {
+		let task_id = task.id();
+		let target_date = &task.range.start.0;
 		let reference_id: TaskId = target_date.point_of_reference.clone().into();
 		let duration: roadline_util::duration::Duration = target_date.duration.clone().into(); // Convert to Duration
 		let duration: std::time::Duration = duration.into(); // Convert to std::time::Duration
@@ -203,9 +435,9 @@ impl PreRangeAlgebra {
 			.get(&reference_id)
 			.ok_or(RangeAlgebraError::InvalidReference { task_id: *task_id, reference_id })?;
 
-		// Start date = reference task's end date + offset duration
+		// Start date = reference task's start date +/- offset duration
 		let reference_start_date = reference_span.start.inner();
-		Ok(add_duration_to_date(reference_start_date, duration))
+		Ok(planned_start(&self.calendars, self.timezone, task, reference_start_date))
 	}
 
 	/// Validates that all dependencies of a task end before the task starts.
@@ -221,6 +453,11 @@ impl PreRangeAlgebra {
 		let mut errors = Vec::new();
 
 		for dep_id in dependencies {
+			// Edges which only stand for subtask dependencies are checked below
+			if !task.depends_on().contains(&dep_id) {
+				continue;
+			}
+
 			let dep_span = match self.spans.get(&dep_id) {
 				Some(span) => span,
 				None => {
@@ -234,10 +471,40 @@ impl PreRangeAlgebra {
 				errors.push(RangeAlgebraError::TooEarlyForDependency {
 					task_id,
 					dependency_id: dep_id,
+					task_start: task_start_date,
+					dependency_end: dep_span.end.inner(),
 				});
 			}
 		}
 
+		// A subtask dependency only requires the subtask to have ended
+		for dependency in task.subtask_dependencies() {
+			let dependency_id = dependency.task();
+			let subtask_id = dependency.subtask();
+			let subtask_end = self.spans.get(&dependency_id).and_then(|span| {
+				let dependency_task = self.graph.task(&dependency_id)?;
+				subtask_end_date(dependency_task, span, &subtask_id)
+			});
+
+			match subtask_end {
+				Some(end) if end.inner() > task_start_date.inner() => {
+					errors.push(RangeAlgebraError::TooEarlyForSubtask {
+						task_id,
+						dependency_id,
+						subtask_id,
+						task_start: task_start_date,
+						subtask_end: end,
+					});
+				}
+				Some(_) => {}
+				None => errors.push(RangeAlgebraError::SubtaskNotFound {
+					task_id,
+					dependency_id,
+					subtask_id,
+				}),
+			}
+		}
+
 		// Return all collected errors if any occurred
 		if !errors.is_empty() {
 			return Err(RangeAlgebraError::Multiple { errors });
@@ -255,6 +522,18 @@ pub struct RangeAlgebra {
 	graph: Graph,
 	/// The computed spans for all tasks in the graph.
 	spans: HashMap<TaskId, Span>,
+	/// The spans forecast from the actual progress of tasks.
+	#[serde(default)]
+	forecasts: HashMap<TaskId, Span>,
+	/// The tasks which drove the forecast start of the task which ends last.
+	#[serde(default)]
+	critical_path: Vec<TaskId>,
+	/// The working calendars durations in working days were resolved with.
+	#[serde(default)]
+	calendars: Calendars,
+	/// The timezone calendar days were resolved in.
+	#[serde(default)]
+	timezone: Timezone,
 }
 
 impl RangeAlgebra {
@@ -273,6 +552,53 @@ impl RangeAlgebra {
 		self.spans.get(task_id)
 	}
 
+	/// Get the working calendars durations in working days were resolved with.
+	pub fn calendars(&self) -> &Calendars {
+		&self.calendars
+	}
+
+	/// Get the timezone calendar days were resolved in.
+	pub fn timezone(&self) -> Timezone {
+		self.timezone
+	}
+
+	/// Get a reference to all forecast spans.
+	pub fn forecasts(&self) -> &HashMap<TaskId, Span> {
+		&self.forecasts
+	}
+
+	/// Get the forecast span for a specific task.
+	///
+	/// The forecast keeps the actual start and finish dates of a task and otherwise follows its
+	/// plan, except that a task waits for dependencies which slipped. The percent complete of a
+	/// task does not move its forecast, as there is no status date to count the remaining work
+	/// from; see [`crate::simulation`] for forecasts of the remaining work.
+	pub fn forecast(&self, task_id: &TaskId) -> Option<&Span> {
+		self.forecasts.get(task_id)
+	}
+
+	/// Get the variance of the forecast span of a task against its planned span.
+	pub fn variance(&self, task_id: &TaskId) -> Option<Variance> {
+		Some(Variance::between(self.spans.get(task_id)?, self.forecasts.get(task_id)?))
+	}
+
+	/// Get the date a subtask ends, see [`RangeAlgebra::span`] for its task.
+	///
+	/// A subtask completed on a known date ends on that date. Otherwise it ends at its
+	/// declared offset from the start of its task, or else together with its task.
+	pub fn subtask_end(&self, task_id: &TaskId, subtask_id: &SubtaskId) -> Option<Date> {
+		subtask_end_date(self.graph.task(task_id)?, self.spans.get(task_id)?, subtask_id)
+	}
+
+	/// Get the critical path of the forecast, up to the task which ends last.
+	///
+	/// As in a [`crate::simulation::Simulation`], the path follows each task back to the task
+	/// which drove its start: a dependency it waits for, or else its point of reference. A task
+	/// which actually started was not driven by another, and begins the path.
+	pub fn critical_path(&self) -> &[TaskId] {
+		&self.critical_path
+	}
+
 	/// Get all task IDs that have computed spans.
 	pub fn task_ids(&self) -> impl Iterator<Item = &TaskId> {
 		self.spans.keys()
@@ -305,7 +631,7 @@ mod tests {
 
 	use crate::graph::Graph;
 	use chrono::{DateTime, Utc};
-	use roadline_util::task::Task;
+	use roadline_util::task::{Progress, Task};
 	use std::time::Duration as StdDuration;
 
 	/// Creates a test date from an ISO string.
@@ -397,6 +723,17 @@ mod tests {
 		Ok(graph)
 	}
 
+	#[test]
+	fn test_critical_path() -> Result<(), anyhow::Error> {
+		let graph = create_complex_valid_test_graph()?;
+		let algebra = PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z"))?;
+
+		// T3 ends last, after T2, which starts relative to T1; T4 has slack
+		assert_eq!(algebra.critical_path(), vec![TaskId::new(1), TaskId::new(2), TaskId::new(3)]);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_simple_valid_graph() -> Result<(), anyhow::Error> {
 		let graph = create_simple_valid_test_graph()?;
@@ -428,6 +765,41 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_start_before_reference() -> Result<(), anyhow::Error> {
+		use roadline_util::task::range::{Start, TargetDate};
+
+		let mut graph = Graph::new();
+
+		// T1 starts at the root date, duration 30 days
+		let task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(30 * 24 * 60 * 60));
+		graph.add(task1.clone())?;
+
+		// T2 starts at T1 + 40 days, duration 10 days
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.offset_start_date(StdDuration::from_secs(10 * 24 * 60 * 60))
+			.for_standard_duration(StdDuration::from_secs(10 * 24 * 60 * 60));
+		graph.add(task2)?;
+
+		// T3 starts at T2 - 5 days, duration 5 days
+		let mut task3 =
+			Task::test_from_id(3)?.for_standard_duration(StdDuration::from_secs(5 * 24 * 60 * 60));
+		task3.range.start = Start::from(TargetDate::before(
+			TaskId::new(2).into(),
+			StdDuration::from_secs(5 * 24 * 60 * 60).into(),
+		));
+		graph.add(task3.with_dependencies([1]))?;
+
+		let algebra = PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z"))?;
+		let span = algebra.span(&TaskId::new(3)).copied();
+
+		assert_eq!(span.map(|span| span.start.inner()), Some(test_date("2021-02-05T00:00:00Z")));
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_simple_invalid_graph() -> Result<(), anyhow::Error> {
 		let graph = create_simple_invalid_test_graph()?;
@@ -441,14 +813,18 @@ mod tests {
 					RangeAlgebraError::Multiple { errors: inner_errors } => {
 						assert_eq!(inner_errors.len(), 1);
 						match &inner_errors[0] {
-							RangeAlgebraError::TooEarlyForDependency { task_id, dependency_id } => {
+							RangeAlgebraError::TooEarlyForDependency {
+								task_id,
+								dependency_id,
+								..
+							} => {
 								assert_eq!(*task_id, TaskId::new(3));
 								assert_eq!(*dependency_id, TaskId::new(2));
 							}
 							e => panic!("Unexpected inner error: {:?}", e),
 						}
 					}
-					RangeAlgebraError::TooEarlyForDependency { task_id, dependency_id } => {
+					RangeAlgebraError::TooEarlyForDependency { task_id, dependency_id, .. } => {
 						assert_eq!(*task_id, TaskId::new(3));
 						assert_eq!(*dependency_id, TaskId::new(2));
 					}
@@ -510,6 +886,7 @@ mod tests {
 								RangeAlgebraError::TooEarlyForDependency {
 									task_id,
 									dependency_id,
+									..
 								} => {
 									assert!(
 										*task_id == TaskId::new(3) || *task_id == TaskId::new(4)
@@ -602,4 +979,204 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[test]
+	fn test_subtask_dependencies() -> Result<(), anyhow::Error> {
+		use roadline_util::task::range::{Start, TargetDate};
+		use roadline_util::task::subtask::{Content, Lead, Position, Subtask, Title};
+		use roadline_util::task::{EmbeddedSubtask, Range, SubtaskDependency};
+
+		const DAY: u64 = 24 * 60 * 60;
+		let starting_after = |task: Task, days: u64| {
+			let start = Start::from(TargetDate::new(
+				TaskId::new(1).into(),
+				StdDuration::from_secs(days * DAY).into(),
+			));
+			let range = Range::new(start, task.range.end.clone());
+			Task { range, ..task }
+		};
+
+		// T1 runs for 30 days, and its subtask T1.3 ends 10 days in
+		let subtask = Subtask::new(
+			SubtaskId::new(3),
+			Position::new(0),
+			Title { text: "Draft".to_string() },
+			Content::new("Draft".to_string()),
+			Status::Incomplete,
+			Lead::unknown(),
+		)
+		.with_end(StdDuration::from_secs(10 * DAY).into());
+		let mut task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(30 * DAY));
+		task1.subtasks_mut().insert(EmbeddedSubtask::new(subtask));
+
+		// T2 only needs T1.3, so it may start before T1 ends
+		let dependency = SubtaskDependency::new(TaskId::new(1), SubtaskId::new(3));
+		let task2 = starting_after(Task::test_from_id(2)?, 10)
+			.for_standard_duration(StdDuration::from_secs(5 * DAY))
+			.with_subtask_dependencies([dependency]);
+		assert!(!task2.is_root());
+
+		let mut graph = Graph::new();
+		graph.add(task1.clone())?;
+		graph.add(task2)?;
+		let algebra = PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z"))?;
+
+		assert_eq!(
+			algebra.subtask_end(&TaskId::new(1), &SubtaskId::new(3)),
+			Some(test_date("2021-01-11T00:00:00Z"))
+		);
+		assert_eq!(
+			algebra.span(&TaskId::new(2)).map(|span| span.start.inner()),
+			Some(test_date("2021-01-11T00:00:00Z"))
+		);
+
+		// T3 starts before T1.3 is done
+		let task3 = starting_after(Task::test_from_id(3)?, 5)
+			.for_standard_duration(StdDuration::from_secs(5 * DAY))
+			.with_subtask_dependencies([dependency]);
+		let mut graph = Graph::new();
+		graph.add(task1)?;
+		graph.add(task3)?;
+
+		match PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z")) {
+			Err(RangeAlgebraError::Multiple { errors }) => assert!(matches!(
+				errors.as_slice(),
+				[RangeAlgebraError::Multiple { errors }] if matches!(
+					errors.as_slice(),
+					[RangeAlgebraError::TooEarlyForSubtask { task_id, .. }] if *task_id == TaskId::new(3)
+				)
+			)),
+			other => panic!("Expected a subtask dependency error, got {other:?}"),
+		}
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forecast_from_actuals() -> Result<(), anyhow::Error> {
+		let root_date = test_date("2024-01-01T00:00:00Z");
+		let days = |n: u64| StdDuration::from_secs(n * 24 * 60 * 60);
+		let mut graph = Graph::new();
+
+		// T1 started 5 days late and took 35 days instead of 30
+		let progress = Progress::new()
+			.with_started(add_duration_to_date(root_date, days(5)).inner())
+			.with_finished(add_duration_to_date(root_date, days(40)).inner());
+		let task1 = Task::test_from_id(1)?.for_standard_duration(days(30)).with_progress(progress);
+		graph.add(task1.clone())?;
+
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(days(15))
+			.with_dependencies([1]);
+		graph.add(task2)?;
+
+		let range_algebra = PreRangeAlgebra::new(graph).compute(root_date)?;
+
+		// The plan is unchanged
+		let span2 = range_algebra.span(&TaskId::new(2)).ok_or(anyhow::anyhow!("no T2 span"))?;
+		assert_eq!(span2.start.inner(), add_duration_to_date(root_date, days(30)));
+
+		// T2 waits for T1 to actually finish
+		let forecast2 = range_algebra
+			.forecast(&TaskId::new(2))
+			.ok_or(anyhow::anyhow!("no T2 forecast"))?;
+		assert_eq!(forecast2.start.inner(), add_duration_to_date(root_date, days(40)));
+		assert_eq!(forecast2.end.inner(), add_duration_to_date(root_date, days(55)));
+
+		let variance1 = range_algebra.variance(&TaskId::new(1)).ok_or(anyhow::anyhow!("no T1"))?;
+		assert_eq!(variance1.start, chrono::Duration::days(5));
+		assert_eq!(variance1.finish, chrono::Duration::days(10));
+		let variance2 = range_algebra.variance(&TaskId::new(2)).ok_or(anyhow::anyhow!("no T2"))?;
+		assert_eq!(variance2.start, chrono::Duration::days(10));
+		assert!(variance2.is_late());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_forecast_of_early_finish() -> Result<(), anyhow::Error> {
+		let root_date = test_date("2024-01-01T00:00:00Z");
+		let days = |n: u64| StdDuration::from_secs(n * 24 * 60 * 60);
+		let mut graph = Graph::new();
+
+		let task1 = Task::test_from_id(1)?.for_standard_duration(days(30));
+		graph.add(task1.clone())?;
+
+		// T2 was planned after T1, but finished on day 20 without a recorded start
+		let finished = add_duration_to_date(root_date, days(20));
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(days(15))
+			.with_dependencies([1])
+			.with_progress(Progress::new().with_finished(finished.inner()));
+		graph.add(task2)?;
+
+		let range_algebra = PreRangeAlgebra::new(graph).compute(root_date)?;
+		let forecast2 = range_algebra
+			.forecast(&TaskId::new(2))
+			.ok_or(anyhow::anyhow!("no T2 forecast"))?;
+		assert_eq!(forecast2.start.inner(), finished);
+		assert_eq!(forecast2.end.inner(), finished);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_workdays_follow_calendars() -> Result<(), anyhow::Error> {
+		use crate::calendar::Calendar;
+		use roadline_util::task::range::{PointOfReference, Start, TargetDate};
+		use roadline_util::task::Lead;
+
+		// Friday 5 January 2024, with Wednesday 10 January off
+		let root_date = test_date("2024-01-05T00:00:00Z");
+		let holiday =
+			chrono::NaiveDate::from_ymd_opt(2024, 1, 10).ok_or(anyhow::anyhow!("date"))?;
+		let calendars = Calendars::new(Calendar::new().with_holidays([holiday])).with_lead(
+			"Ada",
+			Calendar::new().with_weekdays([chrono::Weekday::Mon, chrono::Weekday::Tue]),
+		);
+
+		let mut graph = Graph::new();
+		let task1 = Task::test_from_id(1)?.for_workdays(5);
+		graph.add(task1.clone())?;
+		let task2 = Task::test_from_id(2)?
+			.for_workdays(3)
+			.with_leads([Lead::new("Ada".to_string(), "ada@example.com".to_string())]);
+		graph.add(task2)?;
+		let mut task3 =
+			Task::test_from_id(3)?.for_standard_duration(StdDuration::from_secs(24 * 60 * 60));
+		let reference = PointOfReference::from(TaskId::new(1));
+		task3.range_mut().start =
+			Start::from(TargetDate::before(reference, StdDuration::ZERO.into()).with_workdays(2));
+		graph.add(task3)?;
+		let mut task4 =
+			Task::test_from_id(4)?.for_standard_duration(StdDuration::from_secs(24 * 60 * 60));
+		let reference = PointOfReference::from(TaskId::new(1));
+		task4.range_mut().start =
+			Start::from(TargetDate::new(reference, StdDuration::ZERO.into()).with_workdays(4));
+		graph.add(task4)?;
+
+		let range_algebra =
+			PreRangeAlgebra::new(graph).with_calendars(calendars).compute(root_date)?;
+
+		// Friday, Monday, Tuesday, Thursday and Friday
+		let span1 = range_algebra.span(&TaskId::new(1)).ok_or(anyhow::anyhow!("no T1 span"))?;
+		assert_eq!(span1.end.inner(), test_date("2024-01-13T00:00:00Z"));
+
+		// Ada works Mondays and Tuesdays only
+		let span2 = range_algebra.span(&TaskId::new(2)).ok_or(anyhow::anyhow!("no T2 span"))?;
+		assert_eq!(span2.end.inner(), test_date("2024-01-16T00:00:00Z"));
+
+		// Two working days before Friday is Wednesday, and four after it skip the holiday
+		let span3 = range_algebra.span(&TaskId::new(3)).ok_or(anyhow::anyhow!("no T3 span"))?;
+		assert_eq!(span3.start.inner(), test_date("2024-01-03T00:00:00Z"));
+		let forecast4 = range_algebra
+			.forecast(&TaskId::new(4))
+			.ok_or(anyhow::anyhow!("no T4 forecast"))?;
+		assert_eq!(forecast4.start.inner(), test_date("2024-01-12T00:00:00Z"));
+
+		Ok(())
+	}
 }
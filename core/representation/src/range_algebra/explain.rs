@@ -0,0 +1,451 @@
+//! Human-readable explanations of scheduling errors.
+//!
+//! A [`RangeAlgebraError`] refers to tasks by id only. An [`Explainer`] maps the ids back
+//! to the titles and, when known, the source lines of the tasks, spells out cycles as
+//! `T3 → T5 → T3` with the dependencies which form them, and for a task which starts
+//! before its dependencies end states the dates, the shortfall and the smallest start
+//! offset which would fix it.
+
+use super::{Date, RangeAlgebraError};
+use crate::graph::Graph;
+use crate::timezone::Timezone;
+use roadline_util::task::subtask::Id as SubtaskId;
+use roadline_util::task::{Id as TaskId, Task};
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::fmt;
+
+/// A start offset which lets a task start once its dependencies have ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StartOffset {
+	/// The task to move.
+	pub task_id: TaskId,
+	/// The task its start is written relative to.
+	pub reference_id: TaskId,
+	/// The smallest offset from the start of the reference, negative if it lies before it.
+	pub offset: chrono::Duration,
+}
+
+/// The explanation of a single scheduling error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+	/// What went wrong, in one line.
+	pub message: String,
+	/// The facts behind it, such as dates or the dependencies forming a cycle.
+	pub details: Vec<String>,
+	/// How the error could be fixed, if there is an obvious way.
+	pub suggestion: Option<String>,
+	/// The start offset which fixes a task starting too early.
+	pub start_offset: Option<StartOffset>,
+}
+
+impl Explanation {
+	pub fn new(message: impl Into<String>) -> Self {
+		Self { message: message.into(), details: Vec::new(), suggestion: None, start_offset: None }
+	}
+
+	pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+		self.details.push(detail.into());
+		self
+	}
+
+	pub fn with_suggestion(mut self, suggestion: impl Into<String>) -> Self {
+		self.suggestion = Some(suggestion.into());
+		self
+	}
+
+	pub fn with_start_offset(mut self, start_offset: StartOffset) -> Self {
+		self.start_offset = Some(start_offset);
+		self
+	}
+}
+
+impl fmt::Display for Explanation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.message)?;
+		for detail in &self.details {
+			write!(f, "\n  - {detail}")?;
+		}
+		if let Some(suggestion) = &self.suggestion {
+			write!(f, "\n  help: {suggestion}")?;
+		}
+		Ok(())
+	}
+}
+
+/// Explains scheduling errors in terms of the tasks they concern.
+#[derive(Debug, Clone, Default)]
+pub struct Explainer<'a> {
+	tasks: HashMap<TaskId, &'a Task>,
+	lines: HashMap<TaskId, usize>,
+	timezone: Timezone,
+}
+
+impl<'a> Explainer<'a> {
+	/// Creates an explainer for the given tasks.
+	pub fn new(tasks: impl IntoIterator<Item = &'a Task>) -> Self {
+		Self {
+			tasks: tasks.into_iter().map(|task| (*task.id(), task)).collect(),
+			lines: HashMap::new(),
+			timezone: Timezone::default(),
+		}
+	}
+
+	/// Creates an explainer for the tasks of a graph.
+	pub fn for_graph(graph: &'a Graph) -> Self {
+		Self::new(graph.arena.tasks().values())
+	}
+
+	/// Sets the source lines the tasks are declared on.
+	pub fn with_lines(self, lines: HashMap<TaskId, usize>) -> Self {
+		Self { lines, ..self }
+	}
+
+	/// Sets the timezone dates are shown in.
+	pub fn with_timezone(self, timezone: Timezone) -> Self {
+		Self { timezone, ..self }
+	}
+
+	/// Explains an error, with one explanation per underlying error.
+	pub fn explain(&self, error: &RangeAlgebraError) -> Vec<Explanation> {
+		match error {
+			RangeAlgebraError::Multiple { errors } => {
+				errors.iter().flat_map(|error| self.explain(error)).collect()
+			}
+			RangeAlgebraError::GraphHasCycles { cycles } => {
+				cycles.iter().map(|cycle| self.explain_cycle(cycle)).collect()
+			}
+			RangeAlgebraError::TooEarlyForDependency {
+				task_id,
+				dependency_id,
+				task_start,
+				dependency_end,
+			} => vec![self.explain_too_early(
+				*task_id,
+				&self.task(dependency_id),
+				*task_start,
+				*dependency_end,
+			)],
+			RangeAlgebraError::TooEarlyForSubtask {
+				task_id,
+				dependency_id,
+				subtask_id,
+				task_start,
+				subtask_end,
+			} => vec![self.explain_too_early(
+				*task_id,
+				&format!("subtask {}", self.subtask(dependency_id, subtask_id)),
+				*task_start,
+				*subtask_end,
+			)],
+			RangeAlgebraError::SubtaskNotFound { task_id, dependency_id, subtask_id } => {
+				vec![Explanation::new(format!(
+					"{} depends on subtask {}, which does not exist",
+					self.task(task_id),
+					self.subtask(dependency_id, subtask_id)
+				))
+				.with_detail(format!("{} has no such subtask", self.task(dependency_id)))]
+			}
+			RangeAlgebraError::InvalidReference { task_id, reference_id } => {
+				vec![Explanation::new(format!(
+					"{} starts relative to {}, which is not in the roadmap",
+					self.task(task_id),
+					self.label(reference_id)
+				))]
+			}
+			RangeAlgebraError::TaskNotFound { task_id } => {
+				vec![Explanation::new(format!("{} is not in the roadmap", self.label(task_id)))]
+			}
+			RangeAlgebraError::OnlyRootTasksCanSelfReference { task_id, .. } => {
+				vec![Explanation::new(format!(
+					"{} starts relative to itself, but it depends on other tasks",
+					self.task(task_id)
+				))
+				.with_suggestion(format!(
+					"start {} relative to one of its dependencies",
+					self.label(task_id)
+				))]
+			}
+			error => vec![Explanation::new(error.to_string())],
+		}
+	}
+
+	/// Explains a cycle, given as the tasks of a strongly connected component.
+	fn explain_cycle(&self, component: &[TaskId]) -> Explanation {
+		let path = self.cycle_path(component);
+		let shown: Vec<String> = path.iter().map(|task_id| self.label(task_id)).collect();
+
+		let mut explanation = Explanation::new(format!("Dependency cycle: {}", shown.join(" → ")));
+		for link in path.windows(2) {
+			let (task_id, dependency_id) = (link[0], link[1]);
+			let through_subtasks: Vec<String> = self
+				.tasks
+				.get(&task_id)
+				.filter(|task| !task.depends_on().contains(&dependency_id))
+				.map(|task| {
+					task.subtask_dependencies()
+						.iter()
+						.filter(|dependency| dependency.task() == dependency_id)
+						.map(|dependency| self.subtask(&dependency_id, &dependency.subtask()))
+						.collect()
+				})
+				.unwrap_or_default();
+
+			explanation = explanation.with_detail(if through_subtasks.is_empty() {
+				format!("{} depends on {}", self.task(&task_id), self.task(&dependency_id))
+			} else {
+				format!(
+					"{} depends on subtask {} of {}",
+					self.task(&task_id),
+					through_subtasks.join(", "),
+					self.task(&dependency_id)
+				)
+			});
+		}
+
+		explanation.with_suggestion("remove one of these dependencies to break the cycle")
+	}
+
+	/// Orders the tasks of a cycle along their dependencies, from the smallest id back to it.
+	///
+	/// Falls back to the tasks as given if their dependencies are not known.
+	fn cycle_path(&self, component: &[TaskId]) -> Vec<TaskId> {
+		let members: BTreeSet<TaskId> = component.iter().copied().collect();
+		let Some(start) = members.first().copied() else {
+			return Vec::new();
+		};
+
+		// Breadth first along dependencies within the cycle, until we are back at the start
+		let mut previous: HashMap<TaskId, TaskId> = HashMap::new();
+		let mut queue = VecDeque::from([start]);
+		while let Some(current) = queue.pop_front() {
+			for dependency in self.dependencies(&current) {
+				if !members.contains(&dependency) {
+					continue;
+				}
+				if dependency == start {
+					let mut path = vec![start, current];
+					let mut task_id = current;
+					while let Some(before) = previous.get(&task_id) {
+						path.push(*before);
+						task_id = *before;
+					}
+					path.reverse();
+					return path;
+				}
+				if dependency != current && !previous.contains_key(&dependency) {
+					previous.insert(dependency, current);
+					queue.push_back(dependency);
+				}
+			}
+		}
+
+		let mut path: Vec<TaskId> = component.to_vec();
+		path.push(component[0]);
+		path
+	}
+
+	/// The tasks a task depends on, directly or through a subtask.
+	fn dependencies(&self, task_id: &TaskId) -> BTreeSet<TaskId> {
+		self.tasks
+			.get(task_id)
+			.map(|task| {
+				task.depends_on()
+					.iter()
+					.copied()
+					.chain(task.subtask_dependencies().iter().map(|dependency| dependency.task()))
+					.collect()
+			})
+			.unwrap_or_default()
+	}
+
+	/// Explains a task which starts before something it depends on ends.
+	fn explain_too_early(
+		&self,
+		task_id: TaskId,
+		dependency: &str,
+		task_start: Date,
+		dependency_end: Date,
+	) -> Explanation {
+		let shortfall = dependency_end.inner() - task_start.inner();
+		let mut explanation = Explanation::new(format!(
+			"{} starts {} before {} ends",
+			self.task(&task_id),
+			format_duration(shortfall),
+			dependency
+		))
+		.with_detail(format!("{} starts on {}", self.label(&task_id), self.format_date(task_start)))
+		.with_detail(format!("{} ends on {}", dependency, self.format_date(dependency_end)));
+
+		if let Some(task) = self.tasks.get(&task_id) {
+			let start = &task.range().start;
+			let reference_id: TaskId = start.point_of_reference().clone().into();
+			let current = chrono::Duration::seconds(start.0.signed_secs() as i64);
+			let offset = current + shortfall;
+
+			explanation = explanation
+				.with_suggestion(format!(
+					"start {} at {} or later",
+					self.label(&task_id),
+					self.format_offset(&reference_id, offset)
+				))
+				.with_start_offset(StartOffset { task_id, reference_id, offset });
+		}
+
+		explanation
+	}
+
+	/// A task as `T3`, or `M3` for a milestone.
+	fn label(&self, task_id: &TaskId) -> String {
+		let prefix = self.tasks.get(task_id).map_or('T', |task| task.kind().prefix());
+		format!("{}{}", prefix, task_id.value())
+	}
+
+	/// A task with its title and source line, e.g. `T3 "Review" (line 40)`.
+	fn task(&self, task_id: &TaskId) -> String {
+		let mut shown = self.label(task_id);
+		if let Some(task) = self.tasks.get(task_id) {
+			shown.push_str(&format!(" \"{}\"", task.title().text));
+		}
+		if let Some(line) = self.lines.get(task_id) {
+			shown.push_str(&format!(" (line {line})"));
+		}
+		shown
+	}
+
+	/// A subtask as `T3.2`.
+	fn subtask(&self, task_id: &TaskId, subtask_id: &SubtaskId) -> String {
+		format!("{}.{}", self.label(task_id), subtask_id.value())
+	}
+
+	/// A date in the timezone of the roadmap, leaving out the time at midnight.
+	fn format_date(&self, date: Date) -> String {
+		let local = self.timezone.local(date);
+		if date == self.timezone.start_of_day(date) {
+			local.format("%Y-%m-%d").to_string()
+		} else {
+			local.format("%Y-%m-%d %H:%M").to_string()
+		}
+	}
+
+	/// A start written relative to a reference, e.g. `T1 + 12 days`.
+	fn format_offset(&self, reference_id: &TaskId, offset: chrono::Duration) -> String {
+		let sign = if offset < chrono::Duration::zero() { '-' } else { '+' };
+		format!("`{} {} {}`", self.label(reference_id), sign, format_duration(offset.abs()))
+	}
+}
+
+/// A duration in the largest whole unit it fits, e.g. `12 days` or `36 hours`.
+fn format_duration(duration: chrono::Duration) -> String {
+	let seconds = duration.num_seconds().abs();
+	let (count, unit) = if seconds % 86_400 == 0 {
+		(seconds / 86_400, "day")
+	} else if seconds % 3_600 == 0 {
+		(seconds / 3_600, "hour")
+	} else if seconds % 60 == 0 {
+		(seconds / 60, "minute")
+	} else {
+		(seconds, "second")
+	};
+
+	if count == 1 {
+		format!("1 {unit}")
+	} else {
+		format!("{count} {unit}s")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::range_algebra::PreRangeAlgebra;
+	use chrono::{DateTime, Utc};
+	use std::time::Duration as StdDuration;
+
+	const DAY: u64 = 24 * 60 * 60;
+
+	fn test_date(iso_string: &str) -> Result<Date, anyhow::Error> {
+		Ok(Date::new(DateTime::parse_from_rfc3339(iso_string)?.with_timezone(&Utc)))
+	}
+
+	fn task_title(graph: &Graph, id: u8) -> Result<String, anyhow::Error> {
+		Ok(graph
+			.task(&TaskId::new(id))
+			.ok_or(anyhow::anyhow!("missing task"))?
+			.title()
+			.text
+			.clone())
+	}
+
+	#[test]
+	fn test_explain_too_early() -> Result<(), anyhow::Error> {
+		// T2 runs from day 10 to day 30, but T3 starts on day 18
+		let mut graph = Graph::new();
+		let task1 = Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(10 * DAY));
+		graph.add(task1.clone())?;
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(20 * DAY))
+			.with_dependencies([1]);
+		graph.add(task2.clone())?;
+		let task3 = Task::test_from_id(3)?
+			.after(&task1)
+			.offset_start_date(StdDuration::from_secs(8 * DAY))
+			.for_standard_duration(StdDuration::from_secs(5 * DAY))
+			.with_dependencies([2]);
+		graph.add(task3)?;
+
+		let error =
+			match PreRangeAlgebra::new(graph.clone()).compute(test_date("2025-01-06T00:00:00Z")?) {
+				Ok(_) => return Err(anyhow::anyhow!("T3 should start too early")),
+				Err(error) => error,
+			};
+		let lines = HashMap::from([(TaskId::new(2), 12), (TaskId::new(3), 20)]);
+		let explanations = Explainer::for_graph(&graph).with_lines(lines).explain(&error);
+
+		assert_eq!(explanations.len(), 1);
+		let explanation = &explanations[0];
+		assert_eq!(
+			explanation.message,
+			format!(
+				"T3 \"{}\" (line 20) starts 12 days before T2 \"{}\" (line 12) ends",
+				task_title(&graph, 3)?,
+				task_title(&graph, 2)?
+			)
+		);
+		assert_eq!(explanation.details[0], "T3 starts on 2025-01-24");
+		assert!(explanation.details[1].ends_with("ends on 2025-02-05"));
+		assert_eq!(explanation.suggestion.as_deref(), Some("start T3 at `T1 + 30 days` or later"));
+		assert_eq!(
+			explanation.start_offset,
+			Some(StartOffset {
+				task_id: TaskId::new(3),
+				reference_id: TaskId::new(1),
+				offset: chrono::Duration::days(30),
+			})
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_explain_cycle() -> Result<(), anyhow::Error> {
+		// T3 depends on T5, which depends on T3
+		let mut graph = Graph::new();
+		let task3 = Task::test_from_id(3)?.with_dependencies([5]);
+		let task5 = Task::test_from_id(5)?.with_dependencies([3]);
+		graph.add(task3)?;
+		graph.add(task5)?;
+
+		let error = RangeAlgebraError::GraphHasCycles { cycles: graph.find_cycles()? };
+		let explanations = Explainer::for_graph(&graph).explain(&error);
+
+		assert_eq!(explanations.len(), 1);
+		assert_eq!(explanations[0].message, "Dependency cycle: T3 → T5 → T3");
+		assert_eq!(explanations[0].details.len(), 2);
+		assert!(explanations[0].details[0].starts_with("T3 \""));
+		assert!(explanations[0].details[1].starts_with("T5 \""));
+		assert!(explanations[0].to_string().contains("help: remove one of these dependencies"));
+
+		Ok(())
+	}
+}
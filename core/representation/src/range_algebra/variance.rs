@@ -0,0 +1,27 @@
+use super::span::Span;
+
+/// How far a span has drifted from the span it was planned with.
+///
+/// Positive variances are late and negative variances early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variance {
+	/// The drift of the start date.
+	pub start: chrono::Duration,
+	/// The drift of the finish date.
+	pub finish: chrono::Duration,
+}
+
+impl Variance {
+	/// Computes the variance of a span against the span it was planned with.
+	pub fn between(planned: &Span, actual: &Span) -> Self {
+		Self {
+			start: actual.start.inner().inner() - planned.start.inner().inner(),
+			finish: actual.end.inner().inner() - planned.end.inner().inner(),
+		}
+	}
+
+	/// Whether the span finishes later than planned.
+	pub fn is_late(&self) -> bool {
+		self.finish > chrono::Duration::zero()
+	}
+}
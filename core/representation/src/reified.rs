@@ -7,7 +7,7 @@ pub mod reified_unit;
 pub use down_cell::DownCell;
 pub use down_lane::{DownLane, DownLanePadding};
 pub use down_stretch::{DownStretch, Trim};
-pub use joint::{BezierConnection, ConnectionPoint, Joint};
+pub use joint::{BezierConnection, ConnectionPoint, Joint, SubtaskMarker};
 pub use reified_unit::ReifiedUnit;
 
 use crate::graph::Graph;
@@ -85,10 +85,15 @@ impl PreReified {
 
 		// Step 1: Create DownCells for all tasks
 		for (task_id, cell) in self.grid.tasks() {
-			let down_stretch = DownStretch::canonical_from_stretch(
-				cell.stretch().clone(),
-				self.config.connection_trim.clone(),
-			);
+			// Milestones stay points, so they are not trimmed
+			let down_stretch = if cell.stretch().is_point() {
+				DownStretch::point_from_stretch(cell.stretch().clone())
+			} else {
+				DownStretch::canonical_from_stretch(
+					cell.stretch().clone(),
+					self.config.connection_trim.clone(),
+				)
+			};
 			let down_lane =
 				DownLane::canonical_from_lane(*cell.lane(), self.config.inter_lane_padding.clone());
 			let down_cell = DownCell::new(cell.clone(), down_lane, down_stretch);
@@ -112,24 +117,77 @@ impl PreReified {
 					.get(&dependency_task_id)
 					.ok_or(ReifiedError::TaskNotFound { task_id: dependency_task_id })?;
 
-				let start_point = {
-					let (x, y) = dependency_cell.outgoing_connection_point();
-					ConnectionPoint::new(x, y)
-				};
-
 				let end_point = {
 					let (x, y) = dependent_cell.incoming_connection_point();
 					ConnectionPoint::new(x, y)
 				};
 
-				// Create flowing joint
-				let joint = Joint::flowing_joint(dependency_id, start_point, end_point);
-				joints.insert(dependency_id, joint);
+				// A subtask dependency leaves its task where the subtask ends
+				match self.subtask_anchor(*task_id, dependency_task_id, dependency_cell) {
+					Some(marker) => {
+						let joint =
+							Joint::flowing_joint(dependency_id, marker.point.clone(), end_point)
+								.with_subtask_marker(marker);
+						joints.insert(dependency_id, joint);
+					}
+					None => {
+						let start_point = {
+							let (x, y) = dependency_cell.outgoing_connection_point();
+							ConnectionPoint::new(x, y)
+						};
+
+						// Create flowing joint
+						let joint = Joint::flowing_joint(dependency_id, start_point, end_point);
+						joints.insert(dependency_id, joint);
+					}
+				}
 			}
 		}
 
 		Ok(Reified { grid: self.grid, config: self.config, down_cells, joints })
 	}
+
+	/// Places the marker of a connection which only depends on subtasks of its dependency.
+	///
+	/// The marker sits on the dependency's cell, as far along as the latest of those subtasks
+	/// ends within the dependency's span.
+	fn subtask_anchor(
+		&self,
+		task_id: TaskId,
+		dependency_task_id: TaskId,
+		dependency_cell: &DownCell,
+	) -> Option<SubtaskMarker> {
+		let range_algebra = self.grid.range_algebra();
+		let task = range_algebra.task(&task_id)?;
+		if task.depends_on().contains(&dependency_task_id) {
+			return None;
+		}
+
+		let (subtask, subtask_end) = task
+			.subtask_dependencies()
+			.iter()
+			.filter(|dependency| dependency.task() == dependency_task_id)
+			.filter_map(|dependency| {
+				let end = range_algebra.subtask_end(&dependency_task_id, &dependency.subtask())?;
+				Some((*dependency, end))
+			})
+			.max_by_key(|(_, end)| *end)?;
+
+		let span = range_algebra.span(&dependency_task_id)?;
+		let span_start = span.start.inner().inner();
+		let total = (span.end.inner().inner() - span_start).num_seconds();
+		let elapsed = (subtask_end.inner() - span_start).num_seconds().clamp(0, total.max(0));
+
+		let (end_x, y) = dependency_cell.outgoing_connection_point();
+		let start_x = i64::from(dependency_cell.incoming_connection_point().0.value());
+		let width = i64::from(end_x.value()) - start_x;
+		let x =
+			if total > 0 { start_x + width * elapsed / total } else { i64::from(end_x.value()) };
+
+		let point =
+			ConnectionPoint::new(ReifiedUnit::new(u16::try_from(x).unwrap_or(end_x.value())), y);
+		Some(SubtaskMarker::new(subtask, point))
+	}
 }
 
 /// Immutable reified visual representation
@@ -314,6 +372,108 @@ mod tests {
 		Ok(())
 	}
 
+	#[test]
+	fn test_reified_subtask_marker() -> Result<(), anyhow::Error> {
+		use roadline_util::task::subtask::{
+			Content, Id as SubtaskId, Lead, Position, Status, Subtask, Title,
+		};
+		use roadline_util::task::{EmbeddedSubtask, SubtaskDependency};
+
+		// Task2 only depends on the first subtask of Task1, which ends a third of the way in
+		let subtask = Subtask::new(
+			SubtaskId::new(1),
+			Position::new(0),
+			Title { text: "Draft".to_string() },
+			Content::new("Draft".to_string()),
+			Status::Incomplete,
+			Lead::unknown(),
+		)
+		.with_end(StdDuration::from_secs(10 * 24 * 60 * 60).into());
+		let mut task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(30 * 24 * 60 * 60));
+		task1.subtasks_mut().insert(EmbeddedSubtask::new(subtask));
+
+		let dependency = SubtaskDependency::new(TaskId::new(1), SubtaskId::new(1));
+		let task2 = Task::test_from_id(2)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(10 * 24 * 60 * 60))
+			.with_subtask_dependencies([dependency]);
+
+		let mut graph = Graph::new();
+		graph.add(task1)?;
+		graph.add(task2)?;
+
+		let range_algebra =
+			PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z"))?;
+		let grid_algebra = PreGridAlgebra::new(range_algebra).compute()?;
+		let reified = PreReified::new(grid_algebra).compute()?;
+
+		let joint = reified.get_joint(&DependencyId::new(TaskId::new(1), TaskId::new(2)));
+		let marker = joint.and_then(Joint::subtask_marker);
+		assert_eq!(marker.map(|marker| marker.subtask), Some(dependency));
+		assert_eq!(marker.map(|marker| marker.subtask.to_string()), Some("T1.1".to_string()));
+
+		// The connection leaves Task1 within its cell rather than at its end
+		let cell = reified.get_down_cell(&TaskId::new(1));
+		let (start_x, end_x) = cell
+			.map(|cell| {
+				(
+					cell.incoming_connection_point().0.value(),
+					cell.outgoing_connection_point().0.value(),
+				)
+			})
+			.unwrap_or_default();
+		let marker_x = marker.map(|marker| marker.point.x.value()).unwrap_or_default();
+		assert!(start_x <= marker_x && marker_x < end_x);
+		assert_eq!(joint.map(|joint| joint.bezier_connection().start.x.value()), Some(marker_x));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_reified_milestone() -> Result<(), anyhow::Error> {
+		use roadline_util::task::Kind;
+
+		// Task1 -> Milestone2 -> Task3
+		let task1 =
+			Task::test_from_id(1)?.for_standard_duration(StdDuration::from_secs(30 * 24 * 60 * 60));
+		let milestone2 = Task::test_from_id(2)?
+			.after(&task1)
+			.with_kind(Kind::Milestone)
+			.with_dependencies([1]);
+		let task3 = Task::test_from_id(3)?
+			.after(&milestone2)
+			.for_standard_duration(StdDuration::from_secs(30 * 24 * 60 * 60))
+			.with_dependencies([2]);
+
+		let mut graph = Graph::new();
+		graph.add(task1)?;
+		graph.add(milestone2)?;
+		graph.add(task3)?;
+
+		let range_algebra =
+			PreRangeAlgebra::new(graph).compute(test_date("2021-01-01T00:00:00Z"))?;
+		let grid_algebra = PreGridAlgebra::new(range_algebra).compute()?;
+		let reified = PreReified::new(grid_algebra).compute()?;
+
+		// The milestone is a point where Task1 ends and Task3 starts
+		let milestone = reified.get_down_cell(&TaskId::new(2));
+		assert!(milestone.is_some_and(DownCell::is_point));
+		assert_eq!(milestone.map(|cell| cell.width().value()), Some(0));
+		assert_eq!(
+			milestone.map(DownCell::incoming_connection_point),
+			milestone.map(DownCell::outgoing_connection_point)
+		);
+
+		let milestone_x = milestone.map(|cell| cell.incoming_connection_point().0.value());
+		let task3_x = reified
+			.get_down_cell(&TaskId::new(3))
+			.map(|cell| cell.incoming_connection_point().0.value());
+		assert_eq!(milestone_x, task3_x);
+
+		Ok(())
+	}
+
 	#[test]
 	fn test_reified_visual_bounds() -> Result<(), anyhow::Error> {
 		let mut graph = Graph::new();
@@ -48,6 +48,11 @@ impl DownCell {
 		self.down_stretch().width()
 	}
 
+	/// Whether the cell is a point, as for a milestone
+	pub fn is_point(&self) -> bool {
+		self.cell.stretch().is_point()
+	}
+
 	/// Get the outgoing connection point (right edge center)
 	pub fn outgoing_connection_point(&self) -> (ReifiedUnit, ReifiedUnit) {
 		(self.down_stretch.outgoing_connection_point(), self.down_lane.midpoint())
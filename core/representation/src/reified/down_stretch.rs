@@ -75,6 +75,15 @@ impl DownStretch {
 		Self::new(stretch, trim, down_stretch)
 	}
 
+	/// The down stretch of a point, such as a milestone, which is not trimmed.
+	pub fn point_from_stretch(stretch: Stretch) -> Self {
+		let unit = stretch.unit().down(1);
+		let (start, _) = stretch.scale(unit);
+		let point = ReifiedUnit::new(start as u16);
+
+		Self::new(stretch, Trim::from(0), DownStretchRange::new(point, point))
+	}
+
 	pub fn stretch(&self) -> &Stretch {
 		&self.stretch
 	}
@@ -1,4 +1,5 @@
 use roadline_util::dependency::Id;
+use roadline_util::task::SubtaskDependency;
 use serde::{Deserialize, Serialize};
 use super::reified_unit::ReifiedUnit;
 
@@ -63,16 +64,41 @@ impl BezierConnection {
     }
 }
 
+/// Marks where on its task the subtask behind a connection ends.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SubtaskMarker {
+    pub subtask: SubtaskDependency,
+    pub point: ConnectionPoint,
+}
+
+impl SubtaskMarker {
+    pub fn new(subtask: SubtaskDependency, point: ConnectionPoint) -> Self {
+        Self { subtask, point }
+    }
+}
+
 /// The joint represents a connection between tasks with its routing information
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Joint {
     dependency: Id,
     bezier_connection: BezierConnection,
+    /// The subtask the connection comes from, if it is a subtask dependency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    subtask_marker: Option<SubtaskMarker>,
 }
 
 impl Joint {
     pub fn new(dependency: Id, bezier_connection: BezierConnection) -> Self {
-        Self { dependency, bezier_connection }
+        Self { dependency, bezier_connection, subtask_marker: None }
+    }
+
+    /// Marks the joint as coming from a subtask of its dependency.
+    pub fn with_subtask_marker(self, subtask_marker: SubtaskMarker) -> Self {
+        Self { subtask_marker: Some(subtask_marker), ..self }
+    }
+
+    pub fn subtask_marker(&self) -> Option<&SubtaskMarker> {
+        self.subtask_marker.as_ref()
     }
 
     pub fn dependency(&self) -> &Id {
@@ -3,6 +3,7 @@
 //! This module provides a high-level, builder-pattern API that orchestrates
 //! the entire pipeline from task graphs to visual representations.
 
+use crate::calendar::Calendars;
 use crate::graph::{Graph, GraphError};
 use crate::grid_algebra::{GridAlgebra, GridAlgebraError, PreGridAlgebra};
 use crate::range_algebra::{Date, PreRangeAlgebra, RangeAlgebra, RangeAlgebraError};
@@ -10,6 +11,7 @@ use crate::reified::{
 	ConnectionPoint, DownCell, DownLanePadding, Joint, PreReified, Reified, ReifiedConfig,
 	ReifiedError, ReifiedUnit, Trim,
 };
+use crate::timezone::Timezone;
 use roadline_util::dependency::Id as DependencyId;
 use roadline_util::task::{Id as TaskId, Task};
 use serde::{Deserialize, Serialize};
@@ -62,6 +64,7 @@ pub enum RoadlineBuilderError {
 pub struct BuilderSummary {
 	pub task_count: usize,
 	pub root_date: Date,
+	pub timezone: Timezone,
 	pub trim_units: u16,
 	pub padding_units: u16,
 }
@@ -70,8 +73,12 @@ impl std::fmt::Display for BuilderSummary {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		write!(
 			f,
-			"RoadlineBuilder: {} tasks, root: {:?}, spacing: {}×{}",
-			self.task_count, self.root_date, self.trim_units, self.padding_units
+			"RoadlineBuilder: {} tasks, root: {} ({}), spacing: {}×{}",
+			self.task_count,
+			self.timezone.local(self.root_date).to_rfc3339(),
+			self.timezone,
+			self.trim_units,
+			self.padding_units
 		)
 	}
 }
@@ -100,6 +107,8 @@ pub struct RoadlineBuilder {
 	config: ReifiedConfig,
 	graph: Graph,
 	root_date: Date,
+	calendars: Calendars,
+	timezone: Timezone,
 }
 
 impl Default for RoadlineBuilder {
@@ -126,7 +135,7 @@ impl RoadlineBuilder {
 		let naive_datetime = NaiveDateTime::new(naive_date, naive_time);
 		let y2k_date = Date::new(DateTime::from_naive_utc_and_offset(naive_datetime, Utc));
 
-		Self { config: ReifiedConfig::default_config(), graph: Graph::new(), root_date: y2k_date }
+		Self::with_start_date(y2k_date)
 	}
 
 	/// Create a new builder starting at the Unix epoch.
@@ -137,12 +146,18 @@ impl RoadlineBuilder {
 		let root_date = Date::start_of_epoch()
 			.map_err(|e| RoadlineBuilderError::DateParsing { message: e.to_string() })?;
 
-		Ok(Self { config: ReifiedConfig::default_config(), graph: Graph::new(), root_date })
+		Ok(Self::with_start_date(root_date))
 	}
 
 	/// Create a new builder with a custom start date.
 	pub fn with_start_date(start_date: Date) -> Self {
-		Self { config: ReifiedConfig::default_config(), graph: Graph::new(), root_date: start_date }
+		Self {
+			config: ReifiedConfig::default_config(),
+			graph: Graph::new(),
+			root_date: start_date,
+			calendars: Calendars::default(),
+			timezone: Timezone::default(),
+		}
 	}
 
 	/// Create a new builder with a date parsed from an ISO string.
@@ -193,6 +208,22 @@ impl RoadlineBuilder {
 		Ok(Self::with_start_date(date))
 	}
 
+	/// Set the working calendars durations in working days, e.g. `10 workdays`, are resolved with.
+	pub fn with_calendars(mut self, calendars: Calendars) -> Self {
+		self.calendars = calendars;
+		self
+	}
+
+	/// Set the timezone of the roadmap, UTC by default.
+	///
+	/// Working days are counted and the days of the grid start at midnight in this timezone.
+	/// The root date is an instant and is not moved; to start at local midnight, build with
+	/// `RoadlineBuilder::with_start_date(timezone.midnight(day))`.
+	pub fn with_timezone(mut self, timezone: Timezone) -> Self {
+		self.timezone = timezone;
+		self
+	}
+
 	/// Set the connection trim (horizontal gutter space for arrows).
 	pub fn with_trim(mut self, trim: Trim) -> Self {
 		self.config.connection_trim = trim;
@@ -327,6 +358,11 @@ impl RoadlineBuilder {
 		&self.root_date
 	}
 
+	/// Get the timezone of the roadmap.
+	pub fn timezone(&self) -> Timezone {
+		self.timezone
+	}
+
 	/// Get the current visual configuration.
 	pub fn config(&self) -> &ReifiedConfig {
 		&self.config
@@ -349,6 +385,7 @@ impl RoadlineBuilder {
 		BuilderSummary {
 			task_count: self.task_count(),
 			root_date: self.root_date.clone(),
+			timezone: self.timezone,
 			trim_units: self.config.connection_trim.value().value(),
 			padding_units: self.config.inter_lane_padding.value().value(),
 		}
@@ -366,7 +403,10 @@ impl RoadlineBuilder {
 		}
 
 		// Step 1: Build the range algebra (temporal positioning)
-		let range_algebra = PreRangeAlgebra::new(self.graph).compute(self.root_date)?;
+		let range_algebra = PreRangeAlgebra::new(self.graph)
+			.with_calendars(self.calendars)
+			.with_timezone(self.timezone)
+			.compute(self.root_date)?;
 
 		// Step 2: Build the grid algebra (discrete placement)
 		let grid_algebra = PreGridAlgebra::new(range_algebra).compute()?;
@@ -565,6 +605,11 @@ impl Roadline {
 		self.reified.grid().range_algebra()
 	}
 
+	/// The timezone the roadmap is planned in; display dates in it with [`Timezone::local`].
+	pub fn timezone(&self) -> Timezone {
+		self.range_algebra().timezone()
+	}
+
 	/// Gets the task for a given task id.
 	pub fn task(&self, task_id: &TaskId) -> Option<&Task> {
 		self.reified.grid().task(task_id)
@@ -695,6 +740,14 @@ mod tests {
 		let display_str = format!("{}", summary);
 		assert!(display_str.contains("0 tasks"));
 		assert!(display_str.contains("15×3"));
+
+		// The root date is shown in the timezone of the roadmap
+		let berlin = Timezone::new(chrono_tz::Europe::Berlin);
+		let day = builder.root_date().inner().date_naive();
+		let summary = RoadlineBuilder::with_start_date(berlin.midnight(day))
+			.with_timezone(berlin)
+			.summary();
+		assert!(format!("{summary}").contains("root: 2000-01-01T00:00:00+01:00 (Europe/Berlin)"));
 	}
 
 	#[test]
@@ -0,0 +1,404 @@
+//! Monte Carlo forecasting of a roadline from three-point estimates.
+//!
+//! Each run samples a duration for every task with an [`Estimate`] and pushes tasks back
+//! whenever a dependency now ends after the task was planned to start. Only the work still to
+//! do is sampled, so the further a task has progressed, the less it varies. Tasks without an
+//! estimate keep their planned duration, and actual start and finish dates are kept. The runs
+//! are summarized as P50/P80/P95 finish dates and as how often each task was on the critical
+//! path.
+
+use crate::graph::GraphError;
+use crate::range_algebra::{
+	add_duration_to_date, forward_pass, planned_end, Date, PreRangeAlgebra, RangeAlgebra,
+	RangeAlgebraError,
+};
+use roadline_util::task::{Estimate, Id as TaskId, Task};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Error types for Simulation operations
+#[derive(Error, Debug)]
+pub enum SimulationError {
+	#[error("Range algebra error: {0}")]
+	RangeAlgebra(#[from] RangeAlgebraError),
+	#[error("Graph error: {0}")]
+	Graph(#[from] GraphError),
+	#[error("A simulation needs at least one run")]
+	NoRuns,
+}
+
+/// The 50th, 80th and 95th percentiles of a finish date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Percentiles {
+	pub p50: Date,
+	pub p80: Date,
+	pub p95: Date,
+}
+
+impl Percentiles {
+	/// Computes nearest-rank percentiles of a non-empty list of dates.
+	fn from_dates(mut dates: Vec<Date>) -> Option<Self> {
+		dates.sort();
+		let rank = |p: f64| {
+			let index = (p * dates.len() as f64).ceil() as usize;
+			dates.get(index.saturating_sub(1)).copied()
+		};
+
+		Some(Self { p50: rank(0.5)?, p80: rank(0.8)?, p95: rank(0.95)? })
+	}
+}
+
+/// The forecast for a single task.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TaskForecast {
+	/// The percentiles of the date the task finished on.
+	pub finish: Percentiles,
+	/// The fraction of runs in which the task was on the critical path.
+	pub critical: f64,
+}
+
+/// A small, seeded SplitMix64 generator, so that a seed always gives the same forecast.
+#[derive(Debug, Clone)]
+struct Rng(u64);
+
+impl Rng {
+	fn next_u64(&mut self) -> u64 {
+		self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+		let mut z = self.0;
+		z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+		z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+		z ^ (z >> 31)
+	}
+
+	/// A uniform sample from `[0, 1)`.
+	fn next_f64(&mut self) -> f64 {
+		(self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+	}
+}
+
+/// A mutable structure used to set up a Monte Carlo simulation of a range algebra.
+/// Must be consumed to create a `Simulation`.
+#[derive(Debug)]
+pub struct PreSimulation {
+	/// The range algebra the planned schedule is computed with.
+	range_algebra: PreRangeAlgebra,
+	/// The number of runs.
+	runs: usize,
+	/// The seed of the random number generator.
+	seed: u64,
+}
+
+impl PreSimulation {
+	/// The number of runs used unless specified otherwise.
+	pub const DEFAULT_RUNS: usize = 1000;
+
+	pub fn new(range_algebra: PreRangeAlgebra) -> Self {
+		Self { range_algebra, runs: Self::DEFAULT_RUNS, seed: 0 }
+	}
+
+	/// Sets the number of runs.
+	pub fn with_runs(self, runs: usize) -> Self {
+		Self { runs, ..self }
+	}
+
+	/// Sets the seed of the random number generator.
+	pub fn with_seed(self, seed: u64) -> Self {
+		Self { seed, ..self }
+	}
+
+	/// Computes the planned schedule and simulates it, returning an immutable Simulation.
+	///
+	/// Algorithm:
+	/// 1. Compute the planned spans, which also validates the roadline
+	/// 2. For each run, push the tasks through the graph with durations sampled from their
+	///    estimates, remembering which task drove the start of each task
+	/// 3. Follow the drivers back from the task which finished last to find the critical path
+	/// 4. Summarize the finish dates and critical path counts
+	pub fn compute(self, root_date: Date) -> Result<Simulation, SimulationError> {
+		if self.runs == 0 {
+			return Err(SimulationError::NoRuns);
+		}
+
+		let plan = self.range_algebra.compute(root_date)?;
+		let order = plan.graph().topological_sort()?;
+
+		let (calendars, timezone) = (plan.calendars(), plan.timezone());
+		let mut rng = Rng(self.seed);
+		let mut finishes: HashMap<TaskId, Vec<Date>> = HashMap::new();
+		let mut critical: HashMap<TaskId, usize> = HashMap::new();
+		let mut roadline_finishes = Vec::with_capacity(self.runs);
+
+		for _ in 0..self.runs {
+			let pass = forward_pass(
+				plan.graph(),
+				&order,
+				root_date,
+				calendars,
+				timezone,
+				|task, start| match task.range.estimate() {
+					Some(estimate) => Self::sampled_end(&plan, task, estimate, start, &mut rng),
+					None => planned_end(calendars, timezone, task, start),
+				},
+			)?;
+
+			for (task_id, span) in &pass.spans {
+				finishes.entry(*task_id).or_default().push(span.end.inner());
+			}
+
+			// The critical path ends with the task which finished last
+			let path = pass.critical_path();
+			if let Some(span) = path.last().and_then(|task_id| pass.spans.get(task_id)) {
+				roadline_finishes.push(span.end.inner());
+			}
+			for task_id in path {
+				*critical.entry(task_id).or_default() += 1;
+			}
+		}
+
+		let tasks = finishes
+			.into_iter()
+			.filter_map(|(task_id, dates)| {
+				let forecast = TaskForecast {
+					finish: Percentiles::from_dates(dates)?,
+					critical: critical.get(&task_id).copied().unwrap_or_default() as f64
+						/ self.runs as f64,
+				};
+				Some((task_id, forecast))
+			})
+			.collect();
+		let finish = Percentiles::from_dates(roadline_finishes).ok_or(SimulationError::NoRuns)?;
+
+		Ok(Simulation { plan, runs: self.runs, seed: self.seed, tasks, finish })
+	}
+
+	/// The end of a task starting at `start`, after a duration sampled from its estimate.
+	///
+	/// An estimate counted in working days is sampled in whole working days, which are
+	/// resolved through the calendar of the task like [`planned_end`] does.
+	fn sampled_end(
+		plan: &RangeAlgebra,
+		task: &Task,
+		estimate: &Estimate,
+		start: Date,
+		rng: &mut Rng,
+	) -> Date {
+		let duration = Self::sample(estimate, task.progress().percent_complete(), rng);
+		if !estimate.is_in_workdays() {
+			return add_duration_to_date(start, duration);
+		}
+
+		let workdays = Estimate::whole_days(duration);
+		plan.calendars()
+			.for_task(task)
+			.add_workdays_in(start, workdays, plan.timezone())
+	}
+
+	/// Samples a duration from the triangular distribution spanned by an estimate.
+	///
+	/// The share of the task which is complete takes its likely duration, and only the
+	/// remaining `(1 - progress) × duration` is sampled.
+	fn sample(estimate: &Estimate, percent_complete: u8, rng: &mut Rng) -> std::time::Duration {
+		let done = f64::from(percent_complete) / 100.0;
+		let likely: std::time::Duration = (*estimate.likely()).into();
+		likely.mul_f64(done) + estimate.quantile(rng.next_f64()).mul_f64(1.0 - done)
+	}
+}
+
+/// An immutable structure containing the result of a Monte Carlo simulation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Simulation {
+	/// The planned schedule, with every task at its likely duration.
+	plan: RangeAlgebra,
+	/// The number of runs.
+	runs: usize,
+	/// The seed of the random number generator.
+	seed: u64,
+	/// The forecast for each task.
+	tasks: HashMap<TaskId, TaskForecast>,
+	/// The percentiles of the date the whole roadline finished on.
+	finish: Percentiles,
+}
+
+impl Simulation {
+	/// Get the planned schedule.
+	pub fn plan(&self) -> &RangeAlgebra {
+		&self.plan
+	}
+
+	pub fn runs(&self) -> usize {
+		self.runs
+	}
+
+	pub fn seed(&self) -> u64 {
+		self.seed
+	}
+
+	/// Get the forecasts for all tasks.
+	pub fn tasks(&self) -> &HashMap<TaskId, TaskForecast> {
+		&self.tasks
+	}
+
+	/// Get the forecast for a specific task.
+	pub fn task(&self, task_id: &TaskId) -> Option<&TaskForecast> {
+		self.tasks.get(task_id)
+	}
+
+	/// Get the percentiles of the date the whole roadline finished on.
+	pub fn finish(&self) -> &Percentiles {
+		&self.finish
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use crate::graph::Graph;
+	use chrono::{TimeZone, Utc};
+	use roadline_util::task::Progress;
+	use std::time::Duration as StdDuration;
+
+	fn days(n: u64) -> StdDuration {
+		StdDuration::from_secs(n * 24 * 60 * 60)
+	}
+
+	fn root_date() -> Result<Date, anyhow::Error> {
+		let date = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).single();
+		Ok(Date::new(date.ok_or_else(|| anyhow::anyhow!("invalid date"))?))
+	}
+
+	/// T1 and T2 run side by side and T3 waits for both, planned to start when T1 ends.
+	fn create_uncertain_graph() -> Result<Graph, anyhow::Error> {
+		let mut graph = Graph::new();
+
+		let task1 = Task::test_from_id(1)?.for_standard_duration(days(30));
+		graph.add(task1.clone())?;
+
+		let task2 = Task::test_from_id(2)?.with_estimate(Estimate::new(
+			days(10).into(),
+			days(20).into(),
+			days(60).into(),
+		));
+		graph.add(task2)?;
+
+		let task3 = Task::test_from_id(3)?
+			.after(&task1)
+			.for_standard_duration(days(10))
+			.with_dependencies([1, 2]);
+		graph.add(task3)?;
+
+		Ok(graph)
+	}
+
+	#[test]
+	fn test_monte_carlo_forecast() -> Result<(), anyhow::Error> {
+		let root_date = root_date()?;
+		let simulation = PreSimulation::new(PreRangeAlgebra::new(create_uncertain_graph()?))
+			.with_runs(2000)
+			.with_seed(7)
+			.compute(root_date)?;
+
+		// A task without an estimate and without dependencies always finishes as planned
+		let planned = add_duration_to_date(root_date, days(30));
+		let task1 = simulation.task(&TaskId::new(1)).ok_or_else(|| anyhow::anyhow!("no T1"))?;
+		assert_eq!(task1.finish, Percentiles { p50: planned, p80: planned, p95: planned });
+
+		// T3 is pushed back whenever T2 takes longer than 30 days, about 45% of the time
+		let task2 = simulation.task(&TaskId::new(2)).ok_or_else(|| anyhow::anyhow!("no T2"))?;
+		let task3 = simulation.task(&TaskId::new(3)).ok_or_else(|| anyhow::anyhow!("no T3"))?;
+		assert!((0.4..0.5).contains(&task2.critical), "{}", task2.critical);
+		assert!((task1.critical + task2.critical - 1.0).abs() < 1e-9);
+		assert_eq!(task3.critical, 1.0);
+
+		let finish = simulation.finish();
+		assert_eq!(finish.p50, add_duration_to_date(root_date, days(40)));
+		assert!(finish.p80 > finish.p50 && finish.p95 > finish.p80);
+		assert!(finish.p95 <= add_duration_to_date(root_date, days(70)));
+		assert_eq!(task3.finish, *finish);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_only_remaining_work_is_sampled() -> Result<(), anyhow::Error> {
+		let root_date = root_date()?;
+		let finish = |percent| -> Result<Percentiles, anyhow::Error> {
+			let mut graph = Graph::new();
+			graph.add(
+				Task::test_from_id(1)?
+					.with_estimate(Estimate::new(days(10).into(), days(20).into(), days(60).into()))
+					.with_progress(Progress::new().with_percent_complete(percent)),
+			)?;
+			let simulation = PreSimulation::new(PreRangeAlgebra::new(graph))
+				.with_runs(500)
+				.with_seed(3)
+				.compute(root_date)?;
+			Ok(*simulation.finish())
+		};
+
+		// A task which is all but finished takes its likely duration
+		let likely = add_duration_to_date(root_date, days(20));
+		assert_eq!(finish(100)?, Percentiles { p50: likely, p80: likely, p95: likely });
+
+		// Halfway through, at most half of the pessimistic overrun is left
+		let halfway = finish(50)?;
+		assert!(halfway.p95 > likely);
+		assert!(halfway.p95 <= add_duration_to_date(root_date, days(40)));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_workday_estimates_are_sampled_in_working_days() -> Result<(), anyhow::Error> {
+		let finish = |percent| -> Result<Percentiles, anyhow::Error> {
+			let mut graph = Graph::new();
+			let estimate = Estimate::new(days(5).into(), days(10).into(), days(20).into());
+			graph.add(
+				Task::test_from_id(1)?
+					.with_estimate(estimate.in_workdays())
+					.with_progress(Progress::new().with_percent_complete(percent)),
+			)?;
+			let simulation = PreSimulation::new(PreRangeAlgebra::new(graph))
+				.with_runs(200)
+				.with_seed(5)
+				.compute(root_date()?)?;
+			Ok(*simulation.finish())
+		};
+
+		let january = |day| {
+			let date = Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).single();
+			date.map(Date::new).ok_or_else(|| anyhow::anyhow!("invalid date"))
+		};
+
+		// Ten working days from Wednesday 1 January skip two weekends
+		let likely = january(15)?;
+		assert_eq!(finish(100)?, Percentiles { p50: likely, p80: likely, p95: likely });
+
+		// Twenty working days at most
+		let pessimistic = january(29)?;
+		assert!(finish(0)?.p95 <= pessimistic);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_seeded_runs_are_reproducible() -> Result<(), anyhow::Error> {
+		let simulate = |seed| -> Result<Simulation, anyhow::Error> {
+			Ok(PreSimulation::new(PreRangeAlgebra::new(create_uncertain_graph()?))
+				.with_runs(200)
+				.with_seed(seed)
+				.compute(root_date()?)?)
+		};
+
+		assert_eq!(simulate(1)?.tasks(), simulate(1)?.tasks());
+		assert!(matches!(
+			PreSimulation::new(PreRangeAlgebra::new(create_uncertain_graph()?))
+				.with_runs(0)
+				.compute(root_date()?),
+			Err(SimulationError::NoRuns)
+		));
+
+		Ok(())
+	}
+}
@@ -0,0 +1,135 @@
+//! The timezone a roadmap is planned in.
+//!
+//! Spans are stored as instants in UTC. Calendar days, such as the start date of a document,
+//! the days counted by working calendars and the day boundaries of the grid, are resolved in
+//! the timezone of the roadmap, so that a task starting on Monday starts at local midnight.
+
+use crate::range_algebra::Date;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TimezoneError {
+	#[error("unknown timezone '{name}', expected an IANA name such as 'Europe/Berlin'")]
+	Unknown { name: String },
+}
+
+/// An IANA timezone, UTC by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Timezone(Tz);
+
+impl Default for Timezone {
+	fn default() -> Self {
+		Self::utc()
+	}
+}
+
+impl Timezone {
+	pub fn new(tz: Tz) -> Self {
+		Self(tz)
+	}
+
+	pub fn utc() -> Self {
+		Self(Tz::UTC)
+	}
+
+	/// Parse an IANA timezone name, e.g. `Europe/Berlin` or `UTC`.
+	pub fn parse(name: &str) -> Result<Self, TimezoneError> {
+		Tz::from_str(name.trim())
+			.map(Self)
+			.map_err(|_| TimezoneError::Unknown { name: name.trim().to_string() })
+	}
+
+	/// The IANA name of the timezone.
+	pub fn name(&self) -> &'static str {
+		self.0.name()
+	}
+
+	pub fn tz(&self) -> Tz {
+		self.0
+	}
+
+	pub fn is_utc(&self) -> bool {
+		self.0 == Tz::UTC
+	}
+
+	/// The instant a day starts at in this timezone.
+	///
+	/// Where a clock change skips midnight, the day starts at the first local time after it.
+	pub fn midnight(&self, day: NaiveDate) -> Date {
+		let midnight = day.and_time(NaiveTime::MIN);
+		let start = (0..=24)
+			.find_map(|hours| {
+				self.0.from_local_datetime(&(midnight + TimeDelta::hours(hours))).earliest()
+			})
+			.map(|local| local.with_timezone(&Utc))
+			.unwrap_or_else(|| midnight.and_utc());
+
+		Date::new(start)
+	}
+
+	/// A date as a local time in this timezone, for display.
+	pub fn local(&self, date: Date) -> DateTime<Tz> {
+		date.inner().with_timezone(&self.0)
+	}
+
+	/// The local calendar day a date falls on.
+	pub fn local_date(&self, date: Date) -> NaiveDate {
+		self.local(date).date_naive()
+	}
+
+	/// The start of the local day a date falls on.
+	pub fn start_of_day(&self, date: Date) -> Date {
+		self.midnight(self.local_date(date))
+	}
+}
+
+impl FromStr for Timezone {
+	type Err = TimezoneError;
+
+	fn from_str(name: &str) -> Result<Self, Self::Err> {
+		Self::parse(name)
+	}
+}
+
+impl fmt::Display for Timezone {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(self.name())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn date(year: i32, month: u32, day: u32) -> Result<NaiveDate, anyhow::Error> {
+		NaiveDate::from_ymd_opt(year, month, day).ok_or(anyhow::anyhow!("invalid date"))
+	}
+
+	#[test]
+	fn test_local_midnight() -> Result<(), anyhow::Error> {
+		let berlin = Timezone::parse("Europe/Berlin")?;
+		let monday = date(2025, 1, 6)?;
+
+		// Midnight in Berlin is 23:00 UTC on the day before
+		let start = berlin.midnight(monday);
+		assert_eq!(start.inner().to_rfc3339(), "2025-01-05T23:00:00+00:00");
+		assert_eq!(berlin.local_date(start), monday);
+		assert_eq!(Timezone::utc().local_date(start), date(2025, 1, 5)?);
+		assert_eq!(berlin.start_of_day(Date::new(start.inner() + TimeDelta::hours(20))), start);
+
+		// Santiago skips midnight when summer time starts
+		let santiago = Timezone::parse("America/Santiago")?;
+		let start = santiago.midnight(date(2024, 9, 8)?);
+		assert_eq!(santiago.local(start).to_rfc3339(), "2024-09-08T01:00:00-03:00");
+
+		assert!(Timezone::parse("Mars/Olympus_Mons").is_err());
+		assert_eq!(Timezone::default().to_string(), "UTC");
+
+		Ok(())
+	}
+}
@@ -0,0 +1,464 @@
+//! Workload of the leads of a roadline over time.
+//!
+//! A lead carries every task they lead, and every task with a subtask they lead, for the
+//! whole span of the task. The load of a lead is a step function: a list of intervals over
+//! which the set of concurrent tasks stays the same. A lead is over-allocated wherever they
+//! carry more than a configurable number of tasks in parallel.
+//!
+//! Leveling proposes delays which resolve over-allocation. Only tasks with free float are
+//! delayed, and never beyond it, so no other task moves and the finish date of the roadline
+//! holds; over-allocation which cannot be resolved that way is left for a person to decide.
+
+use crate::range_algebra::{Date, RangeAlgebra};
+use roadline_util::task::subtask::Lead;
+use roadline_util::task::{Id as TaskId, Task};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The default number of tasks a lead may carry in parallel.
+pub const DEFAULT_MAX_PARALLEL: usize = 1;
+
+/// An interval over which a lead carries the same set of tasks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkloadStep {
+	pub start: Date,
+	pub end: Date,
+	pub tasks: BTreeSet<TaskId>,
+}
+
+impl WorkloadStep {
+	/// The number of tasks carried in parallel.
+	pub fn load(&self) -> usize {
+		self.tasks.len()
+	}
+}
+
+/// The workload of a single lead, as a step function over time.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LeadWorkload {
+	steps: Vec<WorkloadStep>,
+}
+
+impl LeadWorkload {
+	/// Builds the step function of a set of spans, from the first start to the last end.
+	///
+	/// Consecutive intervals carrying the same tasks are merged, and intervals between tasks
+	/// carry none.
+	fn from_spans(spans: &BTreeMap<TaskId, (Date, Date)>) -> Self {
+		let boundaries: BTreeSet<Date> =
+			spans.values().flat_map(|(start, end)| [*start, *end]).collect();
+		let boundaries: Vec<Date> = boundaries.into_iter().collect();
+
+		let mut steps: Vec<WorkloadStep> = Vec::new();
+		for window in boundaries.windows(2) {
+			let (start, end) = (window[0], window[1]);
+			let tasks: BTreeSet<TaskId> = spans
+				.iter()
+				.filter(|(_, (task_start, task_end))| *task_start <= start && *task_end >= end)
+				.map(|(task_id, _)| *task_id)
+				.collect();
+
+			match steps.last_mut() {
+				Some(last) if last.tasks == tasks => last.end = end,
+				_ => steps.push(WorkloadStep { start, end, tasks }),
+			}
+		}
+
+		Self { steps }
+	}
+
+	/// The steps of the workload, in order of time.
+	pub fn steps(&self) -> &[WorkloadStep] {
+		&self.steps
+	}
+
+	/// The most tasks carried in parallel at any time.
+	pub fn peak(&self) -> usize {
+		self.steps.iter().map(WorkloadStep::load).max().unwrap_or(0)
+	}
+
+	/// The load at a date, counting tasks which start on it but not tasks which end on it.
+	pub fn load_at(&self, date: Date) -> usize {
+		self.steps
+			.iter()
+			.find(|step| step.start <= date && date < step.end)
+			.map_or(0, WorkloadStep::load)
+	}
+
+	/// The steps carrying more than `max_parallel` tasks.
+	pub fn over_allocations(&self, max_parallel: usize) -> impl Iterator<Item = &WorkloadStep> {
+		self.steps.iter().filter(move |step| step.load() > max_parallel)
+	}
+}
+
+/// A single over-allocated interval of a lead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverAllocation<'a> {
+	pub lead: &'a Lead,
+	pub step: &'a WorkloadStep,
+}
+
+/// The workload of every lead of a roadline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workload {
+	max_parallel: usize,
+	leads: BTreeMap<Lead, LeadWorkload>,
+}
+
+impl Workload {
+	/// Computes the workload of spans, grouping the tasks of the graph by lead.
+	fn from_spans(
+		range_algebra: &RangeAlgebra,
+		spans: &HashMap<TaskId, (Date, Date)>,
+		max_parallel: usize,
+	) -> Self {
+		let mut by_lead: BTreeMap<Lead, BTreeMap<TaskId, (Date, Date)>> = BTreeMap::new();
+		for (task_id, span) in spans {
+			let Some(task) = range_algebra.task(task_id) else {
+				continue;
+			};
+			for lead in task_leads(task) {
+				by_lead.entry(lead).or_default().insert(*task_id, *span);
+			}
+		}
+
+		let leads = by_lead
+			.into_iter()
+			.map(|(lead, spans)| (lead, LeadWorkload::from_spans(&spans)))
+			.collect();
+
+		Self { max_parallel, leads }
+	}
+
+	/// The number of tasks a lead may carry in parallel.
+	pub fn max_parallel(&self) -> usize {
+		self.max_parallel
+	}
+
+	/// The workload of every lead.
+	pub fn leads(&self) -> &BTreeMap<Lead, LeadWorkload> {
+		&self.leads
+	}
+
+	/// The workload of a lead.
+	pub fn lead(&self, lead: &Lead) -> Option<&LeadWorkload> {
+		self.leads.get(lead)
+	}
+
+	/// Every over-allocated interval, by lead and then in order of time.
+	pub fn over_allocations(&self) -> Vec<OverAllocation<'_>> {
+		self.leads
+			.iter()
+			.flat_map(|(lead, workload)| {
+				workload
+					.over_allocations(self.max_parallel)
+					.map(move |step| OverAllocation { lead, step })
+			})
+			.collect()
+	}
+
+	/// The total time leads carry more than the allowed number of tasks, counting each task
+	/// too many separately.
+	pub fn overload(&self) -> chrono::Duration {
+		self.leads
+			.values()
+			.flat_map(|workload| workload.over_allocations(self.max_parallel))
+			.map(|step| {
+				(step.end.inner() - step.start.inner()) * (step.load() - self.max_parallel) as i32
+			})
+			.sum()
+	}
+
+	/// Whether any lead carries more than the allowed number of tasks at some time.
+	pub fn is_over_allocated(&self) -> bool {
+		!self.over_allocations().is_empty()
+	}
+}
+
+/// A proposed delay of a task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Delay {
+	pub task: TaskId,
+	/// The start the task is proposed to move to.
+	pub start: Date,
+	/// How far the task moves.
+	pub by: chrono::Duration,
+}
+
+/// The result of leveling: the proposed delays and the workload once they are applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leveling {
+	delays: BTreeMap<TaskId, Delay>,
+	workload: Workload,
+}
+
+impl Leveling {
+	/// The proposed delays, by task.
+	pub fn delays(&self) -> &BTreeMap<TaskId, Delay> {
+		&self.delays
+	}
+
+	/// The proposed delay of a task, if any.
+	pub fn delay(&self, task_id: &TaskId) -> Option<&Delay> {
+		self.delays.get(task_id)
+	}
+
+	/// The workload with all delays applied.
+	///
+	/// Any over-allocation left could not be resolved within the free float of the tasks.
+	pub fn workload(&self) -> &Workload {
+		&self.workload
+	}
+}
+
+/// The leads carrying a task: its own leads and the leads of its subtasks.
+fn task_leads(task: &Task) -> BTreeSet<Lead> {
+	task.leads()
+		.iter()
+		.chain(task.subtasks().into_iter().flat_map(|subtask| subtask.leads()))
+		.filter(|lead| !lead.is_unknown())
+		.cloned()
+		.collect()
+}
+
+/// Computes the workload of the leads of a range algebra.
+///
+/// Milestones take no time and are never part of a workload.
+#[derive(Debug, Clone, Copy)]
+pub struct PreWorkload {
+	max_parallel: usize,
+}
+
+impl Default for PreWorkload {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PreWorkload {
+	pub fn new() -> Self {
+		Self { max_parallel: DEFAULT_MAX_PARALLEL }
+	}
+
+	/// Sets the number of tasks a lead may carry in parallel before being over-allocated.
+	pub fn with_max_parallel(self, max_parallel: usize) -> Self {
+		Self { max_parallel }
+	}
+
+	/// Computes the workload of every lead over the planned spans.
+	pub fn compute(self, range_algebra: &RangeAlgebra) -> Workload {
+		Workload::from_spans(range_algebra, &Self::spans(range_algebra), self.max_parallel)
+	}
+
+	/// Computes the workload and proposes delays which resolve over-allocation.
+	///
+	/// The earliest over-allocated interval is resolved first, by delaying one of its tasks,
+	/// latest start first, to when the first of the other tasks ends. A task is only delayed
+	/// within its free float, the time it can move without moving a dependent or the finish
+	/// of the roadline, and only if the delay reduces the total [`Workload::overload`].
+	pub fn level(self, range_algebra: &RangeAlgebra) -> Leveling {
+		let planned = Self::spans(range_algebra);
+		let mut spans = planned.clone();
+		let mut float = Self::free_float(range_algebra, &planned);
+		let mut workload = Workload::from_spans(range_algebra, &spans, self.max_parallel);
+		let mut unresolvable: BTreeSet<(Lead, Date)> = BTreeSet::new();
+
+		loop {
+			let next = workload
+				.over_allocations()
+				.into_iter()
+				.filter(|over| !unresolvable.contains(&(over.lead.clone(), over.step.start)))
+				.min_by_key(|over| over.step.start)
+				.map(|over| (over.lead.clone(), over.step.clone()));
+			let Some((lead, step)) = next else {
+				break;
+			};
+
+			let mut candidates: Vec<TaskId> = step.tasks.iter().copied().collect();
+			candidates.sort_by_key(|task_id| std::cmp::Reverse((spans[task_id].0, *task_id)));
+
+			let overload = workload.overload();
+			let leveled = candidates.into_iter().find_map(|task_id| {
+				let (start, end) = spans[&task_id];
+				let new_start = step
+					.tasks
+					.iter()
+					.filter(|other| **other != task_id)
+					.map(|other| spans[other].1)
+					.min()?;
+				let by = new_start.inner() - start.inner();
+				if by <= chrono::Duration::zero() || by > float[&task_id] {
+					return None;
+				}
+
+				let mut trial = spans.clone();
+				trial.insert(task_id, (new_start, Date::new(end.inner() + by)));
+				let trial_workload = Workload::from_spans(range_algebra, &trial, self.max_parallel);
+				(trial_workload.overload() < overload).then_some((
+					task_id,
+					by,
+					trial,
+					trial_workload,
+				))
+			});
+
+			match leveled {
+				Some((task_id, by, trial, trial_workload)) => {
+					float.insert(task_id, float[&task_id] - by);
+					spans = trial;
+					workload = trial_workload;
+				}
+				None => {
+					unresolvable.insert((lead, step.start));
+				}
+			}
+		}
+
+		let delays = spans
+			.iter()
+			.filter(|(task_id, span)| planned[*task_id] != **span)
+			.map(|(task_id, (start, _))| {
+				let by = start.inner() - planned[task_id].0.inner();
+				(*task_id, Delay { task: *task_id, start: *start, by })
+			})
+			.collect();
+
+		Leveling { delays, workload }
+	}
+
+	/// The planned spans of all tasks which take time.
+	fn spans(range_algebra: &RangeAlgebra) -> HashMap<TaskId, (Date, Date)> {
+		range_algebra
+			.spans()
+			.iter()
+			.filter(|(task_id, span)| {
+				span.end.inner() > span.start.inner()
+					&& !range_algebra.task(task_id).is_some_and(Task::is_milestone)
+			})
+			.map(|(task_id, span)| (*task_id, (span.start.inner(), span.end.inner())))
+			.collect()
+	}
+
+	/// The free float of each task: the time until its first dependent starts, or until the
+	/// roadline finishes for tasks without dependents.
+	fn free_float(
+		range_algebra: &RangeAlgebra,
+		spans: &HashMap<TaskId, (Date, Date)>,
+	) -> HashMap<TaskId, chrono::Duration> {
+		let finish = range_algebra.spans().values().map(|span| span.end.inner()).max();
+
+		spans
+			.iter()
+			.map(|(task_id, (_, end))| {
+				let next = range_algebra
+					.graph()
+					.get_dependents(task_id)
+					.iter()
+					.filter_map(|dependent| range_algebra.span(dependent))
+					.map(|span| span.start.inner())
+					.min()
+					.or(finish)
+					.unwrap_or(*end);
+				(*task_id, (next.inner() - end.inner()).max(chrono::Duration::zero()))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::graph::Graph;
+	use crate::range_algebra::PreRangeAlgebra;
+	use chrono::{DateTime, Utc};
+	use std::time::Duration as StdDuration;
+
+	const DAY: u64 = 24 * 60 * 60;
+
+	fn test_date(iso_string: &str) -> Result<Date, anyhow::Error> {
+		Ok(Date::new(DateTime::parse_from_rfc3339(iso_string)?.with_timezone(&Utc)))
+	}
+
+	fn lead(name: &str) -> Lead {
+		Lead::new(name.to_string(), format!("{}@example.com", name.to_lowercase()))
+	}
+
+	/// T1 (Ada, 10 days) is followed by T3 (no lead, 10 days), while T2 (Ada and Grace,
+	/// 4 days) runs alongside T1 and could wait until the roadline finishes.
+	fn test_graph() -> Result<Graph, anyhow::Error> {
+		let mut graph = Graph::new();
+		let task1 = Task::test_from_id(1)?
+			.for_standard_duration(StdDuration::from_secs(10 * DAY))
+			.with_leads([lead("Ada")]);
+		graph.add(task1.clone())?;
+		let task2 = Task::test_from_id(2)?
+			.offset_start_date(StdDuration::from_secs(2 * DAY))
+			.for_standard_duration(StdDuration::from_secs(4 * DAY))
+			.with_leads([lead("Ada"), lead("Grace")]);
+		graph.add(task2)?;
+		let task3 = Task::test_from_id(3)?
+			.after(&task1)
+			.for_standard_duration(StdDuration::from_secs(10 * DAY))
+			.with_dependencies([1]);
+		graph.add(task3)?;
+
+		Ok(graph)
+	}
+
+	fn compute(graph: Graph) -> Result<RangeAlgebra, anyhow::Error> {
+		Ok(PreRangeAlgebra::new(graph).compute(test_date("2025-01-06T00:00:00Z")?)?)
+	}
+
+	#[test]
+	fn test_workload_steps() -> Result<(), anyhow::Error> {
+		let range_algebra = compute(test_graph()?)?;
+		let workload = PreWorkload::new().compute(&range_algebra);
+		let ada = workload.lead(&lead("Ada")).ok_or(anyhow::anyhow!("missing Ada"))?;
+
+		let loads: Vec<usize> = ada.steps().iter().map(WorkloadStep::load).collect();
+		assert_eq!(loads, vec![1, 2, 1]);
+		assert_eq!(ada.peak(), 2);
+		assert_eq!(ada.load_at(test_date("2025-01-09T00:00:00Z")?), 2);
+		assert_eq!(ada.load_at(test_date("2025-01-12T00:00:00Z")?), 1);
+		assert_eq!(ada.load_at(test_date("2025-01-20T00:00:00Z")?), 0);
+
+		// Only Ada carries two tasks at once, from the 8th to the 12th
+		let over = workload.over_allocations();
+		assert_eq!(over.len(), 1);
+		assert_eq!(over[0].lead, &lead("Ada"));
+		assert_eq!(over[0].step.start, test_date("2025-01-08T00:00:00Z")?);
+		assert_eq!(over[0].step.end, test_date("2025-01-12T00:00:00Z")?);
+		assert_eq!(workload.overload(), chrono::Duration::days(4));
+		assert_eq!(workload.lead(&lead("Grace")).map(LeadWorkload::peak), Some(1));
+
+		let relaxed = PreWorkload::new().with_max_parallel(2).compute(&range_algebra);
+		assert!(!relaxed.is_over_allocated());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_leveling() -> Result<(), anyhow::Error> {
+		// T1 is followed directly by T3, so T2 waits for T1 to end
+		let leveling = PreWorkload::new().level(&compute(test_graph()?)?);
+		let delay = leveling.delay(&TaskId::new(2)).ok_or(anyhow::anyhow!("missing delay"))?;
+		assert_eq!(delay.start, test_date("2025-01-16T00:00:00Z")?);
+		assert_eq!(delay.by, chrono::Duration::days(8));
+		assert_eq!(leveling.delays().len(), 1);
+		assert!(!leveling.workload().is_over_allocated());
+
+		// With T4 following T2 directly, T2 has no float and nothing can move
+		let mut graph = test_graph()?;
+		let task2 = graph.task(&TaskId::new(2)).ok_or(anyhow::anyhow!("missing T2"))?.clone();
+		let task4 = Task::test_from_id(4)?
+			.after(&task2)
+			.for_standard_duration(StdDuration::from_secs(DAY))
+			.with_dependencies([2]);
+		graph.add(task4)?;
+
+		let leveling = PreWorkload::new().level(&compute(graph)?);
+		assert!(leveling.delays().is_empty());
+		assert_eq!(leveling.workload().overload(), chrono::Duration::days(4));
+
+		Ok(())
+	}
+}
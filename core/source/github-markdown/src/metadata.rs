@@ -1,8 +1,6 @@
 //! Metadata collection for GitHub source instrumentation.
-pub mod fragment;
-
-use fragment::extract_markdown_fragments;
 use roadline_parser_markdown::error::MarkdownParseError;
+use roadline_parser_markdown::fragment::extract_markdown_fragments;
 use roadline_parser_markdown::{Instrumentation, TaskParsedEvent};
 use roadline_util::task::Id as TaskId;
 use std::collections::HashMap;
@@ -1,6 +1,7 @@
 #![warn(clippy::unwrap_used)]
 #![warn(clippy::expect_used)]
 
+pub mod markdown;
 pub mod task;
 pub mod dependency;
 pub mod short_id;
@@ -0,0 +1,134 @@
+//! A structured representation of the markdown in titles, summaries and subtask content.
+//!
+//! Parsers fill these types in so that renderers can show links, code and emphasis
+//! faithfully, while search only needs [`RichText::plain_text`].
+
+use serde::{Deserialize, Serialize};
+
+/// An inline markdown element, e.g. a run of text, a code span or a link.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Inline {
+	/// Plain text.
+	Text(String),
+	/// A code span, e.g. `` `fuste` ``.
+	Code(String),
+	/// Inline math, e.g. `$\emptyset$`.
+	Math(String),
+	/// Emphasized content, e.g. `*content*`.
+	Emphasis(Vec<Inline>),
+	/// Strong content, e.g. `**content**`.
+	Strong(Vec<Inline>),
+	/// Struck through content, e.g. `~~content~~`.
+	Strikethrough(Vec<Inline>),
+	/// A link, e.g. `[content](destination)`.
+	Link { destination: String, content: Vec<Inline> },
+	/// A line break within a paragraph.
+	Break,
+}
+
+impl Inline {
+	/// The text of the element without any markup.
+	pub fn plain_text(&self) -> String {
+		let mut text = String::new();
+		self.push_plain_text(&mut text);
+		text
+	}
+
+	fn push_plain_text(&self, text: &mut String) {
+		match self {
+			Self::Text(content) | Self::Code(content) | Self::Math(content) => {
+				text.push_str(content);
+			}
+			Self::Emphasis(content)
+			| Self::Strong(content)
+			| Self::Strikethrough(content)
+			| Self::Link { content, .. } => {
+				for inline in content {
+					inline.push_plain_text(text);
+				}
+			}
+			Self::Break => text.push(' '),
+		}
+	}
+}
+
+/// The kind of a GitHub callout such as `> [!IMPORTANT]`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CalloutKind {
+	Note,
+	Tip,
+	Important,
+	Warning,
+	Caution,
+}
+
+/// A block-level markdown element, e.g. a paragraph, a list or a quote.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Block {
+	/// A paragraph of inline content.
+	Paragraph(Vec<Inline>),
+	/// A heading, e.g. `##### Notes`.
+	Heading { level: u8, content: Vec<Inline> },
+	/// A list, numbered from `start` if it is ordered.
+	///
+	/// Each item is a list of blocks, so lists may nest.
+	List { start: Option<u64>, items: Vec<Vec<Block>> },
+	/// A block quote, which is a GitHub callout if it has a kind.
+	Quote { callout: Option<CalloutKind>, blocks: Vec<Block> },
+	/// A fenced or indented code block.
+	Code { language: Option<String>, code: String },
+	/// A thematic break, e.g. `---`.
+	Rule,
+}
+
+impl Block {
+	/// The text of the block without any markup.
+	///
+	/// Nested blocks are separated by newlines.
+	pub fn plain_text(&self) -> String {
+		match self {
+			Self::Paragraph(content) | Self::Heading { content, .. } => {
+				content.iter().map(Inline::plain_text).collect()
+			}
+			Self::List { items, .. } => items
+				.iter()
+				.map(|item| item.iter().map(Block::plain_text).collect::<Vec<_>>().join("\n"))
+				.collect::<Vec<_>>()
+				.join("\n"),
+			Self::Quote { blocks, .. } => {
+				blocks.iter().map(Block::plain_text).collect::<Vec<_>>().join("\n")
+			}
+			Self::Code { code, .. } => code.trim_end().to_string(),
+			Self::Rule => String::new(),
+		}
+	}
+}
+
+/// A parsed markdown document fragment.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RichText {
+	pub blocks: Vec<Block>,
+}
+
+impl RichText {
+	pub fn new(blocks: Vec<Block>) -> Self {
+		Self { blocks }
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.blocks.is_empty()
+	}
+
+	/// The text without any markup, with blocks separated by newlines.
+	pub fn plain_text(&self) -> String {
+		self.blocks
+			.iter()
+			.map(Block::plain_text)
+			.filter(|text| !text.is_empty())
+			.collect::<Vec<_>>()
+			.join("\n")
+	}
+}
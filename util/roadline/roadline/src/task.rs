@@ -1,22 +1,35 @@
+pub mod annotation;
 pub mod embedded_subtask;
+pub mod field_value;
 pub mod id;
+pub mod kind;
+pub mod label;
+pub mod progress;
 pub mod range;
 pub mod subtask;
+pub mod subtask_dependency;
 pub mod summary;
 pub mod title;
 
+pub use annotation::Annotation;
 pub use embedded_subtask::EmbeddedSubtask;
+pub use field_value::FieldValue;
 pub use id::Id;
-pub use range::{End, Range, Start, TargetDate};
+pub use kind::Kind;
+pub use label::Label;
+pub use progress::{Progress, ProgressStatus};
+pub use range::{End, Estimate, Range, Start, TargetDate};
 use std::time::Duration as StdDuration;
-pub use subtask::Subtask;
+pub use subtask::{Lead, Subtask};
+pub use subtask_dependency::SubtaskDependency;
 pub use summary::Summary;
 pub use title::Title;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use serde::{Deserialize, Serialize};
 
+use crate::markdown::CalloutKind;
 use crate::short_id::ShortIdError;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -25,8 +38,16 @@ pub struct Task {
 	pub id: Id,
 	/// The title of the task is the main title of the task.
 	pub title: Title,
+	/// Whether the task is ordinary work or a milestone.
+	#[serde(default)]
+	pub kind: Kind,
 	/// Which tasks the task depends on.
 	pub depends_on: BTreeSet<Id>,
+	/// Which subtasks of other tasks the task depends on.
+	///
+	/// Unlike [Task::depends_on], these only require the subtask to be done.
+	#[serde(default)]
+	pub subtask_dependencies: BTreeSet<SubtaskDependency>,
 	/// The subtasks of the task are a small finite set of subtasks and is non-recursive.
 	///
 	/// The should be embedded within the task structure.
@@ -38,6 +59,23 @@ pub struct Task {
 	pub summary: Summary,
 	/// The range of the task is the start and end date of the task.
 	pub range: Range,
+	/// The people leading the task.
+	///
+	/// Subtasks without a lead of their own are led by the first of these.
+	#[serde(default)]
+	pub leads: Vec<Lead>,
+	/// The callouts of the task, such as notes and warnings, in document order.
+	#[serde(default)]
+	pub annotations: Vec<Annotation>,
+	/// The labels of the task, e.g. `infra` or `security`.
+	#[serde(default)]
+	pub labels: BTreeSet<Label>,
+	/// Custom fields of the task, e.g. `Priority: P1`, keyed by field name.
+	#[serde(default)]
+	pub fields: BTreeMap<String, FieldValue>,
+	/// What actually happened to the task, such as when it started and finished.
+	#[serde(default)]
+	pub progress: Progress,
 }
 
 impl Task {
@@ -49,7 +87,21 @@ impl Task {
 		summary: Summary,
 		range: Range,
 	) -> Self {
-		Self { id, title, depends_on, subtasks, summary, range }
+		Self {
+			id,
+			title,
+			kind: Kind::Task,
+			depends_on,
+			subtask_dependencies: BTreeSet::new(),
+			subtasks,
+			summary,
+			range,
+			leads: Vec::new(),
+			annotations: Vec::new(),
+			labels: BTreeSet::new(),
+			fields: BTreeMap::new(),
+			progress: Progress::new(),
+		}
 	}
 
 	/// Creates a new test task.
@@ -57,10 +109,17 @@ impl Task {
 		Self {
 			id: Id::new_test(),
 			title: Title::new_test(),
+			kind: Kind::Task,
 			depends_on: BTreeSet::new(),
+			subtask_dependencies: BTreeSet::new(),
 			subtasks: BTreeSet::new(),
 			summary: Summary::new_test(),
 			range: Range::new_test(),
+			leads: Vec::new(),
+			annotations: Vec::new(),
+			labels: BTreeSet::new(),
+			fields: BTreeMap::new(),
+			progress: Progress::new(),
 		}
 	}
 
@@ -78,14 +137,58 @@ impl Task {
 		self
 	}
 
+	/// Constructs with a specified set of subtask dependencies.
+	pub fn with_subtask_dependencies(
+		mut self,
+		deps: impl IntoIterator<Item = SubtaskDependency>,
+	) -> Self {
+		self.subtask_dependencies.extend(deps);
+		self
+	}
+
+	/// Constructs with a specified kind.
+	///
+	/// A milestone is a point in time, so its duration is set to zero.
+	pub fn with_kind(self, kind: Kind) -> Self {
+		match kind {
+			Kind::Milestone => Self { kind, ..self }.for_standard_duration(StdDuration::ZERO),
+			Kind::Task => Self { kind, ..self },
+		}
+	}
+
+	/// Constructs with a specified list of leads.
+	pub fn with_leads(self, leads: impl IntoIterator<Item = Lead>) -> Self {
+		Self { leads: leads.into_iter().collect(), ..self }
+	}
+
+	/// Constructs with a specified list of annotations.
+	pub fn with_annotations(self, annotations: impl IntoIterator<Item = Annotation>) -> Self {
+		Self { annotations: annotations.into_iter().collect(), ..self }
+	}
+
+	/// Constructs with a specified set of labels.
+	pub fn with_labels(mut self, labels: impl IntoIterator<Item = impl Into<Label>>) -> Self {
+		self.labels.extend(labels.into_iter().map(Into::into));
+		self
+	}
+
+	/// Constructs with a custom field, replacing any earlier value of the field.
+	pub fn with_field(mut self, name: impl Into<String>, value: FieldValue) -> Self {
+		self.fields.insert(name.into(), value);
+		self
+	}
+
+	/// Constructs with a specified set of custom fields, replacing earlier values of the same fields.
+	pub fn with_fields(mut self, fields: impl IntoIterator<Item = (String, FieldValue)>) -> Self {
+		self.fields.extend(fields);
+		self
+	}
+
 	/// Constructs a task to start after a certain dependency ends
 	///
 	/// The start date of the task will be based on the end duration of the other task.
 	pub fn after(self, other: &Self) -> Self {
-		let start = Start::from(TargetDate {
-			point_of_reference: other.id.into(),
-			duration: other.range.end.duration().clone(),
-		});
+		let start = Start::from(TargetDate::new(other.id.into(), *other.range.end.duration()));
 
 		let range = Range::new(start, self.range.end);
 
@@ -96,12 +199,15 @@ impl Task {
 	///
 	/// The start date of the task will be based on the existing offset of the other task.
 	pub fn offset_start_date(self, offset: StdDuration) -> Self {
-		let start = Start::from(TargetDate {
-			point_of_reference: self.range.start.point_of_reference().clone(),
-			duration: std::time::Duration::from(std::time::Duration::from_secs(
-				self.range.start.duration().0.as_secs() + offset.as_secs(),
-			))
-			.into(),
+		let point_of_reference = self.range.start.point_of_reference().clone();
+		let offset_secs = self.range.start.0.signed_secs() + i128::from(offset.as_secs());
+		let duration =
+			StdDuration::from_secs(u64::try_from(offset_secs.unsigned_abs()).unwrap_or(u64::MAX))
+				.into();
+		let start = Start::from(if offset_secs < 0 {
+			TargetDate::before(point_of_reference, duration)
+		} else {
+			TargetDate::new(point_of_reference, duration)
 		});
 
 		let range = Range::new(start, self.range.end);
@@ -115,6 +221,16 @@ impl Task {
 		Self { range, ..self }
 	}
 
+	/// Constructs a task which takes a number of working days.
+	pub fn for_workdays(self, workdays: u32) -> Self {
+		Self { range: self.range.with_workdays(workdays), ..self }
+	}
+
+	/// Constructs with a three-point estimate of the duration.
+	pub fn with_estimate(self, estimate: Estimate) -> Self {
+		Self { range: self.range.with_estimate(estimate), ..self }
+	}
+
 	/// Creates a new test task from a string id.
 	pub fn test_from_id(id: u8) -> Result<Self, ShortIdError> {
 		Ok(Self::new_test().with_id(Id::new(id)))
@@ -145,6 +261,15 @@ impl Task {
 		&mut self.title
 	}
 
+	pub fn kind(&self) -> Kind {
+		self.kind
+	}
+
+	/// Whether the task is a milestone, a point in time without duration.
+	pub fn is_milestone(&self) -> bool {
+		self.kind.is_milestone()
+	}
+
 	pub fn depends_on(&self) -> &BTreeSet<Id> {
 		&self.depends_on
 	}
@@ -161,6 +286,22 @@ impl Task {
 		&mut self.depends_on
 	}
 
+	pub fn subtask_dependencies(&self) -> &BTreeSet<SubtaskDependency> {
+		&self.subtask_dependencies
+	}
+
+	pub fn subtask_dependencies_mut(&mut self) -> &mut BTreeSet<SubtaskDependency> {
+		&mut self.subtask_dependencies
+	}
+
+	/// Finds a subtask by its id.
+	pub fn subtask(&self, id: &subtask::Id) -> Option<&Subtask> {
+		self.subtasks
+			.iter()
+			.map(EmbeddedSubtask::subtask)
+			.find(|subtask| subtask.id() == id)
+	}
+
 	pub fn subtasks_mut(&mut self) -> &mut BTreeSet<EmbeddedSubtask> {
 		&mut self.subtasks
 	}
@@ -177,7 +318,68 @@ impl Task {
 		&mut self.range
 	}
 
+	pub fn leads(&self) -> &[Lead] {
+		&self.leads
+	}
+
+	pub fn leads_mut(&mut self) -> &mut Vec<Lead> {
+		&mut self.leads
+	}
+
+	pub fn annotations(&self) -> &[Annotation] {
+		&self.annotations
+	}
+
+	pub fn annotations_mut(&mut self) -> &mut Vec<Annotation> {
+		&mut self.annotations
+	}
+
+	/// Whether any annotation of the task is of the given kind, e.g. a warning.
+	pub fn has_annotation(&self, kind: CalloutKind) -> bool {
+		self.annotations.iter().any(|annotation| annotation.kind == kind)
+	}
+
+	pub fn labels(&self) -> &BTreeSet<Label> {
+		&self.labels
+	}
+
+	pub fn labels_mut(&mut self) -> &mut BTreeSet<Label> {
+		&mut self.labels
+	}
+
+	/// Whether the task carries the given label.
+	pub fn has_label(&self, label: &str) -> bool {
+		self.labels.iter().any(|candidate| candidate.as_str() == label)
+	}
+
+	pub fn fields(&self) -> &BTreeMap<String, FieldValue> {
+		&self.fields
+	}
+
+	pub fn fields_mut(&mut self) -> &mut BTreeMap<String, FieldValue> {
+		&mut self.fields
+	}
+
+	/// The value of a custom field, e.g. `Priority`.
+	pub fn field(&self, name: &str) -> Option<&FieldValue> {
+		self.fields.get(name)
+	}
+
+	/// Constructs with a specified progress.
+	pub fn with_progress(self, progress: Progress) -> Self {
+		Self { progress, ..self }
+	}
+
+	pub fn progress(&self) -> &Progress {
+		&self.progress
+	}
+
+	pub fn progress_mut(&mut self) -> &mut Progress {
+		&mut self.progress
+	}
+
+	/// Whether the task depends on nothing, neither whole tasks nor subtasks.
 	pub fn is_root(&self) -> bool {
-		self.depends_on.is_empty()
+		self.depends_on.is_empty() && self.subtask_dependencies.is_empty()
 	}
 }
@@ -0,0 +1,36 @@
+use crate::markdown::{CalloutKind, RichText};
+use crate::task::subtask::Lead;
+use serde::{Deserialize, Serialize};
+
+/// An annotation on a task.
+///
+/// Annotations come from GitHub callouts such as `> [!NOTE]` or `> [!WARNING]`.
+/// They are kept apart from the summary so that they can be shown separately,
+/// e.g. as a badge on tasks which carry a warning.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Annotation {
+	/// The kind of the callout.
+	pub kind: CalloutKind,
+	/// The person the annotation is attributed to, if any.
+	pub author: Option<Lead>,
+	/// The body of the annotation, without the attribution.
+	pub body: RichText,
+}
+
+impl Annotation {
+	pub fn new(kind: CalloutKind, author: Option<Lead>, body: RichText) -> Self {
+		Self { kind, author, body }
+	}
+
+	pub fn kind(&self) -> CalloutKind {
+		self.kind
+	}
+
+	pub fn author(&self) -> Option<&Lead> {
+		self.author.as_ref()
+	}
+
+	pub fn body(&self) -> &RichText {
+		&self.body
+	}
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+/// The value of a custom field on a task, such as `Priority: P1` or `Estimate: 3`.
+///
+/// Values are typed when they are read, so that whole numbers and booleans can be
+/// compared and grouped as such.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(untagged)]
+pub enum FieldValue {
+	Boolean(bool),
+	Integer(i64),
+	/// A comma-separated list, e.g. `Reviewers: Ada, Grace`.
+	List(Vec<String>),
+	Text(String),
+}
+
+impl FieldValue {
+	/// Reads a field value as written, keeping it as text unless it is clearly typed.
+	///
+	/// `true` and `false` are booleans, and whole numbers without leading zeros are integers,
+	/// so that ids such as `0042` stay text. Values separated by commas are lists when every
+	/// item is a name of at most two words, e.g. `Ada Lovelace, Grace Hopper`; prose which
+	/// happens to contain a comma stays text.
+	pub fn parse(value: &str) -> Self {
+		let value = value.trim();
+
+		if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") {
+			return Self::Boolean(value.eq_ignore_ascii_case("true"));
+		}
+		if Self::is_integer(value) {
+			if let Ok(integer) = value.parse() {
+				return Self::Integer(integer);
+			}
+		}
+		if let Some(items) = Self::list_items(value) {
+			return Self::List(items);
+		}
+
+		Self::Text(value.to_string())
+	}
+
+	/// Whether a value is written as a whole number, without a plus sign or leading zeros.
+	fn is_integer(value: &str) -> bool {
+		let digits = value.strip_prefix('-').unwrap_or(value);
+		!digits.is_empty()
+			&& digits.bytes().all(|byte| byte.is_ascii_digit())
+			&& (digits == "0" || !digits.starts_with('0'))
+	}
+
+	/// The items of a value which is clearly a list of names.
+	fn list_items(value: &str) -> Option<Vec<String>> {
+		if !value.contains(',') {
+			return None;
+		}
+
+		let items: Vec<&str> = value.split(',').map(str::trim).collect();
+		let names = items.iter().all(|item| {
+			let words = item.split_whitespace().count();
+			(1..=2).contains(&words) && !item.ends_with(['.', '!', '?', ':', ';'])
+		});
+
+		names.then(|| items.into_iter().map(str::to_string).collect())
+	}
+
+	pub fn as_text(&self) -> Option<&str> {
+		match self {
+			Self::Text(text) => Some(text),
+			_ => None,
+		}
+	}
+
+	pub fn as_integer(&self) -> Option<i64> {
+		match self {
+			Self::Integer(integer) => Some(*integer),
+			_ => None,
+		}
+	}
+
+	pub fn as_boolean(&self) -> Option<bool> {
+		match self {
+			Self::Boolean(boolean) => Some(*boolean),
+			_ => None,
+		}
+	}
+
+	pub fn as_list(&self) -> Option<&[String]> {
+		match self {
+			Self::List(items) => Some(items),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for FieldValue {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Boolean(boolean) => write!(f, "{boolean}"),
+			Self::Integer(integer) => write!(f, "{integer}"),
+			Self::List(items) => write!(f, "{}", items.join(", ")),
+			Self::Text(text) => write!(f, "{text}"),
+		}
+	}
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+/// The kind of a task.
+///
+/// A milestone marks a point in time, such as a release or a decision, rather than work
+/// which takes time. It is written as `### M1: Title` or with `- **Type:** milestone`
+/// and always has a zero duration.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    /// Work which takes time.
+    #[default]
+    Task,
+    /// A point in time.
+    Milestone,
+}
+
+impl Kind {
+    /// The kind named by a `Type` field, e.g. `milestone`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "task" => Some(Self::Task),
+            "milestone" => Some(Self::Milestone),
+            _ => None,
+        }
+    }
+
+    /// The letter task ids of this kind are written with, e.g. `M` in `M1`.
+    pub fn prefix(&self) -> char {
+        match self {
+            Self::Task => 'T',
+            Self::Milestone => 'M',
+        }
+    }
+
+    pub fn is_milestone(&self) -> bool {
+        *self == Self::Milestone
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Task => write!(f, "task"),
+            Self::Milestone => write!(f, "milestone"),
+        }
+    }
+}
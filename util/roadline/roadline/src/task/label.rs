@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// A label on a task, such as `infra` or `security`.
+///
+/// Labels come from a `Tags:` or `Labels:` field and are used to filter and group tasks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(transparent)]
+pub struct Label(String);
+
+impl Label {
+	pub fn new(label: impl Into<String>) -> Self {
+		Self(label.into())
+	}
+
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+impl AsRef<str> for Label {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl From<&str> for Label {
+	fn from(label: &str) -> Self {
+		Self::new(label)
+	}
+}
+
+impl std::fmt::Display for Label {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.0)
+	}
+}
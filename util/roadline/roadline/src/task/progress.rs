@@ -0,0 +1,76 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How far a task has got, derived from its [`Progress`].
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStatus {
+	NotStarted,
+	InProgress,
+	Complete,
+}
+
+/// What actually happened to a task, as opposed to its planned range.
+///
+/// Written as `- **Started:** 2025-02-03`, `- **Finished:** 2025-03-01` and
+/// `- **Progress:** 40%`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Progress {
+	/// When the task actually started.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub started: Option<DateTime<Utc>>,
+	/// When the task actually finished.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub finished: Option<DateTime<Utc>>,
+	/// How much of the task is done, from 0 to 100.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub percent_complete: Option<u8>,
+}
+
+impl Progress {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Constructs with an actual start date.
+	pub fn with_started(self, started: DateTime<Utc>) -> Self {
+		Self { started: Some(started), ..self }
+	}
+
+	/// Constructs with an actual finish date.
+	pub fn with_finished(self, finished: DateTime<Utc>) -> Self {
+		Self { finished: Some(finished), ..self }
+	}
+
+	/// Constructs with a percent complete, capped at 100.
+	pub fn with_percent_complete(self, percent: u8) -> Self {
+		Self { percent_complete: Some(percent.min(100)), ..self }
+	}
+
+	pub fn started(&self) -> Option<DateTime<Utc>> {
+		self.started
+	}
+
+	pub fn finished(&self) -> Option<DateTime<Utc>> {
+		self.finished
+	}
+
+	/// How much of the task is done, from 0 to 100.
+	///
+	/// A finished task is complete whatever its recorded percentage.
+	pub fn percent_complete(&self) -> u8 {
+		if self.finished.is_some() {
+			100
+		} else {
+			self.percent_complete.unwrap_or_default()
+		}
+	}
+
+	pub fn status(&self) -> ProgressStatus {
+		match self.percent_complete() {
+			100 => ProgressStatus::Complete,
+			0 if self.started.is_none() => ProgressStatus::NotStarted,
+			_ => ProgressStatus::InProgress,
+		}
+	}
+}
@@ -1,28 +1,38 @@
 pub mod end;
+pub mod estimate;
 pub mod point_of_reference;
 pub mod start;
 pub mod target_date;
 
 pub use end::End;
+pub use estimate::Estimate;
 pub use point_of_reference::PointOfReference;
 pub use start::Start;
-pub use target_date::TargetDate;
+pub use target_date::{Direction, TargetDate};
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration as StdDuration;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Range {
 	pub start: Start,
 	pub end: End,
+	/// A three-point estimate of the duration, whose likely duration is `end`.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub estimate: Option<Estimate>,
+	/// The duration counted in working days, e.g. `10 workdays`, which a working calendar
+	/// resolves; `end` then holds as many calendar days.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub workdays: Option<u32>,
 }
 
 impl Range {
 	pub fn new(start: Start, end: End) -> Self {
-		Self { start, end }
+		Self { start, end, estimate: None, workdays: None }
 	}
 
 	pub fn new_test() -> Self {
-		Self { start: Start::new_test(), end: End::new_test() }
+		Self { start: Start::new_test(), end: End::new_test(), estimate: None, workdays: None }
 	}
 
 	pub fn start(&self) -> &Start {
@@ -32,4 +42,31 @@ impl Range {
 	pub fn end(&self) -> &End {
 		&self.end
 	}
+
+	/// Constructs with a three-point estimate, ending after its likely duration.
+	///
+	/// An estimate counted in working days also sets [`Range::workdays`] to its likely
+	/// duration.
+	pub fn with_estimate(self, estimate: Estimate) -> Self {
+		let workdays = match estimate.is_in_workdays() {
+			true => Some(estimate.likely_workdays()),
+			false => self.workdays,
+		};
+		Self { end: End::new(*estimate.likely()), estimate: Some(estimate), workdays, ..self }
+	}
+
+	pub fn estimate(&self) -> Option<&Estimate> {
+		self.estimate.as_ref()
+	}
+
+	/// Constructs with a duration of working days, ending after as many calendar days until a
+	/// working calendar resolves it.
+	pub fn with_workdays(self, workdays: u32) -> Self {
+		let end = End::from(StdDuration::from_secs(u64::from(workdays) * 24 * 60 * 60));
+		Self { end, workdays: Some(workdays), ..self }
+	}
+
+	pub fn workdays(&self) -> Option<u32> {
+		self.workdays
+	}
 }
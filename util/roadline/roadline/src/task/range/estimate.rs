@@ -0,0 +1,83 @@
+use crate::duration::Duration;
+use std::time::Duration as StdDuration;
+
+use serde::{Deserialize, Serialize};
+
+/// A three-point estimate of how long a task takes, e.g. `2–3–6 weeks`.
+///
+/// The likely duration is the one the task is planned with; the optimistic and pessimistic
+/// durations bound it for forecasting.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Estimate {
+	pub optimistic: Duration,
+	pub likely: Duration,
+	pub pessimistic: Duration,
+	/// Whether the durations are counted in working days, e.g. `2–3–6 workdays`, which a
+	/// working calendar resolves; the durations then hold as many calendar days.
+	#[serde(default, skip_serializing_if = "std::ops::Not::not")]
+	pub workdays: bool,
+}
+
+impl Estimate {
+	pub fn new(optimistic: Duration, likely: Duration, pessimistic: Duration) -> Self {
+		Self { optimistic, likely, pessimistic, workdays: false }
+	}
+
+	/// Constructs with the durations counted in working days.
+	pub fn in_workdays(self) -> Self {
+		Self { workdays: true, ..self }
+	}
+
+	pub fn is_in_workdays(&self) -> bool {
+		self.workdays
+	}
+
+	pub fn optimistic(&self) -> &Duration {
+		&self.optimistic
+	}
+
+	pub fn likely(&self) -> &Duration {
+		&self.likely
+	}
+
+	pub fn pessimistic(&self) -> &Duration {
+		&self.pessimistic
+	}
+
+	/// Whether optimistic <= likely <= pessimistic.
+	pub fn is_ordered(&self) -> bool {
+		self.optimistic <= self.likely && self.likely <= self.pessimistic
+	}
+
+	/// The duration at quantile `u` in `[0, 1]` of the triangular distribution spanned by
+	/// the estimate.
+	pub fn quantile(&self, u: f64) -> StdDuration {
+		let a = self.optimistic.0.as_secs_f64();
+		let m = self.likely.0.as_secs_f64();
+		let b = self.pessimistic.0.as_secs_f64();
+		let u = u.clamp(0.0, 1.0);
+
+		if b <= a {
+			return self.likely.0;
+		}
+
+		let seconds = if u < (m - a) / (b - a) {
+			a + (u * (b - a) * (m - a)).sqrt()
+		} else {
+			b - ((1.0 - u) * (b - a) * (b - m)).sqrt()
+		};
+
+		StdDuration::from_secs_f64(seconds.clamp(a, b))
+	}
+
+	/// The likely number of working days, for an estimate counted in working days.
+	pub fn likely_workdays(&self) -> u32 {
+		Self::whole_days(self.likely.0)
+	}
+
+	/// The number of whole days in a duration sampled from the estimate, rounded to the
+	/// nearest day.
+	pub fn whole_days(duration: StdDuration) -> u32 {
+		(duration.as_secs_f64() / (24.0 * 60.0 * 60.0)).round() as u32
+	}
+}
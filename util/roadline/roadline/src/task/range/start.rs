@@ -1,4 +1,4 @@
-use super::{PointOfReference, TargetDate};
+use super::{Direction, PointOfReference, TargetDate};
 use crate::duration::Duration;
 
 use serde::{Deserialize, Serialize};
@@ -14,6 +14,15 @@ impl Start {
 	pub fn duration(&self) -> &Duration {
 		self.0.duration()
 	}
+
+	pub fn direction(&self) -> Direction {
+		self.0.direction()
+	}
+
+	/// The offset counted in working days, if any; see [`TargetDate::workdays`].
+	pub fn workdays(&self) -> Option<u32> {
+		self.0.workdays()
+	}
 }
 
 impl From<TargetDate> for Start {
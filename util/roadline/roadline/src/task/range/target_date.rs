@@ -2,6 +2,17 @@ use crate::duration::Duration;
 use super::PointOfReference;
 
 use serde::{Deserialize, Serialize};
+use std::time::Duration as StdDuration;
+
+/// Whether a target date lies after or before its point of reference.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    /// The duration is added to the point of reference, e.g. "T3 + 1 week".
+    #[default]
+    After,
+    /// The duration is subtracted from the point of reference, e.g. "T3 - 1 week".
+    Before,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct TargetDate {
@@ -9,12 +20,36 @@ pub struct TargetDate {
     pub point_of_reference: PointOfReference,
     /// The duration since the point of reference. 
     pub duration: Duration,
+    /// Whether the duration is counted forwards or backwards from the point of reference.
+    #[serde(default)]
+    pub direction: Direction,
+    /// The duration counted in working days, e.g. "T3 + 5 workdays", which a working
+    /// calendar resolves; `duration` then holds as many calendar days.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workdays: Option<u32>,
 }
 
 
 impl TargetDate {
+    /// Creates a target date lying `duration` after the point of reference.
+    pub fn new(point_of_reference: PointOfReference, duration: Duration) -> Self {
+        Self { point_of_reference, duration, direction: Direction::After, workdays: None }
+    }
+
+    /// Creates a target date lying `duration` before the point of reference.
+    pub fn before(point_of_reference: PointOfReference, duration: Duration) -> Self {
+        Self { point_of_reference, duration, direction: Direction::Before, workdays: None }
+    }
+
+    /// Constructs with the duration counted in working days, holding as many calendar days
+    /// until a working calendar resolves it.
+    pub fn with_workdays(self, workdays: u32) -> Self {
+        let duration = Duration::from(StdDuration::from_secs(u64::from(workdays) * 24 * 60 * 60));
+        Self { duration, workdays: Some(workdays), ..self }
+    }
+
     pub fn new_test() -> Self {
-        Self { point_of_reference: PointOfReference::new_test(), duration: Duration::new_test() }
+        Self::new(PointOfReference::new_test(), Duration::new_test())
     }
 
     pub fn duration(&self) -> &Duration {
@@ -24,4 +59,21 @@ impl TargetDate {
     pub fn point_of_reference(&self) -> &PointOfReference {
         &self.point_of_reference
     }
-}
\ No newline at end of file
+
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub fn workdays(&self) -> Option<u32> {
+        self.workdays
+    }
+
+    /// The offset from the point of reference in seconds, negative if it lies before it.
+    pub fn signed_secs(&self) -> i128 {
+        let secs = i128::from(self.duration.0.as_secs());
+        match self.direction {
+            Direction::After => secs,
+            Direction::Before => -secs,
+        }
+    }
+}
@@ -9,9 +9,10 @@ pub use id::Id;
 pub use position::Position;
 pub use title::Title;
 pub use content::Content;
-pub use status::Status;
+pub use status::{Status, StatusDate};
 pub use lead::Lead;
 
+use crate::task::range::End;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -28,11 +29,27 @@ pub struct Subtask {
     pub status: Status,
     /// The lead of the subtask.
     pub lead: Lead,
+    /// Further leads sharing the subtask with its lead.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub co_leads: Vec<Lead>,
+    /// When the subtask ends, as an offset from the start of its task.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<End>,
 }
 
 impl Subtask {
     pub fn new(id: Id, position: Position, title: Title, content: Content, status: Status, lead: Lead) -> Self {
-        Self { id, position, title, content, status, lead }
+        Self { id, position, title, content, status, lead, co_leads: Vec::new(), end: None }
+    }
+
+    /// Constructs with further leads sharing the subtask with its lead.
+    pub fn with_co_leads(self, co_leads: Vec<Lead>) -> Self {
+        Self { co_leads, ..self }
+    }
+
+    /// Constructs with an end offset from the start of the task.
+    pub fn with_end(self, end: End) -> Self {
+        Self { end: Some(end), ..self }
     }
 
     pub fn id(&self) -> &Id {
@@ -58,6 +75,19 @@ impl Subtask {
     pub fn lead(&self) -> &Lead {
         &self.lead
     }
+
+    pub fn co_leads(&self) -> &[Lead] {
+        &self.co_leads
+    }
+
+    /// The lead followed by the co-leads of the subtask.
+    pub fn leads(&self) -> impl Iterator<Item = &Lead> {
+        std::iter::once(&self.lead).chain(&self.co_leads)
+    }
+
+    pub fn end(&self) -> Option<&End> {
+        self.end.as_ref()
+    }
     
     
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::markdown::RichText;
 use serde::{Deserialize, Serialize};
 
 /// The content of a subtask.
@@ -7,11 +8,36 @@ use serde::{Deserialize, Serialize};
 /// It is also used to search for the task.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Content {
+    /// The content as written, including any markdown.
     pub text: String,
+    /// The structured markdown of the content, if it was parsed from markdown.
+    #[serde(default, skip_serializing_if = "RichText::is_empty")]
+    pub markdown: RichText,
 }
 
 impl AsRef<str> for Content {
     fn as_ref(&self) -> &str {
         &self.text
     }
+}
+
+impl Content {
+    /// Creates content from plain text.
+    pub fn new(text: String) -> Self {
+        Self { text, markdown: RichText::default() }
+    }
+
+    /// Constructs with the parsed markdown of the content.
+    pub fn with_markdown(self, markdown: RichText) -> Self {
+        Self { markdown, ..self }
+    }
+
+    /// The content without any markup, for search.
+    pub fn plain_text(&self) -> String {
+        if self.markdown.is_empty() {
+            self.text.clone()
+        } else {
+            self.markdown.plain_text()
+        }
+    }
 }
\ No newline at end of file
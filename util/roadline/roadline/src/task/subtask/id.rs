@@ -15,4 +15,9 @@ impl Id {
         Self(ShortId::new(byte))
     }   
 
+    /// The number of the subtask within its task, e.g. 3 for `T1.3`.
+    pub fn value(&self) -> u8 {
+        self.0.into()
+    }
+
 }
\ No newline at end of file
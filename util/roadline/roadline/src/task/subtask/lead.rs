@@ -16,6 +16,16 @@ impl Lead {
         Self { name, email }
     }
 
+    /// The placeholder lead of a subtask whose lead is not known.
+    pub fn unknown() -> Self {
+        Self::new("Unknown".to_string(), "unknown@example.com".to_string())
+    }
+
+    /// Whether this is the [`Lead::unknown`] placeholder.
+    pub fn is_unknown(&self) -> bool {
+        *self == Self::unknown()
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
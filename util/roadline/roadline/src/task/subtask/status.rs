@@ -1,9 +1,24 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 
+/// When a subtask was completed.
+///
+/// A subtask may be checked off without saying when, in which case the date is unknown.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct StatusDate {
-    pub date: DateTime<Utc>,
+    pub date: Option<DateTime<Utc>>,
+}
+
+impl StatusDate {
+    /// A completion on a known date.
+    pub fn on(date: DateTime<Utc>) -> Self {
+        Self { date: Some(date) }
+    }
+
+    /// A completion on an unknown date.
+    pub fn undated() -> Self {
+        Self { date: None }
+    }
 }
 
 /// The status of a subtask.
@@ -0,0 +1,35 @@
+use crate::task::subtask::Id as SubtaskId;
+use crate::task::Id;
+use serde::{Deserialize, Serialize};
+
+/// A dependency on a single subtask of another task.
+///
+/// Written as `[T1.3](#t13-...)` in `Depends-on`, it lets a task start once subtask 3
+/// of task 1 is done rather than once all of task 1 is done.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubtaskDependency {
+	/// The task the subtask belongs to.
+	pub task: Id,
+	/// The subtask depended upon.
+	pub subtask: SubtaskId,
+}
+
+impl SubtaskDependency {
+	pub fn new(task: Id, subtask: SubtaskId) -> Self {
+		Self { task, subtask }
+	}
+
+	pub fn task(&self) -> Id {
+		self.task
+	}
+
+	pub fn subtask(&self) -> SubtaskId {
+		self.subtask
+	}
+}
+
+impl std::fmt::Display for SubtaskDependency {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "T{}.{}", self.task.value(), self.subtask.value())
+	}
+}
@@ -1,3 +1,4 @@
+use crate::markdown::RichText;
 use serde::{Deserialize, Serialize};
 
 /// The summary of a task.
@@ -7,7 +8,11 @@ use serde::{Deserialize, Serialize};
 /// It is also used to search for the task.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Summary {
+    /// The summary as written, including any markdown.
     pub text: String,
+    /// The structured markdown of the summary, if it was parsed from markdown.
+    #[serde(default, skip_serializing_if = "RichText::is_empty")]
+    pub markdown: RichText,
 }
 
 impl AsRef<str> for Summary {
@@ -17,8 +22,27 @@ impl AsRef<str> for Summary {
 }
 
 impl Summary {
+    /// Creates a summary from plain text.
+    pub fn new(text: String) -> Self {
+        Self { text, markdown: RichText::default() }
+    }
+
+    /// Constructs with the parsed markdown of the summary.
+    pub fn with_markdown(self, markdown: RichText) -> Self {
+        Self { markdown, ..self }
+    }
+
+    /// The summary without any markup, for search.
+    pub fn plain_text(&self) -> String {
+        if self.markdown.is_empty() {
+            self.text.clone()
+        } else {
+            self.markdown.plain_text()
+        }
+    }
+
     /// Creates a new test summary.
     pub fn new_test() -> Self {
-        Self { text: "Test Summary".to_string() }
+        Self::new("Test Summary".to_string())
     }
 }
\ No newline at end of file
@@ -1,3 +1,4 @@
+use crate::markdown::Inline;
 use serde::{Deserialize, Serialize};
 
 /// The title of a task.
@@ -7,7 +8,11 @@ use serde::{Deserialize, Serialize};
 /// It is also used to search for the task.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Title {
+    /// The title as written, including any markdown.
     pub text: String,
+    /// The structured inline markdown of the title, if it was parsed from markdown.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub markdown: Vec<Inline>,
 }
 
 /// Implement `AsRef<str>` for `Title` to allow for easy conversion to a string.
@@ -18,8 +23,27 @@ impl AsRef<str> for Title {
 }
 
 impl Title {
+    /// Creates a title from plain text.
+    pub fn new(text: String) -> Self {
+        Self { text, markdown: Vec::new() }
+    }
+
+    /// Constructs with the parsed inline markdown of the title.
+    pub fn with_markdown(self, markdown: Vec<Inline>) -> Self {
+        Self { markdown, ..self }
+    }
+
+    /// The title without any markup, for search.
+    pub fn plain_text(&self) -> String {
+        if self.markdown.is_empty() {
+            self.text.clone()
+        } else {
+            self.markdown.iter().map(Inline::plain_text).collect()
+        }
+    }
+
     /// Creates a new test title.
     pub fn new_test() -> Self {
-        Self { text: "Test Title".to_string() }
+        Self::new("Test Title".to_string())
     }
 }
\ No newline at end of file